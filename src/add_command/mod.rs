@@ -1,8 +1,16 @@
-use clap::{AppSettings, Args, Subcommand};
+use chrono::NaiveDate;
+use clap::{AppSettings, Args, Subcommand, ValueEnum};
 use ptree::{item, TreeBuilder};
-use std::{fs, io, path};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, fs, io, path};
 
+use crate::component::{list_archived, list_components, ComponentId, Stage};
+use crate::front_matter::{self, ComponentFrontMatter, DataSourceFrontMatter};
+use crate::interview::{self, Interview};
+use crate::manifest::Manifest;
+use crate::naming::{self, NamingConfig};
 use crate::pleasant_error::PleasantErrorHandler;
+use crate::template_command;
 
 #[derive(Args)]
 pub struct Add {
@@ -15,22 +23,170 @@ pub struct Add {
 pub enum AddCommands {
     /// Adds a data source instance
     DataSource(DataSource),
+    /// Adds an exploration instance
+    Exploration(Component),
+    /// Adds a model instance
+    Model(Component),
+    /// Adds a product instance
+    Product(Component),
 }
 
-#[derive(Args)]
+/// Finds the components passed with `--uses`
+///
+/// # Arguments
+///
+/// * `uses` - The components as written by the user
+///
+/// # Errors
+///
+/// * Any of the components don't exist or are ambiguous
+pub fn resolve_uses(uses: &[String]) -> Vec<ComponentId> {
+    uses.iter()
+        .map(|reference| ComponentId::resolve(reference))
+        .collect()
+}
+
+/// Adds the stage's prefix to a new name, counting archived components so
+/// their numbers aren't reused
+///
+/// # Arguments
+///
+/// * `checked` - The name, already checked against the naming rules
+/// * `stage` - The stage the component is being added to
+/// * `naming` - The project's naming rules
+pub fn prefixed(checked: naming::Name, stage: Stage, naming: &NamingConfig) -> naming::Name {
+    let existing: Vec<String> = list_components(stage)
+        .into_iter()
+        .chain(list_archived(stage))
+        .map(|component| component.name)
+        .collect();
+
+    naming::prefix(checked, stage, naming, &existing, &front_matter::today())
+}
+
+/// The kinds of data source, each of which has its own metadata template
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DataSourceKind {
+    /// A database the data is queried from
+    Database,
+    /// An extract someone else has sent
+    Extract,
+    /// A file downloaded from a website
+    Web,
+    /// An API the data is requested from
+    Api,
+    /// Responses collected from a survey
+    Survey,
+    /// Data created by a generator
+    Synthetic,
+}
+
+/// How sensitive the data in a data source is
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Sensitivity {
+    /// Can be shared with anyone
+    Public,
+    /// Can be shared within the organisation
+    Internal,
+    /// Can only be shared with the people who need it
+    Confidential,
+    /// Can only be shared with named people, e.g personal data
+    Restricted,
+}
+
+impl fmt::Display for Sensitivity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = self
+            .to_possible_value()
+            .expect("Every sensitivity has a possible value");
+
+        write!(f, "{}", value.get_name())
+    }
+}
+
+impl fmt::Display for DataSourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = self
+            .to_possible_value()
+            .expect("Every kind has a possible value");
+
+        write!(f, "{}", value.get_name())
+    }
+}
+
+/// Gets the metadata template for a kind of data source
+///
+/// # Arguments
+///
+/// * `kind` - The kind of data source, or None for the default template
+pub fn metadata_template(kind: Option<DataSourceKind>) -> &'static str {
+    match kind {
+        Some(DataSourceKind::Database) => {
+            include_str!("../templates/data_sources/database_metadata.md")
+        }
+        Some(DataSourceKind::Extract) => {
+            include_str!("../templates/data_sources/extract_metadata.md")
+        }
+        Some(DataSourceKind::Web) => include_str!("../templates/data_sources/web_metadata.md"),
+        Some(DataSourceKind::Api) => include_str!("../templates/data_sources/api_metadata.md"),
+        Some(DataSourceKind::Survey) => {
+            include_str!("../templates/data_sources/survey_metadata.md")
+        }
+        Some(DataSourceKind::Synthetic) => {
+            include_str!("../templates/data_sources/synthetic_metadata.md")
+        }
+        None => include_str!("../templates/data_sources/default_metadata.md"),
+    }
+}
+
+/// Gets the name of the metadata template for a kind of data source
+///
+/// The name is the template's path within geoff's templates and is recorded
+/// against each metadata.md so it can be updated when the template changes.
+///
+/// # Arguments
+///
+/// * `kind` - The kind of data source, or None for the default template
+pub fn metadata_template_name(kind: Option<DataSourceKind>) -> String {
+    match kind {
+        Some(kind) => format!("data_sources/{}_metadata.md", kind),
+        None => String::from("data_sources/default_metadata.md"),
+    }
+}
+
+#[derive(Args, Clone)]
 pub struct DataSource {
     /// The name of the data source
-    #[clap(value_parser)]
-    pub name: path::PathBuf,
-    /// Flag to add a database data source
-    #[clap(short, long, conflicts_with_all(&["extract", "web"]))]
-    pub database: bool,
-    /// Flag to add an extract data source
-    #[clap(short, long, conflicts_with_all(&["database", "web"]))]
-    pub extract: bool,
-    /// Flag to add a web data source
-    #[clap(short, long, conflicts_with_all(&["extract", "database"]))]
-    pub web: bool,
+    #[clap(value_parser, required_unless_present = "from-csv")]
+    pub name: Option<path::PathBuf>,
+    /// The kind of data source, which decides the metadata template
+    #[clap(short, long, value_enum)]
+    pub kind: Option<DataSourceKind>,
+    /// The person responsible for the data source
+    #[clap(short, long, value_parser)]
+    pub owner: Option<String>,
+    /// How sensitive the data is
+    #[clap(short, long, value_enum)]
+    pub sensitivity: Option<Sensitivity>,
+    /// The date the data files must be deleted by (YYYY-MM-DD)
+    #[clap(long, value_parser)]
+    pub expires: Option<NaiveDate>,
+    /// A component this data source is derived from, can be repeated
+    #[clap(short, long, value_parser)]
+    pub uses: Vec<String>,
+    /// A CSV inventory of data sources to add, one per row
+    #[clap(long, value_parser, conflicts_with_all = &["name", "interactive", "answers"])]
+    pub from_csv: Option<path::PathBuf>,
+    /// Makes a name from the one given, keeping it as the title
+    #[clap(long)]
+    pub slugify: bool,
+    /// The name as it was written, set when the name is made from it
+    #[clap(skip)]
+    pub title: Option<String>,
+    #[clap(flatten)]
+    pub interview: Interview,
 }
 
 impl PleasantErrorHandler for DataSource {}
@@ -44,41 +200,145 @@ impl DataSource {
         }
     }
 
+    /// The name of the data source
+    ///
+    /// # Errors
+    ///
+    /// * No name was given, which is only allowed with `--from-csv`
+    pub fn name(&self) -> &path::Path {
+        self.name
+            .as_deref()
+            .expect("A data source needs a name unless --from-csv is used")
+    }
+
+    /// The name of the data source as a str
+    fn _name_str(&self) -> &str {
+        self.name()
+            .to_str()
+            .expect("Unable to convert data source name to str")
+    }
+
+    /// The title of the data source, which is its name unless the name was
+    /// made from it
+    fn _title(&self) -> &str {
+        self.title.as_deref().unwrap_or_else(|| self._name_str())
+    }
+
+    /// Checks the name against the project's naming rules
+    ///
+    /// # Arguments
+    ///
+    /// * `naming` - The project's naming rules
+    ///
+    /// # Return value
+    ///
+    /// The data source with the name made from the one given if `--slugify`
+    /// was passed, with the name given kept as its title, and the stage's
+    /// prefix added
+    ///
+    /// # Errors
+    ///
+    /// * The name breaks one of the rules
+    pub fn named(&self, naming: &NamingConfig) -> DataSource {
+        let checked = prefixed(
+            naming::check(
+                self._name_str(),
+                Some(Stage::DataSource),
+                naming,
+                self.slugify,
+            ),
+            Stage::DataSource,
+            naming,
+        );
+
+        DataSource {
+            name: Some(path::PathBuf::from(checked.name)),
+            title: checked.title,
+            ..self.clone()
+        }
+    }
+
+    /// The id of the data source as a component
+    pub fn id(&self) -> ComponentId {
+        ComponentId::new(Stage::DataSource, self._name_str())
+    }
+
     pub fn create_data_source(&self) {
-        let result = fs::create_dir(format!("data_sources/{}", self.name.display()));
+        let result = fs::create_dir(format!("data_sources/{}", self.name().display()));
 
-        self.validate_create_folder_result(&self.name, &result);
+        self.validate_create_folder_result(self.name(), &result);
     }
 
     pub fn retrieve_metadata_contents(&self) -> &str {
-        let metadata_contents: &str = if self.database {
-            include_str!("../templates/data_sources/database_metadata.md")
-        } else if self.extract {
-            include_str!("../templates/data_sources/extract_metadata.md")
-        } else if self.web {
-            include_str!("../templates/data_sources/web_metadata.md")
-        } else {
-            include_str!("../templates/data_sources/default_metadata.md")
-        };
-
-        metadata_contents
+        metadata_template(self.kind)
     }
 
     pub fn update_placeholders(&self, text: &&str) -> String {
-        let name_str = self
-            .name
-            .to_str()
-            .expect("Unable to convert data source name to str");
+        text.replace("<<<data_source_name>>>", self._title())
+    }
+
+    /// Creates the front matter for the metadata from the arguments
+    pub fn front_matter(&self) -> DataSourceFrontMatter {
+        DataSourceFrontMatter {
+            name: self._name_str().to_string(),
+            title: self.title.clone(),
+            kind: self.kind,
+            owner: self.owner.clone(),
+            sensitivity: self.sensitivity,
+            created: front_matter::today(),
+            refreshed: None,
+            expires: self
+                .expires
+                .map(|expires| expires.format("%Y-%m-%d").to_string()),
+            needs_review: None,
+            extra: BTreeMap::new(),
+        }
+    }
 
-        text.replace("<<<data_source_name>>>", name_str)
+    /// Adds the front matter block to the start of the metadata
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The metadata with placeholders replaced
+    pub fn add_front_matter(&self, text: &str) -> String {
+        front_matter::replace(text, &self.front_matter())
     }
 
     pub fn create_metadata(&self, contents: &String) {
-        let metadata_path = format!("data_sources/{}/metadata.md", self.name.display());
+        let metadata_path = format!("data_sources/{}/metadata.md", self.name().display());
         fs::write(&metadata_path, contents)
             .unwrap_or_else(|_| panic!("Unable to copy to {}", &metadata_path));
     }
 
+    /// Creates the data source with its metadata and records it in the manifest
+    ///
+    /// # Arguments
+    ///
+    /// * `uses` - The components the data source is derived from
+    /// * `answers` - The answers to the data source questions
+    pub fn add(&self, uses: &[ComponentId], answers: &BTreeMap<String, String>) {
+        self.create_data_source();
+
+        let contents =
+            self.add_front_matter(&self.update_placeholders(&self.retrieve_metadata_contents()));
+        self.create_metadata(&interview::apply(
+            &contents,
+            &interview::data_source_questions(self.kind),
+            answers,
+        ));
+
+        let mut manifest = Manifest::load();
+        template_command::remember(
+            &mut manifest,
+            path::Path::new("."),
+            &self.id().document().to_string_lossy(),
+            &metadata_template_name(self.kind),
+            self._title(),
+        );
+        manifest.add_dependencies(&self.id(), uses);
+        manifest.save();
+    }
+
     pub fn create_tree(&self) -> item::StringItem {
         let gold = console::Style::new().color256(220);
         let hd = console::Style::new().color256(194);
@@ -87,7 +347,7 @@ impl DataSource {
             .begin_child(format!(
                 "{} {}",
                 gold.apply_to("\u{1F5BF}"),
-                self.name.display()
+                self.name().display()
             ))
             .add_empty_child(format!("{} metadata.md", hd.apply_to("\u{1F5CE}")))
             .end_child()
@@ -97,5 +357,222 @@ impl DataSource {
     }
 }
 
+#[derive(Args, Clone)]
+pub struct Component {
+    /// The name of the component
+    #[clap(value_parser)]
+    pub name: path::PathBuf,
+    /// The person responsible for the component
+    #[clap(short, long, value_parser)]
+    pub owner: Option<String>,
+    /// A component this one uses, can be repeated
+    #[clap(short, long, value_parser)]
+    pub uses: Vec<String>,
+    /// Makes a name from the one given, keeping it as the title
+    #[clap(long)]
+    pub slugify: bool,
+    /// The name as it was written, set when the name is made from it
+    #[clap(skip)]
+    pub title: Option<String>,
+    #[clap(flatten)]
+    pub interview: Interview,
+}
+
+impl PleasantErrorHandler for Component {}
+
+impl Component {
+    /// The name of the component as a str
+    fn _name_str(&self) -> &str {
+        self.name
+            .to_str()
+            .expect("Unable to convert component name to str")
+    }
+
+    /// The title of the component, which is its name unless the name was
+    /// made from it
+    fn _title(&self) -> &str {
+        self.title.as_deref().unwrap_or_else(|| self._name_str())
+    }
+
+    /// Checks the name against the project's naming rules
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The stage the component is being added to
+    /// * `naming` - The project's naming rules
+    ///
+    /// # Return value
+    ///
+    /// The component with the name made from the one given if `--slugify`
+    /// was passed, with the name given kept as its title, and the stage's
+    /// prefix added
+    ///
+    /// # Errors
+    ///
+    /// * The name breaks one of the rules
+    pub fn named(&self, stage: Stage, naming: &NamingConfig) -> Component {
+        let checked = prefixed(
+            naming::check(self._name_str(), Some(stage), naming, self.slugify),
+            stage,
+            naming,
+        );
+
+        Component {
+            name: path::PathBuf::from(checked.name),
+            title: checked.title,
+            ..self.clone()
+        }
+    }
+
+    /// The id of the component within the stage
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The stage the component is being added to
+    pub fn id(&self, stage: Stage) -> ComponentId {
+        ComponentId::new(stage, self._name_str())
+    }
+
+    /// Creates the component's folder within the stage folder
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The stage the component is being added to
+    pub fn create_component(&self, stage: Stage) {
+        let result = fs::create_dir(self.id(stage).path());
+
+        self.validate_create_folder_result(&self.name, &result);
+    }
+
+    /// Gets the README template for the stage
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The stage the component is being added to
+    pub fn retrieve_readme_contents(&self, stage: Stage) -> &'static str {
+        readme_template(stage)
+    }
+
+    /// Creates the README for the component with its placeholders replaced
+    /// and front matter added
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The stage the component is being added to
+    pub fn readme_contents(&self, stage: Stage) -> String {
+        let contents = self
+            .retrieve_readme_contents(stage)
+            .replace("<<<component_name>>>", self._title());
+
+        front_matter::replace(&contents, &self.front_matter(stage))
+    }
+
+    /// Creates the front matter for the README from the arguments
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The stage the component is being added to
+    pub fn front_matter(&self, stage: Stage) -> ComponentFrontMatter {
+        ComponentFrontMatter {
+            name: self._name_str().to_string(),
+            title: self.title.clone(),
+            stage,
+            owner: self.owner.clone(),
+            created: front_matter::today(),
+            needs_review: None,
+            extra: BTreeMap::new(),
+        }
+    }
+
+    /// Writes the README into the component's folder
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The stage the component is being added to
+    /// * `contents` - The contents of the README
+    pub fn create_readme(&self, stage: Stage, contents: &str) {
+        let readme_path = self.id(stage).path().join("README.md");
+        fs::write(&readme_path, contents)
+            .unwrap_or_else(|_| panic!("Unable to copy to {}", readme_path.display()));
+    }
+
+    /// Creates the component with its README and records it in the manifest
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The stage the component is being added to
+    /// * `uses` - The components it uses
+    /// * `answers` - The answers to the component questions
+    pub fn add(&self, stage: Stage, uses: &[ComponentId], answers: &BTreeMap<String, String>) {
+        self.create_component(stage);
+        self.create_readme(
+            stage,
+            &interview::apply(
+                &self.readme_contents(stage),
+                &interview::component_questions(stage),
+                answers,
+            ),
+        );
+
+        let mut manifest = Manifest::load();
+        template_command::remember(
+            &mut manifest,
+            path::Path::new("."),
+            &self.id(stage).document().to_string_lossy(),
+            &readme_template_name(stage),
+            self._title(),
+        );
+        manifest.add_dependencies(&self.id(stage), uses);
+        manifest.save();
+    }
+
+    /// Creates a tree showing the folder and README that were added
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The stage the component was added to
+    pub fn create_tree(&self, stage: Stage) -> item::StringItem {
+        let gold = console::Style::new().color256(220);
+        let hd = console::Style::new().color256(194);
+
+        TreeBuilder::new(format!("{} {}", gold.apply_to("\u{1F5BF}"), stage.folder()))
+            .begin_child(format!(
+                "{} {}",
+                gold.apply_to("\u{1F5BF}"),
+                self.name.display()
+            ))
+            .add_empty_child(format!("{} README.md", hd.apply_to("\u{1F5CE}")))
+            .end_child()
+            .build()
+    }
+}
+
+/// Gets the README template for an exploration, model or product
+///
+/// # Arguments
+///
+/// * `stage` - The stage of the component
+///
+/// # Errors
+///
+/// * Data sources use a metadata template rather than a README
+pub fn readme_template(stage: Stage) -> &'static str {
+    match stage {
+        Stage::Exploration => include_str!("../templates/explorations/README.md"),
+        Stage::Model => include_str!("../templates/models/README.md"),
+        Stage::Product => include_str!("../templates/products/README.md"),
+        Stage::DataSource => panic!("Data sources use a metadata template"),
+    }
+}
+
+/// Gets the name of the README template for an exploration, model or product
+///
+/// # Arguments
+///
+/// * `stage` - The stage of the component
+pub fn readme_template_name(stage: Stage) -> String {
+    format!("{}/README.md", stage.folder())
+}
+
 #[cfg(test)]
 mod tests;
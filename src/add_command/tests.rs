@@ -5,32 +5,38 @@ use std::{env, fs, path};
 use test_fixtures::test_in_tmp_dir;
 
 use super::*;
+use crate::component::Stage;
 
 // ++++++++++++ //
 // _geoff_check //
 // ++++++++++++ //
 
 #[rstest]
-#[case(false, false, false)]
-#[case(true, false, false)]
-#[case(false, true, false)]
-#[case(false, false, true)]
+#[case(None)]
+#[case(Some(DataSourceKind::Database))]
+#[case(Some(DataSourceKind::Extract))]
+#[case(Some(DataSourceKind::Web))]
+#[case(Some(DataSourceKind::Api))]
+#[case(Some(DataSourceKind::Survey))]
+#[case(Some(DataSourceKind::Synthetic))]
 #[should_panic(expected = "This directory is not managed by geoff")]
-fn check_not_managed_by_geoff(
-    #[case] db_opt: bool,
-    #[case] extract_opt: bool,
-    #[case] web_opt: bool,
-) -> () {
+fn check_not_managed_by_geoff(#[case] kind: Option<DataSourceKind>) {
     test_in_tmp_dir(
         || {
             fs::create_dir_all("test_project/data_sources").unwrap();
             env::set_current_dir("test_project").unwrap();
 
             let data_source = DataSource {
-                name: path::PathBuf::from("test_data_source"),
-                database: db_opt,
-                extract: extract_opt,
-                web: web_opt,
+                name: Some(path::PathBuf::from("test_data_source")),
+                kind,
+                owner: None,
+                sensitivity: None,
+                expires: None,
+                uses: vec![],
+                from_csv: None,
+                slugify: false,
+                title: None,
+                interview: Interview::default(),
             };
 
             data_source._geoff_check().unwrap();
@@ -40,15 +46,14 @@ fn check_not_managed_by_geoff(
 }
 
 #[rstest]
-#[case(false, false, false)]
-#[case(true, false, false)]
-#[case(false, true, false)]
-#[case(false, false, true)]
-fn check_managed_by_geoff(
-    #[case] db_opt: bool,
-    #[case] extract_opt: bool,
-    #[case] web_opt: bool,
-) -> () {
+#[case(None)]
+#[case(Some(DataSourceKind::Database))]
+#[case(Some(DataSourceKind::Extract))]
+#[case(Some(DataSourceKind::Web))]
+#[case(Some(DataSourceKind::Api))]
+#[case(Some(DataSourceKind::Survey))]
+#[case(Some(DataSourceKind::Synthetic))]
+fn check_managed_by_geoff(#[case] kind: Option<DataSourceKind>) {
     test_in_tmp_dir(
         || {
             fs::create_dir_all("test_project/data_sources").unwrap();
@@ -56,10 +61,16 @@ fn check_managed_by_geoff(
             fs::File::create(".geoff").unwrap();
 
             let data_source = DataSource {
-                name: path::PathBuf::from("test_data_source"),
-                database: db_opt,
-                extract: extract_opt,
-                web: web_opt,
+                name: Some(path::PathBuf::from("test_data_source")),
+                kind,
+                owner: None,
+                sensitivity: None,
+                expires: None,
+                uses: vec![],
+                from_csv: None,
+                slugify: false,
+                title: None,
+                interview: Interview::default(),
             };
 
             data_source._geoff_check().unwrap();
@@ -73,25 +84,30 @@ fn check_managed_by_geoff(
 // ++++++++++++++++++ //
 
 #[rstest]
-#[case(false, false, false)]
-#[case(true, false, false)]
-#[case(false, true, false)]
-#[case(false, false, true)]
-fn check_data_source_is_created(
-    #[case] db_opt: bool,
-    #[case] extract_opt: bool,
-    #[case] web_opt: bool,
-) -> () {
+#[case(None)]
+#[case(Some(DataSourceKind::Database))]
+#[case(Some(DataSourceKind::Extract))]
+#[case(Some(DataSourceKind::Web))]
+#[case(Some(DataSourceKind::Api))]
+#[case(Some(DataSourceKind::Survey))]
+#[case(Some(DataSourceKind::Synthetic))]
+fn check_data_source_is_created(#[case] kind: Option<DataSourceKind>) {
     test_in_tmp_dir(
         || {
             fs::create_dir_all("test_project/data_sources").unwrap();
             env::set_current_dir("test_project").unwrap();
 
             let data_source = DataSource {
-                name: path::PathBuf::from("test_data_source"),
-                database: db_opt,
-                extract: extract_opt,
-                web: web_opt,
+                name: Some(path::PathBuf::from("test_data_source")),
+                kind,
+                owner: None,
+                sensitivity: None,
+                expires: None,
+                uses: vec![],
+                from_csv: None,
+                slugify: false,
+                title: None,
+                interview: Interview::default(),
             };
 
             data_source.create_data_source();
@@ -103,16 +119,15 @@ fn check_data_source_is_created(
 }
 
 #[rstest]
-#[case(false, false, false)]
-#[case(true, false, false)]
-#[case(false, true, false)]
-#[case(false, false, true)]
+#[case(None)]
+#[case(Some(DataSourceKind::Database))]
+#[case(Some(DataSourceKind::Extract))]
+#[case(Some(DataSourceKind::Web))]
+#[case(Some(DataSourceKind::Api))]
+#[case(Some(DataSourceKind::Survey))]
+#[case(Some(DataSourceKind::Synthetic))]
 #[should_panic(expected = "test_data_source exists")]
-fn errors_on_dir_already_exists(
-    #[case] db_opt: bool,
-    #[case] extract_opt: bool,
-    #[case] web_opt: bool,
-) -> () {
+fn errors_on_dir_already_exists(#[case] kind: Option<DataSourceKind>) {
     test_in_tmp_dir(
         || {
             fs::create_dir_all("test_project/data_sources").unwrap();
@@ -120,10 +135,16 @@ fn errors_on_dir_already_exists(
             fs::File::create("data_sources/test_data_source").unwrap();
 
             let data_source = DataSource {
-                name: path::PathBuf::from("test_data_source"),
-                database: db_opt,
-                extract: extract_opt,
-                web: web_opt,
+                name: Some(path::PathBuf::from("test_data_source")),
+                kind,
+                owner: None,
+                sensitivity: None,
+                expires: None,
+                uses: vec![],
+                from_csv: None,
+                slugify: false,
+                title: None,
+                interview: Interview::default(),
             };
 
             data_source.create_data_source();
@@ -137,12 +158,18 @@ fn errors_on_dir_already_exists(
 // ++++++++++++++++++++++++++ //
 
 #[test]
-fn gets_correct_metadata_no_options() -> () {
+fn gets_correct_metadata_no_options() {
     let data_source = DataSource {
-        name: path::PathBuf::from("test_data_source"),
-        database: false,
-        extract: false,
-        web: false,
+        name: Some(path::PathBuf::from("test_data_source")),
+        kind: None,
+        owner: None,
+        sensitivity: None,
+        expires: None,
+        uses: vec![],
+        from_csv: None,
+        slugify: false,
+        title: None,
+        interview: Interview::default(),
     };
 
     let metadata: &str = data_source.retrieve_metadata_contents();
@@ -153,12 +180,18 @@ fn gets_correct_metadata_no_options() -> () {
 }
 
 #[test]
-fn gets_correct_metadata_database() -> () {
+fn gets_correct_metadata_database() {
     let data_source = DataSource {
-        name: path::PathBuf::from("test_data_source"),
-        database: true,
-        extract: false,
-        web: false,
+        name: Some(path::PathBuf::from("test_data_source")),
+        kind: Some(DataSourceKind::Database),
+        owner: None,
+        sensitivity: None,
+        expires: None,
+        uses: vec![],
+        from_csv: None,
+        slugify: false,
+        title: None,
+        interview: Interview::default(),
     };
 
     let metadata: &str = data_source.retrieve_metadata_contents();
@@ -174,10 +207,10 @@ fn gets_correct_metadata_database() -> () {
         \n\
         ## Data dictionary\n\
         \n\
-        | data field | data type | description |\n\
-        | :--------- | :-------- | :---------- |\n\
-        | *e.g ID* | *e.g INT* | *e.g A unique identifier for each record* |\n\
-        | *e.g sepal_width* | *e.g FLOAT* | *e.g The width of the sepal part of the plant* |\n\
+        | data field | data type | description | pii |\n\
+        | :--------- | :-------- | :---------- | :-- |\n\
+        | *e.g ID* | *e.g INT* | *e.g A unique identifier for each record* | *e.g no* |\n\
+        | *e.g sepal_width* | *e.g FLOAT* | *e.g The width of the sepal part of the plant* | *e.g no* |\n\
         \n\
         ## Key people\n\
         \n\
@@ -196,12 +229,18 @@ fn gets_correct_metadata_database() -> () {
 }
 
 #[test]
-fn gets_correct_metadata_extract() -> () {
+fn gets_correct_metadata_extract() {
     let data_source = DataSource {
-        name: path::PathBuf::from("test_data_source"),
-        database: false,
-        extract: true,
-        web: false,
+        name: Some(path::PathBuf::from("test_data_source")),
+        kind: Some(DataSourceKind::Extract),
+        owner: None,
+        sensitivity: None,
+        expires: None,
+        uses: vec![],
+        from_csv: None,
+        slugify: false,
+        title: None,
+        interview: Interview::default(),
     };
 
     let metadata: &str = data_source.retrieve_metadata_contents();
@@ -217,10 +256,10 @@ fn gets_correct_metadata_extract() -> () {
         \n\
         ## Data dictionary\n\
         \n\
-        | data field | data type | description |\n\
-        | :--------- | :-------- | :---------- |\n\
-        | *e.g ID* | *e.g INT* | *e.g A unique identifier for each record* |\n\
-        | *e.g sepal_width* | *e.g FLOAT* | *e.g The width of the sepal part of the plant* |\n\
+        | data field | data type | description | pii |\n\
+        | :--------- | :-------- | :---------- | :-- |\n\
+        | *e.g ID* | *e.g INT* | *e.g A unique identifier for each record* | *e.g no* |\n\
+        | *e.g sepal_width* | *e.g FLOAT* | *e.g The width of the sepal part of the plant* | *e.g no* |\n\
         \n\
         ## Key people\n\
         \n\
@@ -239,12 +278,18 @@ fn gets_correct_metadata_extract() -> () {
 }
 
 #[test]
-fn gets_correct_metadata_web() -> () {
+fn gets_correct_metadata_web() {
     let data_source = DataSource {
-        name: path::PathBuf::from("test_data_source"),
-        database: false,
-        extract: false,
-        web: true,
+        name: Some(path::PathBuf::from("test_data_source")),
+        kind: Some(DataSourceKind::Web),
+        owner: None,
+        sensitivity: None,
+        expires: None,
+        uses: vec![],
+        from_csv: None,
+        slugify: false,
+        title: None,
+        interview: Interview::default(),
     };
 
     let metadata: &str = data_source.retrieve_metadata_contents();
@@ -260,10 +305,10 @@ fn gets_correct_metadata_web() -> () {
         \n\
         ## Data dictionary\n\
         \n\
-        | data field | data type | description |\n\
-        | :--------- | :-------- | :---------- |\n\
-        | *e.g ID* | *e.g INT* | *e.g A unique identifier for each record* |\n\
-        | *e.g sepal_width* | *e.g FLOAT* | *e.g The width of the sepal part of the plant* |\n\
+        | data field | data type | description | pii |\n\
+        | :--------- | :-------- | :---------- | :-- |\n\
+        | *e.g ID* | *e.g INT* | *e.g A unique identifier for each record* | *e.g no* |\n\
+        | *e.g sepal_width* | *e.g FLOAT* | *e.g The width of the sepal part of the plant* | *e.g no* |\n\
         \n\
         ## Key people\n\
         \n\
@@ -282,25 +327,67 @@ fn gets_correct_metadata_web() -> () {
     assert!(metadata_contents_fn.eval(metadata))
 }
 
+#[rstest]
+#[case(
+    DataSourceKind::Api,
+    "## API details\n\n| endpoint | auth method | rate limits | pagination |"
+)]
+#[case(
+    DataSourceKind::Survey,
+    "## Survey details\n\n| instrument | sample frame | response rate | consent |"
+)]
+#[case(
+    DataSourceKind::Synthetic,
+    "## Synthetic data details\n\n| generator | seed | source distribution |"
+)]
+fn gets_correct_metadata_new_kinds(#[case] kind: DataSourceKind, #[case] details: &str) {
+    let data_source = DataSource {
+        name: Some(path::PathBuf::from("test_data_source")),
+        kind: Some(kind),
+        owner: None,
+        sensitivity: None,
+        expires: None,
+        uses: vec![],
+        from_csv: None,
+        slugify: false,
+        title: None,
+        interview: Interview::default(),
+    };
+
+    let metadata = data_source
+        .retrieve_metadata_contents()
+        .replace("\r\n", "\n");
+
+    assert!(metadata.starts_with("# <<<data_source_name>>>\n"));
+    assert!(metadata.contains(details));
+    assert!(metadata.contains("## Data dictionary"));
+    assert!(metadata.contains("## Key people"));
+}
+
 // +++++++++++++++++++ //
 // update_placeholders //
 // +++++++++++++++++++ //
 
 #[rstest]
-#[case(false, false, false)]
-#[case(true, false, false)]
-#[case(false, true, false)]
-#[case(false, false, true)]
-fn replace_placeholder_tags(
-    #[case] db_opt: bool,
-    #[case] extract_opt: bool,
-    #[case] web_opt: bool,
-) -> () {
+#[case(None)]
+#[case(Some(DataSourceKind::Database))]
+#[case(Some(DataSourceKind::Extract))]
+#[case(Some(DataSourceKind::Web))]
+#[case(Some(DataSourceKind::Api))]
+#[case(Some(DataSourceKind::Survey))]
+#[case(Some(DataSourceKind::Synthetic))]
+fn replace_placeholder_tags(#[case] kind: Option<DataSourceKind>) {
     let data_source = DataSource {
-        name: path::PathBuf::from("test_data_source"),
-        database: db_opt,
-        extract: extract_opt,
-        web: web_opt,
+        name: Some(path::PathBuf::from("test_data_source")),
+        kind,
+        owner: None,
+        sensitivity: None,
+        expires: None,
+        uses: vec![],
+        from_csv: None,
+        slugify: false,
+        title: None,
+        interview: Interview::default(),
     };
 
     let replaced_str = data_source.update_placeholders(&"# <<<data_source_name>>>");
@@ -313,15 +400,14 @@ fn replace_placeholder_tags(
 // +++++++++++++++ //
 
 #[rstest]
-#[case(false, false, false)]
-#[case(true, false, false)]
-#[case(false, true, false)]
-#[case(false, false, true)]
-fn metadata_file_created(
-    #[case] db_opt: bool,
-    #[case] extract_opt: bool,
-    #[case] web_opt: bool,
-) -> () {
+#[case(None)]
+#[case(Some(DataSourceKind::Database))]
+#[case(Some(DataSourceKind::Extract))]
+#[case(Some(DataSourceKind::Web))]
+#[case(Some(DataSourceKind::Api))]
+#[case(Some(DataSourceKind::Survey))]
+#[case(Some(DataSourceKind::Synthetic))]
+fn metadata_file_created(#[case] kind: Option<DataSourceKind>) {
     test_in_tmp_dir(
         || {
             fs::create_dir_all("test_project/data_sources").unwrap();
@@ -329,10 +415,16 @@ fn metadata_file_created(
             fs::create_dir("data_sources/test_data_source").unwrap();
 
             let data_source = DataSource {
-                name: path::PathBuf::from("test_data_source"),
-                database: db_opt,
-                extract: extract_opt,
-                web: web_opt,
+                name: Some(path::PathBuf::from("test_data_source")),
+                kind,
+                owner: None,
+                sensitivity: None,
+                expires: None,
+                uses: vec![],
+                from_csv: None,
+                slugify: false,
+                title: None,
+                interview: Interview::default(),
             };
 
             data_source.create_metadata(&String::from("# test_data_source"));
@@ -347,21 +439,30 @@ fn metadata_file_created(
 // create_tree //
 // +++++++++++ //
 #[rstest]
-#[case(false, false, false)]
-#[case(true, false, false)]
-#[case(false, true, false)]
-#[case(false, false, true)]
-fn creates_tree(#[case] db_opt: bool, #[case] extract_opt: bool, #[case] web_opt: bool) -> () {
+#[case(None)]
+#[case(Some(DataSourceKind::Database))]
+#[case(Some(DataSourceKind::Extract))]
+#[case(Some(DataSourceKind::Web))]
+#[case(Some(DataSourceKind::Api))]
+#[case(Some(DataSourceKind::Survey))]
+#[case(Some(DataSourceKind::Synthetic))]
+fn creates_tree(#[case] kind: Option<DataSourceKind>) {
     test_in_tmp_dir(
         || {
             let gold = Style::new().color256(220);
             let hd = Style::new().color256(194);
 
             let data_source = DataSource {
-                name: path::PathBuf::from("test_data_source"),
-                database: db_opt,
-                extract: extract_opt,
-                web: web_opt,
+                name: Some(path::PathBuf::from("test_data_source")),
+                kind,
+                owner: None,
+                sensitivity: None,
+                expires: None,
+                uses: vec![],
+                from_csv: None,
+                slugify: false,
+                title: None,
+                interview: Interview::default(),
             };
 
             let tree = data_source.create_tree();
@@ -382,3 +483,78 @@ fn creates_tree(#[case] db_opt: bool, #[case] extract_opt: bool, #[case] web_opt
         false,
     )
 }
+
+// +++++++++ //
+// Component //
+// +++++++++ //
+fn component() -> Component {
+    Component {
+        name: path::PathBuf::from("classifier"),
+        owner: Some(String::from("Ronald Fisher")),
+        uses: vec![],
+        slugify: false,
+        title: None,
+        interview: Interview::default(),
+    }
+}
+
+#[rstest]
+#[case(Stage::Exploration, "## Findings")]
+#[case(Stage::Model, "## Performance")]
+#[case(Stage::Product, "## Deployment")]
+fn component_readme_uses_stage_template(#[case] stage: Stage, #[case] section: &str) {
+    let contents = component().readme_contents(stage);
+
+    assert!(contents.starts_with(&format!(
+        "---\nname: classifier\nstage: {}\nowner: Ronald Fisher\ncreated: ",
+        stage
+    )));
+    assert!(contents.contains("# classifier\n"));
+    assert!(contents.contains(section));
+}
+
+#[test]
+fn creates_component_folder_and_readme() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("models").unwrap();
+
+            let model = component();
+            model.create_component(Stage::Model);
+            model.create_readme(Stage::Model, &model.readme_contents(Stage::Model));
+
+            assert!(path::Path::new("models/classifier/README.md").exists());
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "classifier exists")]
+fn errors_on_component_already_exists() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("models/classifier").unwrap();
+
+            component().create_component(Stage::Model);
+        },
+        true,
+    )
+}
+
+#[test]
+fn creates_component_tree() {
+    let gold = Style::new().color256(220);
+    let hd = Style::new().color256(194);
+
+    let tree = component().create_tree(Stage::Exploration);
+
+    assert_eq!(
+        tree.text,
+        format!("{} explorations", gold.apply_to("\u{1F5BF}"))
+    );
+    assert_eq!(
+        tree.children[0].children[0].text,
+        format!("{} README.md", hd.apply_to("\u{1F5CE}"))
+    );
+}
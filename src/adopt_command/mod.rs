@@ -0,0 +1,515 @@
+use clap::{Args, ValueEnum};
+use console::Style;
+use ptree::{item, TreeBuilder};
+use std::{env, fs, io, path, str};
+
+use crate::add_command::{self, Component, DataSource};
+use crate::component::{list_components, ComponentId, Stage};
+use crate::create_command::Create;
+use crate::interview::Interview;
+use crate::manifest::Manifest;
+use crate::naming::{self, NamingConfig};
+use crate::prompt;
+use crate::template_command;
+
+/// Guesses the stage for a folder from its name
+///
+/// # Arguments
+///
+/// * `folder` - The name of a folder in the project root, e.g notebooks
+pub fn guess_stage(folder: &str) -> Option<Stage> {
+    match folder.to_lowercase().as_str() {
+        "data" | "datasets" | "raw" | "raw_data" | "input" | "inputs" => Some(Stage::DataSource),
+        "notebooks" | "analysis" | "eda" | "exploration" | "scratch" => Some(Stage::Exploration),
+        "model" | "training" | "experiments" => Some(Stage::Model),
+        "app" | "apps" | "api" | "dashboard" | "dashboards" | "reports" | "product" => {
+            Some(Stage::Product)
+        }
+        _ => None,
+    }
+}
+
+/// Where the contents of an existing folder should go
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mapping {
+    /// The folder in the project root
+    pub folder: String,
+    /// The stage it maps onto, or None to leave the folder alone
+    pub stage: Option<Stage>,
+}
+
+impl str::FromStr for Mapping {
+    type Err = String;
+
+    /// Parses a mapping written as `FOLDER=STAGE`, e.g `notebooks=exploration`
+    ///
+    /// The stage can be `skip` to leave the folder alone.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (folder, stage) = value
+            .split_once('=')
+            .ok_or_else(|| format!("{} should be written as FOLDER=STAGE", value))?;
+
+        let stage = match stage {
+            "skip" => None,
+            stage => Some(Stage::from_str(stage, true)?),
+        };
+
+        Ok(Mapping {
+            folder: folder.trim_end_matches('/').to_string(),
+            stage,
+        })
+    }
+}
+
+/// Something in a mapped folder that becomes a component
+#[derive(Clone, Debug, PartialEq)]
+pub struct Adoption {
+    /// The file or folder being adopted
+    pub from: path::PathBuf,
+    /// The component it becomes
+    pub component: ComponentId,
+    /// The file or folder's name when the component's name was made from it
+    pub title: Option<String>,
+}
+
+#[derive(Args)]
+pub struct Adopt {
+    /// The folder to adopt, defaults to the current directory
+    #[clap(default_value = ".", value_parser)]
+    pub path: path::PathBuf,
+    /// Maps a folder onto a stage as FOLDER=STAGE, e.g data=data-source,
+    /// can be repeated. Use FOLDER=skip to leave a folder alone
+    #[clap(short, long, value_parser)]
+    pub map: Vec<Mapping>,
+    /// Asks which stage each folder maps onto
+    #[clap(short, long)]
+    pub interactive: bool,
+    /// Adopts the folder without asking for confirmation
+    #[clap(short, long)]
+    pub yes: bool,
+}
+
+impl Adopt {
+    /// Changes to the folder being adopted
+    ///
+    /// # Errors
+    ///
+    /// * The folder doesn't exist
+    /// * The folder is already managed by geoff
+    pub fn enter(&self) {
+        let red = Style::new().color256(9);
+
+        if !self.path.is_dir() {
+            eprintln!(
+                "{} {}",
+                self.path.display(),
+                red.apply_to("isn't a folder that can be adopted")
+            );
+            panic!("{} doesn't exist", self.path.display());
+        }
+
+        if self.path.join(".geoff").exists() {
+            eprintln!(
+                "{} {}",
+                self.path.display(),
+                red.apply_to("is already managed by geoff")
+            );
+            panic!("{} is already a geoff project", self.path.display());
+        }
+
+        env::set_current_dir(&self.path)
+            .unwrap_or_else(|_| panic!("Unable to change to {}", self.path.display()));
+    }
+
+    /// The name of the project, taken from its folder
+    pub fn project_name(&self) -> String {
+        env::current_dir()
+            .expect("Unable to get current directory")
+            .file_name()
+            .expect("The project folder has a name")
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Lists the folders in the project root that could be mapped onto a stage
+    ///
+    /// Hidden folders and the stage folders themselves are left out.
+    pub fn folders(&self) -> Vec<String> {
+        let mut folders: Vec<String> = fs::read_dir(".")
+            .expect("Unable to read the project folder")
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|folder| !folder.starts_with('.') && Stage::from_folder(folder).is_none())
+            .collect();
+
+        folders.sort();
+        folders
+    }
+
+    /// Decides which stage each folder maps onto
+    ///
+    /// Folders passed with `--map` are used as given. The rest are guessed
+    /// from their names, or asked about when `--interactive` is passed with
+    /// the guess as the default answer.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Where interactive answers are read from
+    ///
+    /// # Errors
+    ///
+    /// * A folder passed with `--map` doesn't exist
+    pub fn mappings<R: io::BufRead>(&self, mut input: R) -> Vec<Mapping> {
+        let folders = self.folders();
+
+        for mapping in self.map.iter() {
+            if !folders.contains(&mapping.folder) {
+                eprintln!(
+                    "{} {}",
+                    mapping.folder,
+                    Style::new()
+                        .color256(9)
+                        .apply_to("isn't a folder in the project root")
+                );
+                panic!("{} doesn't exist", mapping.folder);
+            }
+        }
+
+        folders
+            .into_iter()
+            .map(|folder| {
+                if let Some(mapping) = self.map.iter().find(|mapping| mapping.folder == folder) {
+                    return mapping.clone();
+                }
+
+                let guess = guess_stage(&folder);
+                let stage = if self.interactive {
+                    _ask_stage(&folder, guess, &mut input)
+                } else {
+                    guess
+                };
+
+                Mapping { folder, stage }
+            })
+            .collect()
+    }
+
+    /// Lists what in each mapped folder becomes a component
+    ///
+    /// Sub folders become components with the same name. Files become a
+    /// component named after the file, e.g `notebooks/eda.ipynb` becomes
+    /// `explorations/eda`. Names that break the project's naming rules are
+    /// slugified, keeping the original as the component's title.
+    ///
+    /// # Arguments
+    ///
+    /// * `mappings` - The stage each folder maps onto
+    /// * `naming` - The project's naming rules
+    ///
+    /// # Errors
+    ///
+    /// * A name can't be made to follow the naming rules
+    /// * Two things would become the same component
+    /// * A component with the same name already exists
+    pub fn plan(&self, mappings: &[Mapping], naming: &NamingConfig) -> Vec<Adoption> {
+        let mut adoptions: Vec<Adoption> = Vec::new();
+
+        for mapping in mappings.iter() {
+            let stage = match mapping.stage {
+                Some(stage) => stage,
+                None => continue,
+            };
+
+            let mut entries: Vec<path::PathBuf> = fs::read_dir(&mapping.folder)
+                .unwrap_or_else(|_| panic!("Unable to read {}", mapping.folder))
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|entry| {
+                    !entry
+                        .file_name()
+                        .map(|name| name.to_string_lossy().starts_with('.'))
+                        .unwrap_or(true)
+                })
+                .collect();
+            entries.sort();
+
+            for entry in entries {
+                let name = if entry.is_dir() {
+                    entry.file_name()
+                } else {
+                    entry.file_stem()
+                }
+                .expect("Entries have a name")
+                .to_string_lossy()
+                .to_string();
+
+                let named = naming::name(&name, Some(stage), naming, true).unwrap_or_else(|err| {
+                    eprintln!(
+                        "{} {}",
+                        entry.display(),
+                        Style::new().color256(9).apply_to(naming::explain(
+                            &name,
+                            Some(stage),
+                            naming,
+                            &err
+                        ))
+                    );
+                    panic!("{} can't be adopted", entry.display())
+                });
+
+                let component = ComponentId::new(stage, &named.name);
+                if component.path().exists()
+                    || adoptions
+                        .iter()
+                        .any(|adoption| adoption.component == component)
+                {
+                    eprintln!(
+                        "{} {} {}",
+                        entry.display(),
+                        Style::new().color256(9).apply_to("would replace"),
+                        component
+                    );
+                    panic!("{} exists", component);
+                }
+
+                adoptions.push(Adoption {
+                    from: entry,
+                    component,
+                    title: named.title,
+                });
+            }
+        }
+
+        adoptions
+    }
+
+    /// Creates a tree showing where everything will go
+    ///
+    /// # Arguments
+    ///
+    /// * `adoptions` - What becomes a component
+    pub fn create_tree(&self, adoptions: &[Adoption]) -> item::StringItem {
+        let gold = Style::new().color256(220);
+        let grey = Style::new().color256(245);
+
+        let mut tree = TreeBuilder::new(format!(
+            "{}",
+            Style::new().bold().apply_to(self.project_name())
+        ));
+        for stage in Stage::ALL {
+            tree.begin_child(format!("{} {}", gold.apply_to("\u{1F5BF}"), stage.folder()));
+            for adoption in adoptions
+                .iter()
+                .filter(|adoption| adoption.component.stage == stage)
+            {
+                tree.add_empty_child(format!(
+                    "{} {} {}",
+                    gold.apply_to("\u{1F5BF}"),
+                    adoption.component.name,
+                    grey.apply_to(format!(
+                        "\u{2190} {}",
+                        adoption.from.to_string_lossy().replace('\\', "/")
+                    ))
+                ));
+            }
+            tree.end_child();
+        }
+
+        tree.build()
+    }
+
+    /// Whether the user wants to go ahead, always true when `--yes` was passed
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Where the answer is read from
+    pub fn confirm<R: io::BufRead>(&self, input: R) -> bool {
+        self.yes || prompt::confirm("Adopt this folder as a geoff project?", input)
+    }
+
+    /// Creates any stage folders that don't exist
+    pub fn create_stage_folders(&self) {
+        for stage in Stage::ALL {
+            fs::create_dir_all(stage.folder())
+                .unwrap_or_else(|_| panic!("Unable to create {}", stage.folder()));
+        }
+    }
+
+    /// Moves everything being adopted into its stage folder
+    ///
+    /// Mapped folders that are left empty are removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `adoptions` - What becomes a component
+    pub fn move_components(&self, adoptions: &[Adoption]) {
+        for adoption in adoptions.iter() {
+            let destination = adoption.component.path();
+
+            if adoption.from.is_dir() {
+                fs::rename(&adoption.from, &destination)
+            } else {
+                fs::create_dir(&destination).and_then(|_| {
+                    fs::rename(
+                        &adoption.from,
+                        destination.join(adoption.from.file_name().expect("Files have a name")),
+                    )
+                })
+            }
+            .unwrap_or_else(|_| panic!("Unable to move {}", adoption.from.display()));
+
+            if let Some(parent) = adoption.from.parent() {
+                // Only succeeds once the folder is empty
+                let _ = fs::remove_dir(parent);
+            }
+        }
+    }
+
+    /// Writes the root documents and `.geoff` marker that don't exist yet
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest, where the templates of the
+    ///   documents are recorded
+    ///
+    /// # Return value
+    ///
+    /// The files that were created
+    pub fn create_root_files(&self, manifest: &mut Manifest) -> Vec<&'static str> {
+        let project = self.project_name();
+        let create = Create {
+            name: path::PathBuf::from(&project),
+            parents: false,
+            slugify: false,
+            title: None,
+            interview: Interview::default(),
+        };
+
+        create
+            .root_files()
+            .into_iter()
+            .filter(|(filename, _)| !path::Path::new(filename).exists())
+            .map(|(filename, contents)| {
+                fs::write(filename, contents)
+                    .unwrap_or_else(|_| panic!("Unable to copy to {}", filename));
+                if !filename.starts_with('.') {
+                    template_command::remember(
+                        manifest,
+                        path::Path::new("."),
+                        filename,
+                        &format!("root/{}", filename),
+                        &project,
+                    );
+                }
+                filename
+            })
+            .collect()
+    }
+
+    /// Gives every component without a main document one from its template
+    ///
+    /// Existing documents are left as they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest, where the templates of the
+    ///   documents are recorded
+    /// * `adoptions` - What was adopted, for the titles of renamed components
+    ///
+    /// # Return value
+    ///
+    /// Every component in the project
+    pub fn register_components(
+        &self,
+        manifest: &mut Manifest,
+        adoptions: &[Adoption],
+    ) -> Vec<ComponentId> {
+        let components: Vec<ComponentId> =
+            Stage::ALL.into_iter().flat_map(list_components).collect();
+
+        for component in components
+            .iter()
+            .filter(|component| !component.document().exists())
+        {
+            let title = adoptions
+                .iter()
+                .find(|adoption| &adoption.component == component)
+                .and_then(|adoption| adoption.title.clone());
+
+            match component.stage {
+                Stage::DataSource => {
+                    let data_source = DataSource {
+                        name: Some(path::PathBuf::from(&component.name)),
+                        kind: None,
+                        owner: None,
+                        sensitivity: None,
+                        expires: None,
+                        uses: Vec::new(),
+                        from_csv: None,
+                        slugify: false,
+                        title,
+                        interview: Interview::default(),
+                    };
+                    let contents = data_source.add_front_matter(
+                        &data_source.update_placeholders(&add_command::metadata_template(None)),
+                    );
+                    data_source.create_metadata(&contents);
+                    template_command::remember(
+                        manifest,
+                        path::Path::new("."),
+                        &component.document().to_string_lossy(),
+                        &add_command::metadata_template_name(None),
+                        data_source.title.as_ref().unwrap_or(&component.name),
+                    );
+                }
+                stage => {
+                    let readme = Component {
+                        name: path::PathBuf::from(&component.name),
+                        owner: None,
+                        uses: Vec::new(),
+                        slugify: false,
+                        title,
+                        interview: Interview::default(),
+                    };
+                    readme.create_readme(stage, &readme.readme_contents(stage));
+                    template_command::remember(
+                        manifest,
+                        path::Path::new("."),
+                        &component.document().to_string_lossy(),
+                        &add_command::readme_template_name(stage),
+                        readme.title.as_ref().unwrap_or(&component.name),
+                    );
+                }
+            }
+        }
+
+        components
+    }
+}
+
+/// Asks which stage a folder maps onto until a valid answer is given
+fn _ask_stage<R: io::BufRead>(folder: &str, guess: Option<Stage>, input: &mut R) -> Option<Stage> {
+    let guess = guess.map(|stage| stage.to_string());
+    let question = format!(
+        "Which stage does {}/ map onto? (data-source, exploration, model, product or skip)",
+        folder
+    );
+
+    loop {
+        match prompt::ask(&question, guess.as_deref().or(Some("skip")), &mut *input).as_deref() {
+            Some("skip") | None => return None,
+            Some(answer) => match Stage::from_str(answer, true) {
+                Ok(stage) => return Some(stage),
+                Err(_) => eprintln!(
+                    "{} {}",
+                    answer,
+                    Style::new().color256(9).apply_to("isn't a stage")
+                ),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
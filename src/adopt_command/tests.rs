@@ -0,0 +1,254 @@
+use rstest::rstest;
+use std::fs;
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+
+fn adopt(map: &[&str], interactive: bool) -> Adopt {
+    Adopt {
+        path: path::PathBuf::from("."),
+        map: map.iter().map(|mapping| mapping.parse().unwrap()).collect(),
+        interactive,
+        yes: true,
+    }
+}
+
+fn setup_folder() {
+    fs::create_dir_all("data/iris").unwrap();
+    fs::write("data/iris/iris.csv", "id\n").unwrap();
+    fs::create_dir_all("notebooks").unwrap();
+    fs::write("notebooks/eda.ipynb", "{}").unwrap();
+    fs::create_dir_all("src").unwrap();
+    fs::create_dir_all(".git").unwrap();
+    fs::write("README.md", "# Iris work\n").unwrap();
+}
+
+fn mapping(folder: &str, stage: Option<Stage>) -> Mapping {
+    Mapping {
+        folder: String::from(folder),
+        stage,
+    }
+}
+
+// +++++++++++ //
+// guess_stage //
+// +++++++++++ //
+#[rstest]
+#[case("data", Some(Stage::DataSource))]
+#[case("Notebooks", Some(Stage::Exploration))]
+#[case("dashboard", Some(Stage::Product))]
+#[case("src", None)]
+fn guesses_stage_from_name(#[case] folder: &str, #[case] expected: Option<Stage>) {
+    assert_eq!(guess_stage(folder), expected);
+}
+
+// +++++++ //
+// Mapping //
+// +++++++ //
+#[rstest]
+#[case("data=data-source", mapping("data", Some(Stage::DataSource)))]
+#[case(
+    "notebooks/=exploration",
+    mapping("notebooks", Some(Stage::Exploration))
+)]
+#[case("src=skip", mapping("src", None))]
+fn parses_mapping(#[case] value: &str, #[case] expected: Mapping) {
+    assert_eq!(value.parse::<Mapping>().unwrap(), expected);
+}
+
+#[test]
+fn mapping_without_stage_errors() {
+    assert!("data".parse::<Mapping>().is_err());
+    assert!("data=warehouse".parse::<Mapping>().is_err());
+}
+
+// ++++++++ //
+// mappings //
+// ++++++++ //
+#[test]
+fn maps_folders_from_flags_and_guesses() {
+    test_in_tmp_dir(
+        || {
+            setup_folder();
+            fs::create_dir_all("models").unwrap();
+
+            assert_eq!(
+                adopt(&["src=model"], false).mappings("".as_bytes()),
+                vec![
+                    mapping("data", Some(Stage::DataSource)),
+                    mapping("notebooks", Some(Stage::Exploration)),
+                    mapping("src", Some(Stage::Model)),
+                ]
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn asks_for_each_folder_when_interactive() {
+    test_in_tmp_dir(
+        || {
+            setup_folder();
+
+            assert_eq!(
+                adopt(&[], true).mappings("\nskip\nwarehouse\nproduct\n".as_bytes()),
+                vec![
+                    mapping("data", Some(Stage::DataSource)),
+                    mapping("notebooks", None),
+                    mapping("src", Some(Stage::Product)),
+                ]
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "scripts doesn't exist")]
+fn mapping_missing_folder_panics() {
+    test_in_tmp_dir(
+        || {
+            setup_folder();
+
+            adopt(&["scripts=model"], false).mappings("".as_bytes());
+        },
+        true,
+    )
+}
+
+// ++++ //
+// plan //
+// ++++ //
+#[test]
+fn plans_components_from_folders_and_files() {
+    test_in_tmp_dir(
+        || {
+            setup_folder();
+
+            let adopt = adopt(&[], false);
+
+            assert_eq!(
+                adopt.plan(&adopt.mappings("".as_bytes()), &NamingConfig::default()),
+                vec![
+                    Adoption {
+                        from: path::PathBuf::from("data/iris"),
+                        component: ComponentId::new(Stage::DataSource, "iris"),
+                        title: None,
+                    },
+                    Adoption {
+                        from: path::PathBuf::from("notebooks/eda.ipynb"),
+                        component: ComponentId::new(Stage::Exploration, "eda"),
+                        title: None,
+                    },
+                ]
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "explorations/eda exists")]
+fn plan_wont_replace_component() {
+    test_in_tmp_dir(
+        || {
+            setup_folder();
+            fs::create_dir_all("explorations/eda").unwrap();
+
+            adopt(&[], false).plan(
+                &[mapping("notebooks", Some(Stage::Exploration))],
+                &NamingConfig::default(),
+            );
+        },
+        true,
+    )
+}
+
+#[test]
+fn plan_slugifies_names() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("notebooks").unwrap();
+            fs::write("notebooks/Churn Analysis.ipynb", "{}").unwrap();
+
+            assert_eq!(
+                adopt(&[], false).plan(
+                    &[mapping("notebooks", Some(Stage::Exploration))],
+                    &NamingConfig::default(),
+                ),
+                vec![Adoption {
+                    from: path::PathBuf::from("notebooks/Churn Analysis.ipynb"),
+                    component: ComponentId::new(Stage::Exploration, "churn_analysis"),
+                    title: Some(String::from("Churn Analysis")),
+                }]
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "notebooks/!!!.ipynb can't be adopted")]
+fn plan_rejects_unfixable_name() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("notebooks").unwrap();
+            fs::write("notebooks/!!!.ipynb", "{}").unwrap();
+
+            adopt(&[], false).plan(
+                &[mapping("notebooks", Some(Stage::Exploration))],
+                &NamingConfig::default(),
+            );
+        },
+        true,
+    )
+}
+
+// +++++++++++++++ //
+// move_components //
+// +++++++++++++++ //
+#[test]
+fn adopts_without_overwriting_files() {
+    test_in_tmp_dir(
+        || {
+            setup_folder();
+
+            let adopt = adopt(&[], false);
+            let adoptions = adopt.plan(&adopt.mappings("".as_bytes()), &NamingConfig::default());
+
+            adopt.create_stage_folders();
+            adopt.move_components(&adoptions);
+
+            let mut manifest = Manifest::default();
+            assert_eq!(
+                adopt.create_root_files(&mut manifest),
+                vec!["project_scoping.md", ".geoff"]
+            );
+            assert_eq!(
+                adopt.register_components(&mut manifest, &adoptions),
+                vec![
+                    ComponentId::new(Stage::DataSource, "iris"),
+                    ComponentId::new(Stage::Exploration, "eda"),
+                ]
+            );
+
+            assert_eq!(fs::read_to_string("README.md").unwrap(), "# Iris work\n");
+            assert!(path::Path::new("data_sources/iris/iris.csv").exists());
+            assert!(path::Path::new("data_sources/iris/metadata.md").exists());
+            assert!(path::Path::new("explorations/eda/eda.ipynb").exists());
+            assert!(path::Path::new("explorations/eda/README.md").exists());
+            assert!(!path::Path::new("data").exists());
+            assert!(path::Path::new("src").exists());
+            assert_eq!(
+                manifest.templates.keys().collect::<Vec<_>>(),
+                vec![
+                    "data_sources/iris/metadata.md",
+                    "explorations/eda/README.md",
+                    "project_scoping.md"
+                ]
+            );
+        },
+        false,
+    )
+}
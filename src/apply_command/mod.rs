@@ -0,0 +1,792 @@
+use chrono::NaiveDate;
+use clap::Args;
+use console::Style;
+use ptree::{item, TreeBuilder};
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env, ffi, fmt, fs, path,
+};
+
+use crate::add_command::{Component, DataSource, DataSourceKind, Sensitivity};
+use crate::component::{list_archived, list_components, ComponentId, Stage};
+use crate::create_command::Create;
+use crate::front_matter::{self, ComponentFrontMatter};
+use crate::interview::Interview;
+use crate::manifest::Manifest;
+use crate::naming::{self, NamingConfig};
+use crate::sync_command::sync_readme;
+
+/// A project described in a single file
+///
+/// The file is YAML, for example
+///
+/// ```yaml
+/// name: iris_classifier
+/// details:
+///   problem: Botanists can't tell iris species apart
+/// layout:
+///   - notebooks
+/// data_sources:
+///   - name: iris
+///     kind: web
+///     owner: Ronald Fisher
+///     metadata:
+///       download_url: https://example.com/iris.data
+/// explorations:
+///   - name: eda
+///     uses: [iris]
+/// ```
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Spec {
+    /// The name of the project, which is also its folder
+    pub name: String,
+    /// Answers to the project questions, see `geoff create --interactive`
+    #[serde(default)]
+    pub details: BTreeMap<String, String>,
+    /// Extra folders in the project root, alongside the stage folders
+    #[serde(default)]
+    pub layout: Vec<path::PathBuf>,
+    #[serde(default)]
+    pub data_sources: Vec<DataSourceSpec>,
+    #[serde(default)]
+    pub explorations: Vec<ComponentSpec>,
+    #[serde(default)]
+    pub models: Vec<ComponentSpec>,
+    #[serde(default)]
+    pub products: Vec<ComponentSpec>,
+}
+
+/// A data source described in a spec
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DataSourceSpec {
+    pub name: String,
+    #[serde(default)]
+    pub kind: Option<DataSourceKind>,
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub sensitivity: Option<Sensitivity>,
+    /// The date the data files must be deleted by, formatted as YYYY-MM-DD
+    #[serde(default)]
+    pub expires: Option<String>,
+    /// The components it is derived from
+    #[serde(default)]
+    pub uses: Vec<String>,
+    /// Answers to the data source questions, see `geoff add data-source --interactive`
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// An exploration, model or product described in a spec
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ComponentSpec {
+    pub name: String,
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// The components it uses
+    #[serde(default)]
+    pub uses: Vec<String>,
+    /// Answers to the component questions, see `geoff add model --interactive`
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl Spec {
+    /// The components of a stage
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The stage to list
+    pub fn components(&self, stage: Stage) -> Vec<ComponentId> {
+        let names: Vec<&String> = match stage {
+            Stage::DataSource => self.data_sources.iter().map(|spec| &spec.name).collect(),
+            Stage::Exploration => self.explorations.iter().map(|spec| &spec.name).collect(),
+            Stage::Model => self.models.iter().map(|spec| &spec.name).collect(),
+            Stage::Product => self.products.iter().map(|spec| &spec.name).collect(),
+        };
+
+        names
+            .into_iter()
+            .map(|name| ComponentId::new(stage, name))
+            .collect()
+    }
+
+    /// Every component in the spec, in pipeline order
+    pub fn all_components(&self) -> Vec<ComponentId> {
+        Stage::ALL
+            .iter()
+            .flat_map(|stage| self.components(*stage))
+            .collect()
+    }
+
+    /// The components a component uses as written in the spec
+    fn _uses(&self, component: &ComponentId) -> &[String] {
+        match component.stage {
+            Stage::DataSource => self
+                ._data_source_spec(component)
+                .map(|spec| spec.uses.as_slice()),
+            _ => self
+                ._component_spec(component)
+                .map(|spec| spec.uses.as_slice()),
+        }
+        .unwrap_or_default()
+    }
+
+    /// Finds the spec of an exploration, model or product
+    fn _component_spec(&self, component: &ComponentId) -> Option<&ComponentSpec> {
+        let specs = match component.stage {
+            Stage::DataSource => return None,
+            Stage::Exploration => &self.explorations,
+            Stage::Model => &self.models,
+            Stage::Product => &self.products,
+        };
+
+        specs.iter().find(|spec| spec.name == component.name)
+    }
+
+    /// Finds the spec of a data source
+    fn _data_source_spec(&self, component: &ComponentId) -> Option<&DataSourceSpec> {
+        self.data_sources
+            .iter()
+            .find(|spec| spec.name == component.name)
+    }
+}
+
+/// Finds the component a name in the spec refers to
+///
+/// Names are resolved the same way as `--uses`, against the components in
+/// the spec and the project.
+///
+/// # Arguments
+///
+/// * `reference` - The component as written in the spec
+/// * `known` - The components in the spec and the project
+///
+/// # Errors
+///
+/// * The component isn't known or is ambiguous
+pub fn resolve(reference: &str, known: &BTreeSet<ComponentId>) -> ComponentId {
+    let red = Style::new().color256(9);
+
+    let candidates: Vec<&ComponentId> = match reference.parse::<ComponentId>() {
+        Ok(component) => known.iter().filter(|known| **known == component).collect(),
+        Err(_) => known
+            .iter()
+            .filter(|known| known.name == reference)
+            .collect(),
+    };
+
+    match candidates.as_slice() {
+        [component] => (*component).clone(),
+        [] => {
+            eprintln!(
+                "{} {}",
+                reference,
+                red.apply_to("isn't a component in the spec or the project")
+            );
+            panic!("{} doesn't exist", reference);
+        }
+        _ => {
+            eprintln!(
+                "{} {} {}",
+                reference,
+                red.apply_to("is the name of more than one component, use one of"),
+                candidates
+                    .iter()
+                    .map(|component| component.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            panic!("{} is ambiguous", reference);
+        }
+    }
+}
+
+/// Something in the project that differs from the spec
+#[derive(Debug, PartialEq)]
+pub struct Drift {
+    /// What differs, e.g data_sources/iris
+    pub subject: String,
+    /// How it differs
+    pub difference: String,
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            self.subject,
+            Style::new().color256(220).apply_to(&self.difference)
+        )
+    }
+}
+
+/// Compares a field set in the spec with the project
+///
+/// Fields the spec doesn't set aren't compared.
+fn _compare(
+    subject: &ComponentId,
+    field: &str,
+    spec: Option<String>,
+    project: Option<String>,
+) -> Option<Drift> {
+    let spec = spec?;
+
+    if Some(&spec) == project.as_ref() {
+        return None;
+    }
+
+    Some(Drift {
+        subject: subject.to_string(),
+        difference: format!(
+            "{} is {} in the spec but {} in the project",
+            field,
+            spec,
+            project.unwrap_or_else(|| String::from("not set"))
+        ),
+    })
+}
+
+/// Reads a string field from front matter that isn't one of its typed fields
+fn _extra(extra: &BTreeMap<String, serde_yaml::Value>, field: &str) -> Option<String> {
+    extra
+        .get(field)
+        .and_then(|value| value.as_str())
+        .map(|value| value.to_string())
+}
+
+/// What applying a spec will do to the project
+#[derive(Debug, Default, PartialEq)]
+pub struct Plan {
+    /// Whether the project itself needs creating
+    pub create_project: bool,
+    /// The layout folders that need creating
+    pub folders: Vec<path::PathBuf>,
+    /// The components that need adding
+    pub components: Vec<ComponentId>,
+    /// The dependencies that need recording, keyed by the component that
+    /// uses them
+    pub links: BTreeMap<ComponentId, Vec<ComponentId>>,
+    /// The things that differ from the spec, which are reported but left alone
+    pub drift: Vec<Drift>,
+}
+
+impl Plan {
+    /// Whether the project already matches the spec
+    pub fn is_empty(&self) -> bool {
+        !self.create_project
+            && self.folders.is_empty()
+            && self.components.is_empty()
+            && self.links.is_empty()
+    }
+}
+
+#[derive(Args)]
+pub struct Apply {
+    /// The YAML file describing the project
+    #[clap(value_parser)]
+    pub spec: path::PathBuf,
+    /// Shows what would be created without creating it
+    #[clap(short, long)]
+    pub dry_run: bool,
+}
+
+impl Apply {
+    /// Reads the spec
+    ///
+    /// # Errors
+    ///
+    /// * The spec can't be read or isn't valid
+    /// * The project or a component has a name that can't be used
+    /// * A component is listed twice in a stage
+    /// * A layout folder is outside the project
+    /// * An expiry date isn't formatted as YYYY-MM-DD
+    pub fn load_spec(&self) -> Spec {
+        let red = Style::new().color256(9);
+
+        let contents = fs::read_to_string(&self.spec).unwrap_or_else(|_| {
+            eprintln!(
+                "{} {}",
+                self.spec.display(),
+                red.apply_to("doesn't exist or can't be read")
+            );
+            panic!("{} doesn't exist", self.spec.display())
+        });
+
+        let spec: Spec = serde_yaml::from_str(&contents)
+            .unwrap_or_else(|err| panic!("Unable to parse {}\n{}", self.spec.display(), err));
+
+        naming::check(&spec.name, None, &NamingConfig::default(), false);
+
+        let mut seen = BTreeSet::new();
+        for component in spec.all_components() {
+            naming::check(
+                &component.name,
+                Some(component.stage),
+                &NamingConfig::default(),
+                false,
+            );
+            if !seen.insert(component.clone()) {
+                eprintln!(
+                    "{} {}",
+                    component,
+                    red.apply_to("is listed more than once in the spec")
+                );
+                panic!("{} is listed more than once", component);
+            }
+        }
+
+        for folder in spec.layout.iter() {
+            if !folder
+                .components()
+                .all(|part| matches!(part, path::Component::Normal(_)))
+            {
+                eprintln!(
+                    "{} {}",
+                    folder.display(),
+                    red.apply_to("isn't a folder within the project")
+                );
+                panic!("{} is outside the project", folder.display());
+            }
+        }
+
+        for data_source in spec.data_sources.iter() {
+            if let Some(expires) = &data_source.expires {
+                if NaiveDate::parse_from_str(expires, "%Y-%m-%d").is_err() {
+                    eprintln!(
+                        "{} {} {}",
+                        data_source.name,
+                        red.apply_to("expires on a date that isn't formatted as YYYY-MM-DD:"),
+                        expires
+                    );
+                    panic!("{} isn't a valid date", expires);
+                }
+            }
+        }
+
+        spec
+    }
+
+    /// Changes to the project's folder if it exists
+    ///
+    /// Applying a spec from within the project it describes works too.
+    ///
+    /// # Arguments
+    ///
+    /// * `spec` - The spec being applied
+    ///
+    /// # Return value
+    ///
+    /// Whether the project exists
+    ///
+    /// # Errors
+    ///
+    /// * The project's folder exists but isn't managed by geoff
+    pub fn enter(&self, spec: &Spec) -> bool {
+        let current = env::current_dir().expect("Unable to read the current directory");
+        if path::Path::new(".geoff").exists()
+            && current.file_name() == Some(ffi::OsStr::new(&spec.name))
+        {
+            return true;
+        }
+
+        let root = path::Path::new(&spec.name);
+        if !root.exists() {
+            return false;
+        }
+
+        if !root.join(".geoff").exists() {
+            eprintln!(
+                "{} {}",
+                root.display(),
+                Style::new()
+                    .color256(9)
+                    .apply_to("exists but isn't managed by geoff, see geoff adopt")
+            );
+            panic!("{} isn't a geoff project", root.display());
+        }
+
+        env::set_current_dir(root)
+            .unwrap_or_else(|_| panic!("Unable to change to {}", root.display()));
+        true
+    }
+
+    /// Works out what needs creating and what has drifted from the spec
+    ///
+    /// Components are created before any dependencies are recorded, so a
+    /// component can use one listed after it. Names aren't given the stage's
+    /// prefix, as the spec's names must match the components on every apply.
+    ///
+    /// # Arguments
+    ///
+    /// * `spec` - The spec being applied
+    /// * `exists` - Whether the project exists, in which case it's the
+    ///   current directory
+    /// * `manifest` - The project manifest
+    ///
+    /// # Errors
+    ///
+    /// * A component to create breaks one of the project's naming rules
+    pub fn plan(&self, spec: &Spec, exists: bool, manifest: &Manifest) -> Plan {
+        let mut plan = Plan {
+            create_project: !exists,
+            ..Plan::default()
+        };
+
+        let existing: BTreeSet<ComponentId> = if exists {
+            Stage::ALL
+                .iter()
+                .flat_map(|stage| list_components(*stage))
+                .collect()
+        } else {
+            BTreeSet::new()
+        };
+        let archived: BTreeSet<ComponentId> = if exists {
+            Stage::ALL
+                .iter()
+                .flat_map(|stage| list_archived(*stage))
+                .collect()
+        } else {
+            BTreeSet::new()
+        };
+        let specified: BTreeSet<ComponentId> = spec.all_components().into_iter().collect();
+        let known: BTreeSet<ComponentId> = existing.union(&specified).cloned().collect();
+
+        plan.folders = spec
+            .layout
+            .iter()
+            .filter(|folder| !exists || !folder.is_dir())
+            .cloned()
+            .collect();
+
+        for component in spec.all_components() {
+            if archived.contains(&component) {
+                plan.drift.push(Drift {
+                    subject: component.to_string(),
+                    difference: String::from("is archived, restore it to apply the spec"),
+                });
+                continue;
+            }
+
+            let uses: BTreeSet<ComponentId> = spec
+                ._uses(&component)
+                .iter()
+                .map(|reference| resolve(reference, &known))
+                .collect();
+            let recorded = manifest
+                .dependencies
+                .get(&component)
+                .cloned()
+                .unwrap_or_default();
+
+            let missing: Vec<ComponentId> = uses.difference(&recorded).cloned().collect();
+            if !missing.is_empty() {
+                plan.links.insert(component.clone(), missing);
+            }
+
+            if !existing.contains(&component) {
+                naming::check(
+                    &component.name,
+                    Some(component.stage),
+                    &manifest.naming,
+                    false,
+                );
+                plan.components.push(component);
+                continue;
+            }
+
+            for extra in recorded.difference(&uses) {
+                plan.drift.push(Drift {
+                    subject: component.to_string(),
+                    difference: format!("uses {} which isn't in the spec", extra),
+                });
+            }
+
+            plan.drift.extend(self.compare(spec, &component));
+        }
+
+        for component in existing.difference(&specified) {
+            plan.drift.push(Drift {
+                subject: component.to_string(),
+                difference: String::from("isn't in the spec"),
+            });
+        }
+
+        plan
+    }
+
+    /// Compares the front matter of a component with the spec
+    ///
+    /// Only the fields the spec sets are compared, the rest of the metadata
+    /// is left for people to fill in.
+    ///
+    /// # Arguments
+    ///
+    /// * `spec` - The spec being applied
+    /// * `component` - A component in both the spec and the project
+    pub fn compare(&self, spec: &Spec, component: &ComponentId) -> Vec<Drift> {
+        let unreadable = |err: front_matter::FrontMatterError| {
+            vec![Drift {
+                subject: component.to_string(),
+                difference: format!("can't be compared as {}", err),
+            }]
+        };
+
+        if component.stage == Stage::DataSource {
+            let specified = match spec._data_source_spec(component) {
+                Some(specified) => specified,
+                None => return Vec::new(),
+            };
+            let project = match front_matter::read_data_source(&component.path()) {
+                Ok(project) => project,
+                Err(err) => return unreadable(err),
+            };
+
+            return [
+                _compare(
+                    component,
+                    "kind",
+                    specified.kind.map(|kind| kind.to_string()),
+                    project.kind.map(|kind| kind.to_string()),
+                ),
+                _compare(
+                    component,
+                    "owner",
+                    specified.owner.clone(),
+                    project.owner.clone(),
+                ),
+                _compare(
+                    component,
+                    "sensitivity",
+                    specified
+                        .sensitivity
+                        .map(|sensitivity| sensitivity.to_string()),
+                    project
+                        .sensitivity
+                        .map(|sensitivity| sensitivity.to_string()),
+                ),
+                _compare(
+                    component,
+                    "expires",
+                    specified.expires.clone(),
+                    project.expires.clone(),
+                ),
+                _compare(
+                    component,
+                    "description",
+                    specified.metadata.get("description").cloned(),
+                    _extra(&project.extra, "description"),
+                ),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+        }
+
+        let specified = match spec._component_spec(component) {
+            Some(specified) => specified,
+            None => return Vec::new(),
+        };
+        let project = match fs::read_to_string(component.document())
+            .map_err(front_matter::FrontMatterError::Io)
+            .and_then(|contents| front_matter::parse::<ComponentFrontMatter>(&contents))
+        {
+            Ok(project) => project,
+            Err(err) => return unreadable(err),
+        };
+
+        [
+            _compare(
+                component,
+                "owner",
+                specified.owner.clone(),
+                project.owner.clone(),
+            ),
+            _compare(
+                component,
+                "description",
+                specified.metadata.get("description").cloned(),
+                _extra(&project.extra, "description"),
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Creates the project, if it doesn't exist, and changes to it
+    ///
+    /// # Arguments
+    ///
+    /// * `spec` - The spec being applied
+    pub fn create_project(&self, spec: &Spec) {
+        let create = Create {
+            name: path::PathBuf::from(&spec.name),
+            parents: false,
+            slugify: false,
+            title: None,
+            interview: Interview::default(),
+        };
+
+        create.create_root();
+        create.create_subdirectories();
+        create.create_files(&spec.details);
+        create.remember_templates();
+
+        env::set_current_dir(&create.name)
+            .unwrap_or_else(|_| panic!("Unable to change to {}", spec.name));
+    }
+
+    /// Makes the changes in the plan
+    ///
+    /// The project must be the current directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `spec` - The spec being applied
+    /// * `plan` - What needs creating
+    pub fn apply(&self, spec: &Spec, plan: &Plan) {
+        for folder in plan.folders.iter() {
+            fs::create_dir_all(folder)
+                .unwrap_or_else(|_| panic!("Unable to create {}", folder.display()));
+        }
+
+        for component in plan.components.iter() {
+            if component.stage == Stage::DataSource {
+                let specified = spec
+                    ._data_source_spec(component)
+                    .expect("Planned data sources are in the spec");
+                let data_source = DataSource {
+                    name: Some(path::PathBuf::from(&specified.name)),
+                    kind: specified.kind,
+                    owner: specified.owner.clone(),
+                    sensitivity: specified.sensitivity,
+                    expires: specified.expires.as_ref().map(|expires| {
+                        NaiveDate::parse_from_str(expires, "%Y-%m-%d")
+                            .expect("Expiry dates are checked when the spec is loaded")
+                    }),
+                    uses: Vec::new(),
+                    from_csv: None,
+                    slugify: false,
+                    title: None,
+                    interview: Interview::default(),
+                };
+
+                data_source.add(&[], &_answers(&specified.metadata, &specified.owner));
+            } else {
+                let specified = spec
+                    ._component_spec(component)
+                    .expect("Planned components are in the spec");
+                let added = Component {
+                    name: path::PathBuf::from(&specified.name),
+                    owner: specified.owner.clone(),
+                    uses: Vec::new(),
+                    slugify: false,
+                    title: None,
+                    interview: Interview::default(),
+                };
+
+                added.add(
+                    component.stage,
+                    &[],
+                    &_answers(&specified.metadata, &specified.owner),
+                );
+            }
+        }
+
+        let mut manifest = Manifest::load();
+        for (component, uses) in plan.links.iter() {
+            manifest.add_dependencies(component, uses);
+        }
+        manifest.save();
+        sync_readme();
+    }
+
+    /// Creates a tree showing what was created
+    ///
+    /// # Arguments
+    ///
+    /// * `spec` - The spec being applied
+    /// * `plan` - What was created
+    pub fn create_tree(&self, spec: &Spec, plan: &Plan) -> item::StringItem {
+        let gold = Style::new().color256(220);
+        let hd = Style::new().color256(194);
+
+        let mut tree = TreeBuilder::new(format!("{}", Style::new().bold().apply_to(&spec.name)));
+
+        if plan.create_project {
+            tree.add_empty_child(format!("{} README.md", hd.apply_to("\u{1F5CE}")));
+            tree.add_empty_child(format!("{} project_scoping.md", hd.apply_to("\u{1F5CE}")));
+        }
+
+        for folder in plan.folders.iter() {
+            tree.add_empty_child(format!(
+                "{} {}",
+                gold.apply_to("\u{1F5BF}"),
+                folder.display()
+            ));
+        }
+
+        for stage in Stage::ALL.iter() {
+            let added: Vec<&ComponentId> = plan
+                .components
+                .iter()
+                .filter(|component| component.stage == *stage)
+                .collect();
+
+            if added.is_empty() && !plan.create_project {
+                continue;
+            }
+
+            tree.begin_child(format!("{} {}", gold.apply_to("\u{1F5BF}"), stage.folder()));
+            for component in added {
+                tree.add_empty_child(format!("{} {}", gold.apply_to("\u{1F5BF}"), component.name));
+            }
+            tree.end_child();
+        }
+
+        tree.build()
+    }
+
+    /// Formats the drift with a line for each difference
+    ///
+    /// # Arguments
+    ///
+    /// * `drift` - The things that differ from the spec
+    pub fn drift_summary(&self, drift: &[Drift]) -> String {
+        let yellow = Style::new().color256(220);
+
+        let mut lines = vec![format!(
+            "{} These differ from the spec and were left alone",
+            yellow.apply_to("\u{26A0}")
+        )];
+        lines.extend(drift.iter().map(|drift| format!("  {}", drift)));
+
+        lines.join("\n")
+    }
+}
+
+/// The answers to put into a component's document
+///
+/// The owner in the spec takes the place of an owner in the metadata.
+fn _answers(
+    metadata: &BTreeMap<String, String>,
+    owner: &Option<String>,
+) -> BTreeMap<String, String> {
+    let mut answers = metadata.clone();
+
+    if owner.is_some() {
+        answers.remove("owner");
+    }
+    answers.retain(|_, answer| !answer.trim().is_empty());
+
+    answers
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,435 @@
+use std::fs;
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+
+fn id(value: &str) -> ComponentId {
+    value.parse().unwrap()
+}
+
+fn apply() -> Apply {
+    Apply {
+        spec: path::PathBuf::from("project.yaml"),
+        dry_run: false,
+    }
+}
+
+fn example_spec() -> Spec {
+    serde_yaml::from_str(
+        "name: iris_classifier\n\
+        layout: [notebooks]\n\
+        data_sources:\n\
+        - name: iris\n  kind: web\n  owner: Ronald Fisher\n\
+        explorations:\n\
+        - name: eda\n  uses: [iris]\n\
+        models:\n\
+        - name: classifier\n  owner: Edgar Anderson\n  uses: [explorations/eda]\n",
+    )
+    .unwrap()
+}
+
+fn setup_project() -> Manifest {
+    fs::create_dir_all("data_sources/iris").unwrap();
+    fs::create_dir_all("explorations/eda").unwrap();
+    fs::create_dir_all("models").unwrap();
+    fs::create_dir_all("products").unwrap();
+    fs::write(
+        "data_sources/iris/metadata.md",
+        "---\nname: iris\nkind: web\nowner: Ronald Fisher\ncreated: 2022-04-01\n---\n\n# iris\n",
+    )
+    .unwrap();
+    fs::write(
+        "explorations/eda/README.md",
+        "---\nname: eda\nstage: exploration\ncreated: 2022-04-01\n---\n\n# eda\n",
+    )
+    .unwrap();
+
+    let mut manifest = Manifest::default();
+    manifest.add_dependencies(&id("explorations/eda"), &[id("data_sources/iris")]);
+
+    manifest
+}
+
+// +++++++ //
+// resolve //
+// +++++++ //
+#[test]
+fn resolves_bare_and_full_names() {
+    let known: BTreeSet<ComponentId> = [id("data_sources/iris"), id("explorations/eda")]
+        .into_iter()
+        .collect();
+
+    assert_eq!(resolve("iris", &known), id("data_sources/iris"));
+    assert_eq!(resolve("explorations/eda", &known), id("explorations/eda"));
+}
+
+#[test]
+#[should_panic(expected = "models/eda doesn't exist")]
+fn unknown_component_panics() {
+    let known: BTreeSet<ComponentId> = [id("explorations/eda")].into_iter().collect();
+
+    resolve("models/eda", &known);
+}
+
+#[test]
+#[should_panic(expected = "iris is ambiguous")]
+fn ambiguous_component_panics() {
+    let known: BTreeSet<ComponentId> = [id("data_sources/iris"), id("explorations/iris")]
+        .into_iter()
+        .collect();
+
+    resolve("iris", &known);
+}
+
+// +++++++++ //
+// load_spec //
+// +++++++++ //
+#[test]
+fn loads_spec() {
+    test_in_tmp_dir(
+        || {
+            fs::write(
+                "project.yaml",
+                "name: iris_classifier\ndata_sources:\n- name: iris\n  expires: 2030-01-01\n",
+            )
+            .unwrap();
+
+            let spec = apply().load_spec();
+
+            assert_eq!(spec.name, "iris_classifier");
+            assert_eq!(
+                spec.components(Stage::DataSource),
+                vec![id("data_sources/iris")]
+            );
+            assert_eq!(
+                spec.data_sources[0].expires,
+                Some(String::from("2030-01-01"))
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "Unable to parse project.yaml")]
+fn unknown_field_panics() {
+    test_in_tmp_dir(
+        || {
+            fs::write("project.yaml", "name: iris_classifier\nmodel: []\n").unwrap();
+
+            apply().load_spec();
+        },
+        true,
+    )
+}
+
+#[test]
+#[should_panic(expected = "models/classifier is listed more than once")]
+fn duplicate_component_panics() {
+    test_in_tmp_dir(
+        || {
+            fs::write(
+                "project.yaml",
+                "name: iris_classifier\nmodels:\n- name: classifier\n- name: classifier\n",
+            )
+            .unwrap();
+
+            apply().load_spec();
+        },
+        true,
+    )
+}
+
+#[test]
+#[should_panic(expected = "../shared is outside the project")]
+fn layout_outside_project_panics() {
+    test_in_tmp_dir(
+        || {
+            fs::write(
+                "project.yaml",
+                "name: iris_classifier\nlayout: [../shared]\n",
+            )
+            .unwrap();
+
+            apply().load_spec();
+        },
+        true,
+    )
+}
+
+#[test]
+#[should_panic(expected = "01/01/2030 isn't a valid date")]
+fn invalid_expiry_panics() {
+    test_in_tmp_dir(
+        || {
+            fs::write(
+                "project.yaml",
+                "name: iris_classifier\ndata_sources:\n- name: iris\n  expires: 01/01/2030\n",
+            )
+            .unwrap();
+
+            apply().load_spec();
+        },
+        true,
+    )
+}
+
+// ++++ //
+// plan //
+// ++++ //
+#[test]
+fn plans_everything_for_new_project() {
+    let plan = apply().plan(&example_spec(), false, &Manifest::default());
+
+    assert!(plan.create_project);
+    assert_eq!(plan.folders, vec![path::PathBuf::from("notebooks")]);
+    assert_eq!(
+        plan.components,
+        vec![
+            id("data_sources/iris"),
+            id("explorations/eda"),
+            id("models/classifier")
+        ]
+    );
+    assert_eq!(
+        plan.links.get(&id("models/classifier")),
+        Some(&vec![id("explorations/eda")])
+    );
+    assert!(plan.drift.is_empty());
+}
+
+#[test]
+fn plans_only_whats_missing() {
+    test_in_tmp_dir(
+        || {
+            let manifest = setup_project();
+            fs::create_dir("notebooks").unwrap();
+
+            let plan = apply().plan(&example_spec(), true, &manifest);
+
+            assert!(!plan.create_project);
+            assert!(plan.folders.is_empty());
+            assert_eq!(plan.components, vec![id("models/classifier")]);
+            assert_eq!(
+                plan.links.keys().collect::<Vec<_>>(),
+                vec![&id("models/classifier")]
+            );
+            assert!(plan.drift.is_empty());
+        },
+        false,
+    )
+}
+
+#[test]
+fn plan_is_empty_once_applied() {
+    test_in_tmp_dir(
+        || {
+            let manifest = setup_project();
+            fs::create_dir("notebooks").unwrap();
+            let mut spec = example_spec();
+            spec.models.clear();
+
+            let plan = apply().plan(&spec, true, &manifest);
+
+            assert!(plan.is_empty());
+        },
+        false,
+    )
+}
+
+#[test]
+fn reports_components_not_in_spec() {
+    test_in_tmp_dir(
+        || {
+            let manifest = setup_project();
+            fs::create_dir_all("products/dashboard").unwrap();
+
+            let plan = apply().plan(&example_spec(), true, &manifest);
+
+            assert_eq!(
+                plan.drift,
+                vec![Drift {
+                    subject: String::from("products/dashboard"),
+                    difference: String::from("isn't in the spec"),
+                }]
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn reports_dependencies_not_in_spec() {
+    test_in_tmp_dir(
+        || {
+            let mut manifest = setup_project();
+            fs::create_dir_all("data_sources/species").unwrap();
+            manifest.add_dependencies(&id("explorations/eda"), &[id("data_sources/species")]);
+            let mut spec = example_spec();
+            spec.data_sources.push(DataSourceSpec {
+                name: String::from("species"),
+                ..DataSourceSpec::default()
+            });
+
+            let plan = apply().plan(&spec, true, &manifest);
+
+            assert!(plan.drift.contains(&Drift {
+                subject: String::from("explorations/eda"),
+                difference: String::from("uses data_sources/species which isn't in the spec"),
+            }));
+        },
+        false,
+    )
+}
+
+#[test]
+fn archived_components_arent_created() {
+    test_in_tmp_dir(
+        || {
+            let manifest = setup_project();
+            fs::create_dir_all("models/_archive/classifier").unwrap();
+
+            let plan = apply().plan(&example_spec(), true, &manifest);
+
+            assert!(!plan.components.contains(&id("models/classifier")));
+            assert_eq!(
+                plan.drift,
+                vec![Drift {
+                    subject: String::from("models/classifier"),
+                    difference: String::from("is archived, restore it to apply the spec"),
+                }]
+            );
+        },
+        false,
+    )
+}
+
+// +++++++ //
+// compare //
+// +++++++ //
+#[test]
+fn compares_fields_set_in_spec() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+            let mut spec = example_spec();
+            spec.data_sources[0].owner = Some(String::from("Edgar Anderson"));
+            spec.data_sources[0].sensitivity = Some(Sensitivity::Public);
+
+            let drift = apply().compare(&spec, &id("data_sources/iris"));
+
+            assert_eq!(
+                drift,
+                vec![
+                    Drift {
+                        subject: String::from("data_sources/iris"),
+                        difference: String::from(
+                            "owner is Edgar Anderson in the spec but Ronald Fisher in the project"
+                        ),
+                    },
+                    Drift {
+                        subject: String::from("data_sources/iris"),
+                        difference: String::from(
+                            "sensitivity is public in the spec but not set in the project"
+                        ),
+                    },
+                ]
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn compares_description() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+            let mut spec = example_spec();
+            spec.explorations[0]
+                .metadata
+                .insert(String::from("description"), String::from("First look"));
+
+            let drift = apply().compare(&spec, &id("explorations/eda"));
+
+            assert_eq!(
+                drift[0].difference,
+                "description is First look in the spec but not set in the project"
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn unreadable_front_matter_is_drift() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+            fs::write("explorations/eda/README.md", "# eda\n").unwrap();
+
+            let drift = apply().compare(&example_spec(), &id("explorations/eda"));
+
+            assert_eq!(
+                drift[0].difference,
+                "can't be compared as the document has no front matter"
+            );
+        },
+        false,
+    )
+}
+
+// +++++ //
+// apply //
+// +++++ //
+#[test]
+fn creates_project_from_spec() {
+    test_in_tmp_dir(
+        || {
+            let root = env::current_dir().unwrap();
+            let spec = example_spec();
+            let plan = apply().plan(&spec, false, &Manifest::default());
+
+            apply().create_project(&spec);
+            apply().apply(&spec, &plan);
+            env::set_current_dir(&root).unwrap();
+
+            assert!(path::Path::new("iris_classifier/notebooks").is_dir());
+            assert!(path::Path::new("iris_classifier/models/classifier/README.md").exists());
+
+            let metadata = front_matter::read_data_source(path::Path::new(
+                "iris_classifier/data_sources/iris",
+            ))
+            .unwrap();
+            assert_eq!(metadata.owner, Some(String::from("Ronald Fisher")));
+
+            let manifest = Manifest::load_from(path::Path::new("iris_classifier/geoff.toml"));
+            assert_eq!(
+                manifest.dependencies.get(&id("models/classifier")),
+                Some(&[id("explorations/eda")].into_iter().collect())
+            );
+        },
+        false,
+    )
+}
+
+// +++++++ //
+// answers //
+// +++++++ //
+#[test]
+fn spec_owner_replaces_owner_answer() {
+    let metadata: BTreeMap<String, String> = [
+        (String::from("owner"), String::from("Edgar Anderson")),
+        (String::from("purpose"), String::from("")),
+        (String::from("description"), String::from("First look")),
+    ]
+    .into_iter()
+    .collect();
+
+    let answers = _answers(&metadata, &Some(String::from("Ronald Fisher")));
+
+    assert_eq!(
+        answers.into_iter().collect::<Vec<_>>(),
+        vec![(String::from("description"), String::from("First look"))]
+    );
+}
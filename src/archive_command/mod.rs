@@ -0,0 +1,196 @@
+use clap::Args;
+use console::Style;
+use ptree::{item, TreeBuilder};
+use std::{fs, path};
+
+use crate::component::{ComponentId, Stage, ARCHIVE_FOLDER};
+use crate::front_matter;
+use crate::lineage_command::downstream_of;
+use crate::manifest::{ArchiveEntry, Manifest};
+
+/// Writes a friendly error and panics when a component can't be found
+fn _missing_component(component: &ComponentId, message: &str) -> ! {
+    eprintln!(
+        "{} {}",
+        component,
+        Style::new().color256(9).apply_to(message)
+    );
+    panic!("{} doesn't exist", component);
+}
+
+/// Writes a friendly error and panics when a folder is already taken
+fn _folder_taken(folder: &path::Path) -> ! {
+    eprintln!(
+        "{} {}",
+        folder.display(),
+        Style::new()
+            .color256(9)
+            .apply_to("already exists, please rename it first")
+    );
+    panic!("{} exists", folder.display());
+}
+
+/// Creates a tree showing where a component was moved to
+fn _moved_tree(stage: Stage, folders: &[&str], name: &str) -> item::StringItem {
+    let gold = Style::new().color256(220);
+
+    let mut tree = TreeBuilder::new(format!("{} {}", gold.apply_to("\u{1F5BF}"), stage.folder()));
+    for folder in folders.iter() {
+        tree.begin_child(format!("{} {}", gold.apply_to("\u{1F5BF}"), folder));
+    }
+    tree.add_empty_child(format!("{} {}", gold.apply_to("\u{1F5BF}"), name));
+    for _ in folders.iter() {
+        tree.end_child();
+    }
+
+    tree.build()
+}
+
+#[derive(Args, Clone)]
+pub struct Archive {
+    /// The stage the component belongs to
+    #[clap(value_enum)]
+    pub stage: Stage,
+    /// The name of the component
+    #[clap(value_parser)]
+    pub name: String,
+    /// Why the component is being archived
+    #[clap(short, long, value_parser)]
+    pub reason: Option<String>,
+}
+
+impl Archive {
+    /// The archiving with the name of the component it refers to, see
+    /// [ComponentId::find]
+    pub fn resolved(&self) -> Archive {
+        Archive {
+            name: ComponentId::find(self.stage, &self.name).name,
+            ..self.clone()
+        }
+    }
+
+    /// The id of the component being archived
+    pub fn id(&self) -> ComponentId {
+        ComponentId::new(self.stage, &self.name)
+    }
+
+    /// Lists the components that still depend on the component
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest holding the dependencies
+    pub fn dependents(&self, manifest: &Manifest) -> Vec<ComponentId> {
+        downstream_of(manifest, &self.id())
+            .into_iter()
+            .filter(|component| !manifest.archived.contains_key(component))
+            .collect()
+    }
+
+    /// Moves the component into its stage's archive folder, along with the
+    /// records of its templates, and records it in the manifest
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest
+    ///
+    /// # Errors
+    ///
+    /// * The component doesn't exist
+    /// * An archived component already has the same name
+    pub fn archive(&self, manifest: &mut Manifest) -> ArchiveEntry {
+        let component = self.id();
+
+        if !component.exists() {
+            _missing_component(&component, "isn't a component in this project");
+        }
+
+        let archive_path = component.archive_path();
+        if archive_path.exists() {
+            _folder_taken(&archive_path);
+        }
+
+        let archive_folder = path::Path::new(self.stage.folder()).join(ARCHIVE_FOLDER);
+        fs::create_dir_all(&archive_folder)
+            .unwrap_or_else(|_| panic!("Unable to create {}", archive_folder.display()));
+        fs::rename(component.path(), &archive_path)
+            .unwrap_or_else(|_| panic!("Unable to move {}", component.path().display()));
+        manifest.move_templates(&component.path(), &archive_path);
+
+        let entry = ArchiveEntry {
+            archived: front_matter::today(),
+            reason: self.reason.clone(),
+        };
+        manifest.archived.insert(component, entry.clone());
+
+        entry
+    }
+
+    /// Creates a tree showing the component in the archive folder
+    pub fn create_tree(&self) -> item::StringItem {
+        _moved_tree(self.stage, &[ARCHIVE_FOLDER], &self.name)
+    }
+}
+
+#[derive(Args, Clone)]
+pub struct Restore {
+    /// The stage the component belongs to
+    #[clap(value_enum)]
+    pub stage: Stage,
+    /// The name of the archived component
+    #[clap(value_parser)]
+    pub name: String,
+}
+
+impl Restore {
+    /// The restore with the name of the archived component it refers to,
+    /// see [ComponentId::find_archived]
+    pub fn resolved(&self) -> Restore {
+        Restore {
+            name: ComponentId::find_archived(self.stage, &self.name).name,
+            ..self.clone()
+        }
+    }
+
+    /// The id of the component being restored
+    pub fn id(&self) -> ComponentId {
+        ComponentId::new(self.stage, &self.name)
+    }
+
+    /// Moves the component and the records of its templates out of the
+    /// archive folder and removes it from the manifest's archived components
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest
+    ///
+    /// # Errors
+    ///
+    /// * The component isn't archived
+    /// * A component with the same name has been added since
+    pub fn restore(&self, manifest: &mut Manifest) {
+        let component = self.id();
+        let archive_path = component.archive_path();
+
+        if !archive_path.is_dir() {
+            _missing_component(&component, "isn't an archived component in this project");
+        }
+
+        if component.path().exists() {
+            _folder_taken(&component.path());
+        }
+
+        fs::rename(&archive_path, component.path())
+            .unwrap_or_else(|_| panic!("Unable to move {}", archive_path.display()));
+        manifest.move_templates(&archive_path, &component.path());
+
+        manifest.archived.remove(&component);
+    }
+
+    /// Creates a tree showing the component back in its stage folder
+    pub fn create_tree(&self) -> item::StringItem {
+        _moved_tree(self.stage, &[], &self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,204 @@
+use std::{fs, path};
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+use crate::manifest::TemplateRecord;
+
+fn archive(stage: Stage, name: &str) -> Archive {
+    Archive {
+        stage,
+        name: String::from(name),
+        reason: Some(String::from("Agreement ended")),
+    }
+}
+
+fn restore(stage: Stage, name: &str) -> Restore {
+    Restore {
+        stage,
+        name: String::from(name),
+    }
+}
+
+// +++++++ //
+// archive //
+// +++++++ //
+#[test]
+fn archive_moves_component_and_records_reason() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+            fs::write("data_sources/iris/metadata.md", "# iris\n").unwrap();
+
+            let mut manifest = Manifest::default();
+            let entry = archive(Stage::DataSource, "iris").archive(&mut manifest);
+
+            assert!(!path::Path::new("data_sources/iris").exists());
+            assert!(path::Path::new("data_sources/_archive/iris/metadata.md").exists());
+            assert_eq!(entry.reason, Some(String::from("Agreement ended")));
+            assert_eq!(entry.archived, front_matter::today());
+            assert_eq!(
+                manifest.archived[&ComponentId::new(Stage::DataSource, "iris")],
+                entry
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "data_sources/iris doesn't exist")]
+fn archive_missing_component_panics() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources").unwrap();
+
+            archive(Stage::DataSource, "iris").archive(&mut Manifest::default());
+        },
+        true,
+    )
+}
+
+#[test]
+#[should_panic(expected = "models/_archive/classifier exists")]
+fn archive_panics_when_already_archived() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("models/classifier").unwrap();
+            fs::create_dir_all("models/_archive/classifier").unwrap();
+
+            archive(Stage::Model, "classifier").archive(&mut Manifest::default());
+        },
+        true,
+    )
+}
+
+#[test]
+fn dependents_lists_components_still_using_it() {
+    let iris = ComponentId::new(Stage::DataSource, "iris");
+    let eda = ComponentId::new(Stage::Exploration, "eda");
+    let old = ComponentId::new(Stage::Exploration, "old");
+
+    let mut manifest = Manifest::default();
+    manifest.add_dependencies(&eda, std::slice::from_ref(&iris));
+    manifest.add_dependencies(&old, std::slice::from_ref(&iris));
+    manifest.archived.insert(
+        old,
+        ArchiveEntry {
+            archived: String::from("2022-04-01"),
+            reason: None,
+        },
+    );
+
+    assert_eq!(
+        archive(Stage::DataSource, "iris").dependents(&manifest),
+        vec![eda]
+    );
+}
+
+// +++++++ //
+// restore //
+// +++++++ //
+#[test]
+fn restore_moves_component_back() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("models/classifier").unwrap();
+
+            let mut manifest = Manifest::default();
+            archive(Stage::Model, "classifier").archive(&mut manifest);
+            restore(Stage::Model, "classifier").restore(&mut manifest);
+
+            assert!(path::Path::new("models/classifier").is_dir());
+            assert!(!path::Path::new("models/_archive/classifier").exists());
+            assert!(manifest.archived.is_empty());
+        },
+        false,
+    )
+}
+
+#[test]
+fn archive_and_restore_move_template_records() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("models/classifier").unwrap();
+
+            let mut manifest = Manifest::default();
+            manifest.templates.insert(
+                String::from("models/classifier/README.md"),
+                TemplateRecord {
+                    template: String::from("models/README.md"),
+                    version: String::from("abc123"),
+                    name: String::from("classifier"),
+                },
+            );
+
+            archive(Stage::Model, "classifier").archive(&mut manifest);
+            assert_eq!(
+                manifest.templates.keys().collect::<Vec<_>>(),
+                vec!["models/_archive/classifier/README.md"]
+            );
+
+            restore(Stage::Model, "classifier").restore(&mut manifest);
+            assert_eq!(
+                manifest.templates.keys().collect::<Vec<_>>(),
+                vec!["models/classifier/README.md"]
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "models/classifier doesn't exist")]
+fn restore_unarchived_component_panics() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("models/classifier").unwrap();
+
+            restore(Stage::Model, "classifier").restore(&mut Manifest::default());
+        },
+        true,
+    )
+}
+
+#[test]
+#[should_panic(expected = "models/classifier exists")]
+fn restore_panics_when_name_taken() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("models/classifier").unwrap();
+            fs::create_dir_all("models/_archive/classifier").unwrap();
+
+            restore(Stage::Model, "classifier").restore(&mut Manifest::default());
+        },
+        true,
+    )
+}
+
+// +++++++++++ //
+// create_tree //
+// +++++++++++ //
+#[test]
+fn archive_tree_shows_archive_folder() {
+    let tree = archive(Stage::Model, "classifier").create_tree();
+
+    assert_eq!(console::strip_ansi_codes(&tree.text), "\u{1F5BF} models");
+    assert_eq!(
+        console::strip_ansi_codes(&tree.children[0].text),
+        "\u{1F5BF} _archive"
+    );
+    assert_eq!(
+        console::strip_ansi_codes(&tree.children[0].children[0].text),
+        "\u{1F5BF} classifier"
+    );
+}
+
+#[test]
+fn restore_tree_shows_stage_folder() {
+    let tree = restore(Stage::Model, "classifier").create_tree();
+
+    assert_eq!(
+        console::strip_ansi_codes(&tree.children[0].text),
+        "\u{1F5BF} classifier"
+    );
+}
@@ -0,0 +1,164 @@
+use clap::{AppSettings, Args, Subcommand};
+use console::Style;
+use serde::Serialize;
+use std::{fs, path};
+
+use crate::add_command::Sensitivity;
+use crate::data_source_command::list_data_sources;
+use crate::metadata;
+use crate::show_command::OutputFormat;
+
+#[derive(Args)]
+pub struct Audit {
+    #[clap(subcommand)]
+    pub command: Option<AuditCommands>,
+}
+
+#[derive(Subcommand)]
+#[clap(setting = AppSettings::SubcommandRequiredElseHelp)]
+pub enum AuditCommands {
+    /// Lists every field flagged as PII across the project
+    Pii(AuditPii),
+}
+
+#[derive(Args)]
+pub struct AuditPii {
+    /// The format to show the report in
+    #[clap(short, long, value_enum, default_value = "text")]
+    pub format: OutputFormat,
+}
+
+/// A field in a data dictionary that holds personal data
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct PiiField {
+    /// The name of the data source the field belongs to
+    pub data_source: String,
+    /// The name of the field
+    pub field: String,
+    /// The description of the field from the data dictionary
+    pub description: String,
+    /// The person responsible for the data source
+    pub owner: Option<String>,
+    /// How sensitive the data source is
+    pub sensitivity: Option<Sensitivity>,
+}
+
+impl AuditPii {
+    /// Finds the PII fields in the metadata of every data source
+    ///
+    /// Data sources without a metadata.md are skipped.
+    ///
+    /// # Return value
+    ///
+    /// The PII fields, ordered by data source
+    pub fn find_pii_fields(&self) -> Vec<PiiField> {
+        let mut fields = Vec::new();
+
+        for name in list_data_sources() {
+            let metadata_path = path::Path::new("data_sources")
+                .join(&name)
+                .join("metadata.md");
+
+            let contents = match fs::read_to_string(&metadata_path) {
+                Ok(contents) => contents,
+                Err(_) => continue,
+            };
+
+            let data_source_metadata = metadata::parse_data_source(&contents);
+            let front_matter = data_source_metadata.front_matter.unwrap_or_default();
+
+            fields.extend(
+                data_source_metadata
+                    .data_dictionary
+                    .into_iter()
+                    .filter(|entry| entry.pii)
+                    .map(|entry| PiiField {
+                        data_source: name.clone(),
+                        field: entry.field,
+                        description: entry.description,
+                        owner: front_matter.owner.clone(),
+                        sensitivity: front_matter.sensitivity,
+                    }),
+            );
+        }
+
+        fields
+    }
+
+    /// Formats the PII fields in the requested format
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - The PII fields found in the project
+    pub fn render(&self, fields: &[PiiField]) -> String {
+        match self.format {
+            OutputFormat::Json => {
+                serde_json::to_string_pretty(fields).expect("Unable to serialise the report")
+            }
+            OutputFormat::Text => self._render_text(fields),
+        }
+    }
+
+    /// Formats the PII fields as a table for the terminal
+    fn _render_text(&self, fields: &[PiiField]) -> String {
+        if fields.is_empty() {
+            return format!(
+                "{}",
+                Style::new()
+                    .color256(10)
+                    .apply_to("No fields are flagged as PII")
+            );
+        }
+
+        let rows: Vec<[String; 4]> = fields
+            .iter()
+            .map(|pii_field| {
+                [
+                    pii_field.data_source.clone(),
+                    pii_field.field.clone(),
+                    pii_field.owner.clone().unwrap_or_else(|| "-".to_string()),
+                    pii_field
+                        .sensitivity
+                        .map(|sensitivity| sensitivity.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                ]
+            })
+            .collect();
+
+        let headers = ["data source", "field", "owner", "sensitivity"];
+        let widths: Vec<usize> = (0..headers.len())
+            .map(|idx| {
+                rows.iter()
+                    .map(|row| row[idx].chars().count())
+                    .chain([headers[idx].len()])
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        let format_row = |cells: Vec<&str>| {
+            cells
+                .iter()
+                .zip(widths.iter())
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        };
+
+        let mut lines = vec![format!(
+            "{}",
+            Style::new().bold().apply_to(format_row(headers.to_vec()))
+        )];
+        lines.extend(
+            rows.iter()
+                .map(|row| format_row(row.iter().map(|cell| cell.as_str()).collect())),
+        );
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests;
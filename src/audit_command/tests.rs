@@ -0,0 +1,154 @@
+use std::fs;
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+
+fn setup_data_sources() {
+    fs::create_dir_all("data_sources/patients").unwrap();
+    fs::write(
+        "data_sources/patients/metadata.md",
+        "---\n\
+        name: patients\n\
+        kind: extract\n\
+        owner: Florence Nightingale\n\
+        sensitivity: restricted\n\
+        created: 2022-04-01\n\
+        ---\n\
+        \n\
+        # patients\n\
+        \n\
+        ## Data dictionary\n\
+        \n\
+        | data field | data type | description | pii |\n\
+        | :--------- | :-------- | :---------- | :-- |\n\
+        | *e.g id* | *e.g INT* | *e.g A unique identifier* | *e.g no* |\n\
+        | id | INT | A unique identifier | no |\n\
+        | full_name | VARCHAR | The patient's name | yes |\n\
+        | postcode | VARCHAR | Where the patient lives | Y |\n",
+    )
+    .unwrap();
+
+    fs::create_dir_all("data_sources/iris").unwrap();
+    fs::write(
+        "data_sources/iris/metadata.md",
+        "# iris\n\
+        \n\
+        ## Data dictionary\n\
+        \n\
+        | data field | data type | description |\n\
+        | :--------- | :-------- | :---------- |\n\
+        | sepal_length | FLOAT | The length of the sepal |\n",
+    )
+    .unwrap();
+
+    fs::create_dir_all("data_sources/no_metadata").unwrap();
+}
+
+fn audit(format: OutputFormat) -> AuditPii {
+    AuditPii { format }
+}
+
+// +++++++++++++++ //
+// find_pii_fields //
+// +++++++++++++++ //
+#[test]
+fn finds_fields_flagged_as_pii() {
+    test_in_tmp_dir(
+        || {
+            setup_data_sources();
+
+            let fields = audit(OutputFormat::Text).find_pii_fields();
+
+            assert_eq!(
+                fields
+                    .iter()
+                    .map(|pii_field| pii_field.field.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["full_name", "postcode"]
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn pii_fields_include_owner_and_sensitivity() {
+    test_in_tmp_dir(
+        || {
+            setup_data_sources();
+
+            let fields = audit(OutputFormat::Text).find_pii_fields();
+
+            assert_eq!(
+                fields[0],
+                PiiField {
+                    data_source: "patients".to_string(),
+                    field: "full_name".to_string(),
+                    description: "The patient's name".to_string(),
+                    owner: Some("Florence Nightingale".to_string()),
+                    sensitivity: Some(Sensitivity::Restricted),
+                }
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn no_data_sources_finds_nothing() {
+    test_in_tmp_dir(
+        || {
+            assert!(audit(OutputFormat::Text).find_pii_fields().is_empty());
+        },
+        false,
+    )
+}
+
+// ++++++ //
+// render //
+// ++++++ //
+#[test]
+fn renders_text_table() {
+    test_in_tmp_dir(
+        || {
+            setup_data_sources();
+
+            let audit_pii = audit(OutputFormat::Text);
+            let text = console::strip_ansi_codes(&audit_pii.render(&audit_pii.find_pii_fields()))
+                .to_string();
+
+            assert_eq!(
+                text,
+                "data source  field      owner                 sensitivity\n\
+                patients     full_name  Florence Nightingale  restricted\n\
+                patients     postcode   Florence Nightingale  restricted"
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn renders_message_when_no_pii() {
+    let text = console::strip_ansi_codes(&audit(OutputFormat::Text).render(&[])).to_string();
+
+    assert_eq!(text, "No fields are flagged as PII");
+}
+
+#[test]
+fn renders_json() {
+    test_in_tmp_dir(
+        || {
+            setup_data_sources();
+
+            let audit_pii = audit(OutputFormat::Json);
+            let json: serde_json::Value =
+                serde_json::from_str(&audit_pii.render(&audit_pii.find_pii_fields())).unwrap();
+
+            assert_eq!(json[1]["field"], "postcode");
+            assert_eq!(json[1]["data_source"], "patients");
+            assert_eq!(json[1]["sensitivity"], "restricted");
+        },
+        false,
+    )
+}
@@ -1,10 +1,29 @@
 use clap::{AppSettings, Parser, Subcommand};
+use std::{io, path, process};
 
-#[cfg(feature = "documentation")]
-use std::path;
-
-use geoffrey::add_command::{Add, AddCommands};
+use geoffrey::add_command::{resolve_uses, Add, AddCommands, Component, DataSource};
+use geoffrey::adopt_command::Adopt;
+use geoffrey::apply_command::Apply;
+use geoffrey::archive_command::{Archive, Restore};
+use geoffrey::audit_command::{Audit, AuditCommands};
+use geoffrey::check_command::Check;
+use geoffrey::component::Stage;
 use geoffrey::create_command::Create;
+use geoffrey::data_source_command::{DataSourceCommands, DataSourceManager, FetchResult};
+use geoffrey::interview::{component_questions, data_source_questions, project_questions};
+use geoffrey::inventory;
+use geoffrey::lineage_command::{Graph, Impact, Link};
+use geoffrey::list_command::List;
+use geoffrey::manifest::Manifest;
+use geoffrey::migrate_command::{Migrate, FORMAT_VERSION};
+use geoffrey::promote_command::Promote;
+use geoffrey::remove_command::{Remove, Trash, TrashCommands};
+use geoffrey::rename_command::{Rename, RenameProject, Renumber};
+use geoffrey::retention_command::{Retention, RetentionCommands};
+use geoffrey::show_command::{Show, ShowCommands};
+use geoffrey::sync_command::{sync_readme, Sync, SyncCommands, README};
+use geoffrey::template_command::{self, Template, TemplateCommands};
+use geoffrey::verify_command::Verify;
 
 #[derive(Parser)]
 #[clap(version, about, long_about = None, setting = AppSettings::SubcommandRequiredElseHelp)]
@@ -25,8 +44,101 @@ enum Commands {
         #[clap(short, long, default_value = "./docs/", value_parser)]
         output: path::PathBuf,
     },
+    /// Turns an existing folder into a project managed by geoffrey
+    Adopt(Adopt),
+    /// Creates or updates a project to match a YAML description of it
+    Apply(Apply),
     /// Adds a new instance of a data source, exploration, model or product
     Add(Add),
+    /// Manages the files within an existing data source
+    DataSource(DataSourceManager),
+    /// Checks the data files in every data source against their checksums
+    Verify(Verify),
+    /// Shows the details recorded about part of the project
+    Show(Show),
+    /// Reports on how personal data is used across the project
+    Audit(Audit),
+    /// Checks and enforces how long data sources can be kept
+    Retention(Retention),
+    /// Finds placeholders and template text that haven't been filled in
+    Check(Check),
+    /// Records that a component uses other components
+    Link(Link),
+    /// Writes the lineage of the project's components as a graph
+    Graph(Graph),
+    /// Lists every component affected by a change to a component
+    Impact(Impact),
+    /// Lists the components in each stage
+    List(List),
+    /// Moves a component into its stage's archive
+    Archive(Archive),
+    /// Brings an archived component back
+    Restore(Restore),
+    /// Moves a component to the project's trash
+    Remove(Remove),
+    /// Manages the components that have been removed
+    Trash(Trash),
+    /// Renames a component and the references to it
+    Rename(Rename),
+    /// Renames the project and its folder
+    RenameProject(RenameProject),
+    /// Numbers the components of a stage from 1 again, closing any gaps
+    Renumber(Renumber),
+    /// Turns a component into one at a later stage, e.g an exploration into a model
+    Promote(Promote),
+    /// Regenerates the parts of the project's documents that geoff manages
+    Sync(Sync),
+    /// Upgrades a project created by an older version of geoffrey
+    Migrate(Migrate),
+    /// Works with the templates geoffrey generates documents from
+    Template(Template),
+}
+
+/// Adds an exploration, model or product and records what it uses
+fn add_component(component: &Component, stage: Stage) {
+    let component = &component.named(stage, &Manifest::load().naming);
+    let uses = resolve_uses(&component.uses);
+
+    let questions = component_questions(stage);
+    let skip: &[&str] = if component.owner.is_some() {
+        &["owner"]
+    } else {
+        &[]
+    };
+    let answers = component
+        .interview
+        .gather(&questions, skip, io::stdin().lock());
+
+    component.add(stage, &uses, &answers);
+    sync_readme();
+
+    println!("\u{1F680} {} created!\n", component.name.display());
+    ptree::print_tree(&component.create_tree(stage)).unwrap();
+}
+
+/// Adds a data source for each row of a CSV inventory
+fn add_from_csv(defaults: &DataSource, inventory_path: &path::Path) {
+    let uses = resolve_uses(&defaults.uses);
+    let naming = Manifest::load().naming;
+    let (rows, rejections) = inventory::read(inventory_path, &naming, defaults.slugify);
+
+    let (added, skipped) = inventory::add_all(rows, defaults, &uses, &naming);
+    sync_readme();
+
+    println!(
+        "\u{1F680} {} data sources created from {}!\n",
+        added.len(),
+        inventory_path.display()
+    );
+    ptree::print_tree(&inventory::create_tree(&added)).unwrap();
+
+    if !skipped.is_empty() || !rejections.is_empty() {
+        println!("\n{}", inventory::summary(&skipped, &rejections));
+    }
+
+    if !rejections.is_empty() {
+        process::exit(1);
+    }
 }
 
 fn main() {
@@ -36,9 +148,15 @@ fn main() {
 
     match &cli.command {
         Some(Commands::Create(create)) => {
+            let create = &create.named();
+            let answers = create
+                .interview
+                .gather(&project_questions(), &[], io::stdin().lock());
+
             create.create_root();
             create.create_subdirectories();
-            create.create_files();
+            create.create_files(&answers);
+            create.remember_templates();
             let tree = create.create_tree();
 
             let name_str = create
@@ -55,18 +173,101 @@ fn main() {
         Some(Commands::BuildDocs { output: _ }) => {
             println!("build docs matched");
         }
+        Some(Commands::Adopt(adopt)) => {
+            adopt.enter();
+
+            let mut input = io::stdin().lock();
+            let adoptions = adopt.plan(&adopt.mappings(&mut input), &Manifest::load().naming);
+
+            ptree::print_tree(&adopt.create_tree(&adoptions)).unwrap();
+            println!();
+
+            if !adopt.confirm(&mut input) {
+                println!("Nothing was adopted");
+                return;
+            }
+
+            adopt.create_stage_folders();
+            adopt.move_components(&adoptions);
+            let mut manifest = Manifest::load();
+            adopt.create_root_files(&mut manifest);
+            let components = adopt.register_components(&mut manifest, &adoptions);
+            manifest.save();
+            sync_readme();
+
+            println!(
+                "\u{1F680} {} adopted with {} components!",
+                adopt.project_name(),
+                components.len()
+            );
+        }
+        Some(Commands::Apply(apply)) => {
+            let spec = apply.load_spec();
+            let exists = apply.enter(&spec);
+            let manifest = if exists {
+                Manifest::load()
+            } else {
+                Manifest::default()
+            };
+            let plan = apply.plan(&spec, exists, &manifest);
+
+            if plan.is_empty() {
+                println!(
+                    "Nothing to create, {} already has everything in the spec",
+                    spec.name
+                );
+            } else {
+                if apply.dry_run {
+                    println!("Dry run, {} would be changed\n", spec.name);
+                } else {
+                    if plan.create_project {
+                        apply.create_project(&spec);
+                    }
+                    apply.apply(&spec, &plan);
+
+                    println!("\u{1F680} {} applied!\n", spec.name);
+                }
+
+                ptree::print_tree(&apply.create_tree(&spec, &plan)).unwrap();
+                for (component, uses) in plan.links.iter() {
+                    for used in uses.iter() {
+                        println!("\u{1F517} {} uses {}", component, used);
+                    }
+                }
+            }
+
+            if !plan.drift.is_empty() {
+                println!("\n{}", apply.drift_summary(&plan.drift));
+                process::exit(1);
+            }
+        }
         Some(Commands::Add(add)) => match &add.command {
             Some(AddCommands::DataSource(data_source)) => {
-                data_source.create_data_source();
+                if let Some(inventory_path) = &data_source.from_csv {
+                    add_from_csv(data_source, inventory_path);
+                    return;
+                }
+
+                let data_source = &data_source.named(&Manifest::load().naming);
+                let uses = resolve_uses(&data_source.uses);
 
-                let contents = data_source.retrieve_metadata_contents();
-                let updated_contents = data_source.update_placeholders(&contents);
-                data_source.create_metadata(&updated_contents);
+                let questions = data_source_questions(data_source.kind);
+                let skip: &[&str] = if data_source.owner.is_some() {
+                    &["owner"]
+                } else {
+                    &[]
+                };
+                let answers = data_source
+                    .interview
+                    .gather(&questions, skip, io::stdin().lock());
+
+                data_source.add(&uses, &answers);
+                sync_readme();
 
                 let tree = data_source.create_tree();
 
                 let name_str = data_source
-                    .name
+                    .name()
                     .file_name()
                     .unwrap()
                     .to_str()
@@ -75,6 +276,443 @@ fn main() {
                 println!("\u{1F680} {} created!\n", name_str);
                 ptree::print_tree(&tree).unwrap();
             }
+            Some(AddCommands::Exploration(exploration)) => {
+                add_component(exploration, Stage::Exploration)
+            }
+            Some(AddCommands::Model(model)) => add_component(model, Stage::Model),
+            Some(AddCommands::Product(product)) => add_component(product, Stage::Product),
+            None => {
+                println!("Matched none");
+            }
+        },
+        Some(Commands::DataSource(manager)) => match &manager.command {
+            Some(DataSourceCommands::Receive(receive)) => {
+                let receive = &receive.resolved();
+                let extract_path = receive.copy_extract();
+                let version = receive.summarise(&extract_path);
+
+                let mut manifest = Manifest::load();
+                let previous = receive.record_version(&mut manifest, version.clone());
+                manifest.save();
+
+                receive.update_metadata(&version);
+
+                println!("\u{1F4E5} {} received!\n", version.file);
+                ptree::print_tree(&receive.create_tree(&version)).unwrap();
+
+                if receive.diff {
+                    match previous {
+                        Some(previous) => {
+                            let diff = receive.compare_versions(&previous, &version);
+                            println!("\nChanges since {}\n{}", previous.file, diff.summary());
+                        }
+                        None => println!("\nNo previous version to compare with"),
+                    }
+                }
+            }
+            Some(DataSourceCommands::Lock(lock)) => {
+                let lock = &lock.resolved();
+                let checksums = lock.hash_data_files();
+
+                let mut manifest = Manifest::load();
+                lock.record_checksums(&mut manifest, checksums.clone());
+                manifest.save();
+
+                println!(
+                    "\u{1F512} {} data files locked in {}!\n",
+                    checksums.len(),
+                    lock.name.display()
+                );
+                ptree::print_tree(&lock.create_tree(&checksums)).unwrap();
+            }
+            Some(DataSourceCommands::Fetch(fetch)) => {
+                let fetch = &fetch.resolved();
+                // Checks the file can be saved before downloading it
+                fetch.file_name();
+                let mut manifest = Manifest::load();
+                let previous = if fetch.refresh {
+                    fetch.previous_download(&manifest).cloned()
+                } else {
+                    None
+                };
+
+                match fetch.download(previous.as_ref()) {
+                    FetchResult::Downloaded {
+                        bytes,
+                        etag,
+                        last_modified,
+                    } => {
+                        let download = fetch.save(&bytes, etag, last_modified);
+
+                        fetch.record_download(&mut manifest, download.clone());
+                        manifest.save();
+                        fetch.update_metadata(&download);
+
+                        println!("\u{1F4E5} {} downloaded!\n", download.file);
+                        ptree::print_tree(&fetch.create_tree(&download)).unwrap();
+                    }
+                    FetchResult::NotModified => {
+                        println!("{} is up to date", fetch.file_name());
+                    }
+                }
+            }
+            None => {
+                println!("Matched none");
+            }
+        },
+        Some(Commands::Verify(verify)) => {
+            let report = verify.verify(&Manifest::load());
+
+            println!("{}", report.summary());
+
+            if !report.is_clean() {
+                process::exit(1);
+            }
+        }
+        Some(Commands::Show(show)) => match &show.command {
+            Some(ShowCommands::DataSource(show_data_source)) => {
+                let data_source_metadata = show_data_source.read_metadata();
+
+                println!("{}", show_data_source.render(&data_source_metadata));
+            }
+            None => {
+                println!("Matched none");
+            }
+        },
+        Some(Commands::Retention(retention)) => match &retention.command {
+            Some(RetentionCommands::Check(check)) => {
+                let expiries = check.check(chrono::Local::now().date_naive());
+
+                println!("{}", check.summary(&expiries));
+
+                if expiries.iter().any(|expiry| expiry.is_expired()) {
+                    process::exit(1);
+                }
+            }
+            Some(RetentionCommands::Purge(purge)) => {
+                let purge = &purge.resolved();
+                let data_files = purge.data_files();
+
+                if data_files.is_empty() {
+                    println!(
+                        "Nothing to purge, {} has no data files",
+                        purge.name.display()
+                    );
+                    return;
+                }
+
+                if !purge.confirm(&data_files, io::stdin().lock()) {
+                    println!("Nothing was deleted");
+                    return;
+                }
+
+                purge.purge(&data_files);
+
+                let mut manifest = Manifest::load();
+                let logged = purge
+                    .record_purge(&mut manifest, &data_files)
+                    .expect("Data files were deleted");
+                manifest.save();
+
+                println!(
+                    "\u{1F5D1} {} data files purged from {}!\n",
+                    logged.files.len(),
+                    purge.name.display()
+                );
+                ptree::print_tree(&purge.create_tree(&logged)).unwrap();
+            }
+            None => {
+                println!("Matched none");
+            }
+        },
+        Some(Commands::Link(link)) => {
+            let mut manifest = Manifest::load();
+            let (component, uses) = link.link(&mut manifest);
+            manifest.save();
+
+            for used in uses.iter() {
+                println!("\u{1F517} {} uses {}", component, used);
+            }
+        }
+        Some(Commands::Graph(graph)) => {
+            println!("{}", graph.render(&Manifest::load()));
+        }
+        Some(Commands::Impact(impact)) => {
+            let manifest = Manifest::load();
+            let (component, affected) = impact.affected(&manifest);
+
+            println!(
+                "\u{1F4A5} {} components depend on {}\n",
+                affected.len(),
+                component
+            );
+            ptree::print_tree(&impact.create_tree(&manifest, &component)).unwrap();
+
+            if impact.mark_review {
+                let marked = impact.mark_for_review(&component, &affected);
+                println!("\n{} components marked as needing review", marked.len());
+            }
+        }
+        Some(Commands::List(list)) => {
+            ptree::print_tree(&list.create_tree(&Manifest::load())).unwrap();
+        }
+        Some(Commands::Archive(archive)) => {
+            let archive = &archive.resolved();
+            let mut manifest = Manifest::load();
+
+            let dependents = archive.dependents(&manifest);
+            if !dependents.is_empty() {
+                let yellow = console::Style::new().color256(220);
+                eprintln!(
+                    "{} {} {} {}",
+                    yellow.apply_to("Warning:"),
+                    archive.id(),
+                    yellow.apply_to("is still used by"),
+                    dependents
+                        .iter()
+                        .map(|component| component.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+
+            archive.archive(&mut manifest);
+            manifest.save();
+            sync_readme();
+
+            println!("\u{1F4E6} {} archived!\n", archive.id());
+            ptree::print_tree(&archive.create_tree()).unwrap();
+        }
+        Some(Commands::Restore(restore)) => {
+            let restore = &restore.resolved();
+            let mut manifest = Manifest::load();
+            restore.restore(&mut manifest);
+            manifest.save();
+            sync_readme();
+
+            println!("\u{1F4E4} {} restored!\n", restore.id());
+            ptree::print_tree(&restore.create_tree()).unwrap();
+        }
+        Some(Commands::Remove(remove)) => {
+            let remove = &remove.resolved();
+            let files = remove.files();
+            let mut manifest = Manifest::load();
+
+            ptree::print_tree(&remove.create_tree(&files)).unwrap();
+            println!();
+
+            if !remove.confirm(&manifest, io::stdin().lock()) {
+                println!("Nothing was removed");
+                return;
+            }
+
+            let trash_path = remove.remove(&mut manifest);
+            manifest.save();
+            sync_readme();
+
+            println!(
+                "\u{1F5D1} {} moved to {}",
+                remove.id(),
+                trash_path.display()
+            );
+        }
+        Some(Commands::Trash(trash)) => match &trash.command {
+            Some(TrashCommands::List(list)) => {
+                println!("{}", list.summary(&Manifest::load()));
+            }
+            Some(TrashCommands::Restore(restore)) => {
+                let mut manifest = Manifest::load();
+                restore.restore(&mut manifest);
+                manifest.save();
+                sync_readme();
+
+                println!("\u{1F4E4} {} restored!", restore.id());
+            }
+            Some(TrashCommands::Empty(empty)) => {
+                let mut manifest = Manifest::load();
+
+                if !empty.confirm(&manifest, io::stdin().lock()) {
+                    println!("Nothing was deleted");
+                    return;
+                }
+
+                let deleted = empty.empty(&mut manifest);
+                manifest.save();
+
+                println!("\u{1F5D1} {} components permanently deleted", deleted);
+            }
+            None => {
+                println!("Matched none");
+            }
+        },
+        Some(Commands::Rename(rename)) => {
+            let mut manifest = Manifest::load();
+
+            rename.move_folder(&manifest.naming);
+            let documents = rename.rewrite_documents();
+            rename.update_manifest(&mut manifest);
+            manifest.save();
+            sync_readme();
+
+            println!(
+                "\u{270F} {} renamed to {}!\n",
+                rename.old_id(),
+                rename.new_id()
+            );
+            ptree::print_tree(&rename.create_tree(&documents)).unwrap();
+        }
+        Some(Commands::RenameProject(rename)) => {
+            rename.validate();
+            let old = rename.old_name();
+            let documents = rename.rewrite_documents(&old);
+            let mut manifest = Manifest::load();
+            manifest.rename_project(&old, &rename.new);
+            manifest.save();
+            let renamed = rename.move_folder();
+
+            println!("\u{270F} {} renamed to {}!\n", old, rename.new);
+            ptree::print_tree(&rename.create_tree(&documents)).unwrap();
+            println!(
+                "\nThe project is now in {}, please change to it",
+                renamed.display()
+            );
+        }
+        Some(Commands::Renumber(renumber)) => {
+            let mut manifest = Manifest::load();
+            let renames = renumber.plan(&manifest.naming);
+
+            if renames.is_empty() {
+                println!(
+                    "Nothing to renumber, the {} are already in order",
+                    renumber.stage.folder()
+                );
+                return;
+            }
+
+            if renumber.dry_run {
+                println!("Dry run, these would be renumbered\n");
+                ptree::print_tree(&renumber.create_tree(&renames)).unwrap();
+                return;
+            }
+
+            for rename in renames.iter() {
+                rename.move_folder(&manifest.naming);
+                rename.rewrite_documents();
+                rename.update_manifest(&mut manifest);
+            }
+            manifest.save();
+            sync_readme();
+
+            println!(
+                "\u{270F} {} {} renumbered!\n",
+                renames.len(),
+                renumber.stage.folder()
+            );
+            ptree::print_tree(&renumber.create_tree(&renames)).unwrap();
+        }
+        Some(Commands::Promote(promote)) => {
+            let mut manifest = Manifest::load();
+
+            promote.validate(&manifest.naming);
+            let promote = &promote.named(&manifest.naming);
+            promote.create(&mut manifest);
+            promote.copy_files();
+            promote.write_provenance();
+            manifest.save();
+            sync_readme();
+
+            println!(
+                "\u{1F680} {} promoted to {}!\n",
+                promote.id(),
+                promote.new_id()
+            );
+            ptree::print_tree(&promote.create_tree()).unwrap();
+        }
+        Some(Commands::Sync(sync)) => match &sync.command {
+            Some(SyncCommands::Readme(_)) => {
+                let sections = sync_readme();
+
+                if sections == 0 {
+                    eprintln!(
+                        "{} {}",
+                        README,
+                        console::Style::new()
+                            .color256(9)
+                            .apply_to("doesn't have any sections managed by geoff")
+                    );
+                    process::exit(1);
+                }
+
+                println!("\u{1F504} {} sections in {} synced", sections, README);
+            }
+            None => {
+                println!("Matched none");
+            }
+        },
+        Some(Commands::Migrate(migrate)) => {
+            let from = migrate.current_format();
+
+            if from == FORMAT_VERSION {
+                println!("The project is already at format {}", FORMAT_VERSION);
+                return;
+            }
+
+            let (changes, steps) = migrate.plan(from);
+            ptree::print_tree(&migrate.create_tree(&steps)).unwrap();
+            println!();
+
+            if migrate.dry_run {
+                println!("Dry run, nothing was changed");
+                return;
+            }
+
+            let backup = migrate.backup(&changes);
+            migrate.apply(&changes);
+
+            println!(
+                "\u{2728} Migrated from format {} to {}, the original files are in {}",
+                from,
+                FORMAT_VERSION,
+                backup.display()
+            );
+        }
+        Some(Commands::Template(template)) => match &template.command {
+            Some(TemplateCommands::Update(update)) => {
+                let mut manifest = Manifest::load();
+                let outcomes = update.update_all(&mut manifest);
+
+                if !update.dry_run {
+                    manifest.save();
+                }
+
+                println!("{}", update.summary(&outcomes));
+
+                if outcomes
+                    .iter()
+                    .any(|(_, outcome)| *outcome == template_command::Outcome::Conflicted)
+                {
+                    process::exit(1);
+                }
+            }
+            None => {
+                println!("Matched none");
+            }
+        },
+        Some(Commands::Check(check)) => {
+            let findings = check.check();
+
+            println!("{}", check.summary(&findings));
+
+            if !findings.is_empty() {
+                process::exit(1);
+            }
+        }
+        Some(Commands::Audit(audit)) => match &audit.command {
+            Some(AuditCommands::Pii(audit_pii)) => {
+                let fields = audit_pii.find_pii_fields();
+
+                println!("{}", audit_pii.render(&fields));
+            }
             None => {
                 println!("Matched none");
             }
@@ -0,0 +1,213 @@
+use clap::{Args, ValueEnum};
+use console::Style;
+use std::{collections::BTreeSet, fmt, fs, path};
+
+use crate::add_command::{self, DataSourceKind};
+use crate::component::{list_components, Stage};
+use crate::data_source_command::list_data_sources;
+use crate::front_matter::{self, DataSourceFrontMatter};
+
+#[derive(Args)]
+pub struct Check {}
+
+/// Why a line still needs filling in
+#[derive(Debug, PartialEq)]
+pub enum Unfilled {
+    /// A `<<<...>>>` tag that was never replaced
+    Placeholder(String),
+    /// An example or instruction copied from the template unchanged
+    TemplateText(String),
+}
+
+impl fmt::Display for Unfilled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Unfilled::Placeholder(tag) => write!(f, "unreplaced tag {}", tag),
+            Unfilled::TemplateText(text) => write!(f, "template text {}", text),
+        }
+    }
+}
+
+/// A line in a document that hasn't been filled in
+#[derive(Debug, PartialEq)]
+pub struct Finding {
+    /// The path of the document relative to the project root
+    pub file: String,
+    /// The line number, starting from 1
+    pub line: usize,
+    /// The heading of the section the line is in
+    pub section: Option<String>,
+    /// What was left unfilled
+    pub unfilled: Unfilled,
+}
+
+/// Finds the `<<<...>>>` tags in a line
+fn _placeholder_tags(line: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut rest = line;
+
+    while let Some(start) = rest.find("<<<") {
+        match rest[start..].find(">>>") {
+            Some(end) => {
+                tags.push(rest[start..start + end + 3].to_string());
+                rest = &rest[start + end + 3..];
+            }
+            None => break,
+        }
+    }
+
+    tags
+}
+
+/// Lists the example and instruction lines in a template
+///
+/// These are the lines written in italics or as list items, which are meant
+/// to be replaced. Headings and table headers are left out as they are kept
+/// in the finished document.
+///
+/// # Arguments
+///
+/// * `template` - The contents of a template
+pub fn template_text(template: &str) -> BTreeSet<String> {
+    template
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| line.starts_with('*') || (line.starts_with('|') && line.contains("| *")))
+        .map(|line| line.to_string())
+        .collect()
+}
+
+/// Scans a document for unreplaced tags and untouched template text
+///
+/// # Arguments
+///
+/// * `file` - The path of the document relative to the project root
+/// * `contents` - The contents of the document
+/// * `template_lines` - The example and instruction lines of the templates
+///   the document could have been created from
+pub fn scan(file: &str, contents: &str, template_lines: &BTreeSet<String>) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let mut section = None;
+
+    for (idx, line) in contents.lines().enumerate() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with('#') {
+            section = Some(trimmed.trim_start_matches('#').trim().to_string());
+        }
+
+        let finding = |unfilled| Finding {
+            file: file.to_string(),
+            line: idx + 1,
+            section: section.clone(),
+            unfilled,
+        };
+
+        for tag in _placeholder_tags(trimmed) {
+            findings.push(finding(Unfilled::Placeholder(tag)));
+        }
+
+        if template_lines.contains(trimmed) {
+            findings.push(finding(Unfilled::TemplateText(trimmed.to_string())));
+        }
+    }
+
+    findings
+}
+
+impl Check {
+    /// Lists the generated documents along with the templates they came from
+    ///
+    /// A data source's template is picked from the kind in its front matter.
+    /// Data sources without a kind are compared with every data source
+    /// template. Explorations, models and products are compared with their
+    /// stage's README template.
+    pub fn documents(&self) -> Vec<(path::PathBuf, BTreeSet<String>)> {
+        let mut documents = vec![
+            (
+                path::PathBuf::from("README.md"),
+                template_text(include_str!("../templates/root/README.md")),
+            ),
+            (
+                path::PathBuf::from("project_scoping.md"),
+                template_text(include_str!("../templates/root/project_scoping.md")),
+            ),
+        ];
+
+        for name in list_data_sources() {
+            let data_source = path::Path::new("data_sources").join(&name);
+            let kind = front_matter::read_data_source(&data_source)
+                .ok()
+                .and_then(|front_matter: DataSourceFrontMatter| front_matter.kind);
+
+            let template_lines = match kind {
+                Some(kind) => template_text(add_command::metadata_template(Some(kind))),
+                None => DataSourceKind::value_variants()
+                    .iter()
+                    .flat_map(|kind| template_text(add_command::metadata_template(Some(*kind))))
+                    .collect(),
+            };
+
+            documents.push((data_source.join("metadata.md"), template_lines));
+        }
+
+        for stage in [Stage::Exploration, Stage::Model, Stage::Product] {
+            for component in list_components(stage) {
+                documents.push((
+                    component.path().join("README.md"),
+                    template_text(add_command::readme_template(stage)),
+                ));
+            }
+        }
+
+        documents
+    }
+
+    /// Scans every generated document that exists
+    pub fn check(&self) -> Vec<Finding> {
+        self.documents()
+            .into_iter()
+            .filter_map(|(document, template_lines)| {
+                let contents = fs::read_to_string(&document).ok()?;
+                let file = document.to_string_lossy().replace('\\', "/");
+
+                Some(scan(&file, &contents, &template_lines))
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Formats the findings with a line for each
+    ///
+    /// # Arguments
+    ///
+    /// * `findings` - The unfilled lines found in the documents
+    pub fn summary(&self, findings: &[Finding]) -> String {
+        if findings.is_empty() {
+            return format!(
+                "{}",
+                Style::new()
+                    .color256(10)
+                    .apply_to("Every document has been filled in")
+            );
+        }
+
+        let location = Style::new().color256(220);
+
+        findings
+            .iter()
+            .map(|finding| {
+                format!(
+                    "{} [{}] {}",
+                    location.apply_to(format!("{}:{}", finding.file, finding.line)),
+                    finding.section.as_deref().unwrap_or("-"),
+                    finding.unfilled
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,199 @@
+use std::{collections::BTreeSet, fs};
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+
+// +++++++++++++ //
+// template_text //
+// +++++++++++++ //
+#[test]
+fn template_text_finds_examples_and_instructions() {
+    let lines = template_text(
+        "# <<<project_name>>>\n\
+        ## Introduction\n\
+        *brief intro to project*\n\
+        Data sources\n\
+        * data source 1\n\
+        | name | details |\n\
+        | :--- | :------ |\n\
+        | *e.g Ronald Fisher* | *Gathered the data* |\n",
+    );
+
+    assert_eq!(
+        lines,
+        BTreeSet::from([
+            String::from("*brief intro to project*"),
+            String::from("* data source 1"),
+            String::from("| *e.g Ronald Fisher* | *Gathered the data* |"),
+        ])
+    );
+}
+
+// ++++ //
+// scan //
+// ++++ //
+#[test]
+fn scan_reports_tags_with_line_and_section() {
+    let findings = scan(
+        "README.md",
+        "# iris\n## Contributors\nAsk <<<owner>>> about <<<team>>>\n",
+        &BTreeSet::new(),
+    );
+
+    assert_eq!(
+        findings,
+        vec![
+            Finding {
+                file: String::from("README.md"),
+                line: 3,
+                section: Some(String::from("Contributors")),
+                unfilled: Unfilled::Placeholder(String::from("<<<owner>>>")),
+            },
+            Finding {
+                file: String::from("README.md"),
+                line: 3,
+                section: Some(String::from("Contributors")),
+                unfilled: Unfilled::Placeholder(String::from("<<<team>>>")),
+            },
+        ]
+    );
+}
+
+#[test]
+fn scan_reports_untouched_template_text() {
+    let template_lines = template_text("## Introduction\n*brief intro to project*\n");
+    let findings = scan(
+        "README.md",
+        "---\nproject: iris\n---\n\n# iris\n## Introduction\n  *brief intro to project*\n",
+        &template_lines,
+    );
+
+    assert_eq!(
+        findings,
+        vec![Finding {
+            file: String::from("README.md"),
+            line: 7,
+            section: Some(String::from("Introduction")),
+            unfilled: Unfilled::TemplateText(String::from("*brief intro to project*")),
+        }]
+    );
+}
+
+#[test]
+fn scan_ignores_filled_in_lines() {
+    let template_lines = template_text("## Introduction\n*brief intro to project*\n");
+    let findings = scan(
+        "README.md",
+        "# iris\n## Introduction\nClassifying *iris* flowers\n",
+        &template_lines,
+    );
+
+    assert!(findings.is_empty());
+}
+
+// +++++ //
+// check //
+// +++++ //
+#[test]
+fn check_uses_data_source_kind_template() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+            fs::write(
+                "data_sources/iris/metadata.md",
+                "---\nname: iris\nkind: extract\ncreated: 2022-04-01\n---\n\n# iris\n\n\
+                ## Key people\n\n| name | details |\n| :--- | :------ |\n\
+                | *e.g Ronald Fisher* | *Gathered the data and is the subject metter expert. Ron and the data collection team have access to the database and extracted it to a csv* |\n",
+            )
+            .unwrap();
+
+            let findings = Check {}.check();
+
+            assert_eq!(findings.len(), 1);
+            assert_eq!(findings[0].file, "data_sources/iris/metadata.md");
+            assert_eq!(findings[0].line, 13);
+            assert_eq!(findings[0].section, Some(String::from("Key people")));
+        },
+        false,
+    )
+}
+
+#[test]
+fn check_without_kind_compares_every_template() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+            fs::write(
+                "data_sources/iris/metadata.md",
+                "# iris\n\n## Website details\n\n\
+                | *e.g iris.data* | *e.g https://archive.ics.uci.edu/ml/machine-learning-databases/iris/iris.data* | *e.g 2022-01-01* | *e.g https://archive.ics.uci.edu/ml/datasets/iris* |\n",
+            )
+            .unwrap();
+
+            let findings = Check {}.check();
+
+            assert_eq!(findings.len(), 1);
+            assert_eq!(findings[0].line, 5);
+            assert_eq!(findings[0].section, Some(String::from("Website details")));
+        },
+        false,
+    )
+}
+
+#[test]
+fn check_includes_component_readmes() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("models/classifier").unwrap();
+            fs::write(
+                "models/classifier/README.md",
+                "# classifier\n\n## Limitations\n*Where the model shouldn't be used or is known to perform poorly*\n",
+            )
+            .unwrap();
+
+            let findings = Check {}.check();
+
+            assert_eq!(findings.len(), 1);
+            assert_eq!(findings[0].file, "models/classifier/README.md");
+            assert_eq!(findings[0].section, Some(String::from("Limitations")));
+        },
+        false,
+    )
+}
+
+#[test]
+fn check_skips_missing_documents() {
+    test_in_tmp_dir(
+        || {
+            assert!(Check {}.check().is_empty());
+        },
+        false,
+    )
+}
+
+// +++++++ //
+// summary //
+// +++++++ //
+#[test]
+fn summary_lists_findings() {
+    let findings = vec![Finding {
+        file: String::from("README.md"),
+        line: 3,
+        section: Some(String::from("Introduction")),
+        unfilled: Unfilled::TemplateText(String::from("*brief intro to project*")),
+    }];
+
+    let summary = console::strip_ansi_codes(&Check {}.summary(&findings)).to_string();
+
+    assert_eq!(
+        summary,
+        "README.md:3 [Introduction] template text *brief intro to project*"
+    );
+}
+
+#[test]
+fn summary_when_everything_filled_in() {
+    let summary = console::strip_ansi_codes(&Check {}.summary(&[])).to_string();
+
+    assert_eq!(summary, "Every document has been filled in");
+}
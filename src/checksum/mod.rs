@@ -0,0 +1,44 @@
+use sha2::{Digest, Sha256};
+use std::{fs, io, path};
+
+/// Calculates the SHA-256 hash of some bytes
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes to hash
+///
+/// # Return value
+///
+/// The hash as a lowercase hex string
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    _to_hex(&Sha256::digest(bytes))
+}
+
+/// Calculates the SHA-256 hash of a file's contents
+///
+/// The file is streamed through the hasher so large extracts don't need to be
+/// read into memory.
+///
+/// # Arguments
+///
+/// * `file_path` - The file to hash
+///
+/// # Errors
+///
+/// * The file doesn't exist or can't be read
+pub fn file_sha256(file_path: &path::Path) -> io::Result<String> {
+    let mut file = fs::File::open(file_path)?;
+    let mut hasher = Sha256::new();
+
+    io::copy(&mut file, &mut hasher)?;
+
+    Ok(_to_hex(&hasher.finalize()))
+}
+
+/// Formats a digest as a lowercase hex string
+fn _to_hex(digest: &[u8]) -> String {
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests;
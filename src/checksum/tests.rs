@@ -0,0 +1,43 @@
+use std::{fs, path};
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+
+// ++++++++++ //
+// sha256_hex //
+// ++++++++++ //
+#[test]
+fn hashes_bytes() {
+    assert_eq!(
+        sha256_hex(b"abc"),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+}
+
+// +++++++++++ //
+// file_sha256 //
+// +++++++++++ //
+#[test]
+fn file_hash_matches_bytes_hash() {
+    test_in_tmp_dir(
+        || {
+            fs::write("iris.csv", "id,sepal_width\n1,3.5\n").unwrap();
+
+            assert_eq!(
+                file_sha256(path::Path::new("iris.csv")).unwrap(),
+                sha256_hex(b"id,sepal_width\n1,3.5\n")
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn missing_file_errors() {
+    test_in_tmp_dir(
+        || {
+            assert!(file_sha256(path::Path::new("iris.csv")).is_err());
+        },
+        false,
+    )
+}
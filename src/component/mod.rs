@@ -0,0 +1,390 @@
+use clap::ValueEnum;
+use console::Style;
+use serde::{Deserialize, Serialize};
+use std::{fmt, fs, path, str};
+
+/// The folder within each stage that archived components are moved into
+pub const ARCHIVE_FOLDER: &str = "_archive";
+
+/// The stages of a project, in the order data flows through them
+#[derive(
+    ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Stage {
+    /// Where the raw data comes from
+    DataSource,
+    /// Investigations into the data
+    Exploration,
+    /// Models trained on the data
+    Model,
+    /// What is delivered to the business
+    Product,
+}
+
+impl Stage {
+    /// Every stage in pipeline order
+    pub const ALL: [Stage; 4] = [
+        Stage::DataSource,
+        Stage::Exploration,
+        Stage::Model,
+        Stage::Product,
+    ];
+
+    /// The folder in the project root that holds the stage's components
+    pub fn folder(&self) -> &'static str {
+        match self {
+            Stage::DataSource => "data_sources",
+            Stage::Exploration => "explorations",
+            Stage::Model => "models",
+            Stage::Product => "products",
+        }
+    }
+
+    /// Finds the stage that a folder in the project root belongs to
+    ///
+    /// # Arguments
+    ///
+    /// * `folder` - The name of the folder, e.g data_sources
+    pub fn from_folder(folder: &str) -> Option<Stage> {
+        Stage::ALL
+            .into_iter()
+            .find(|stage| stage.folder() == folder)
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = self
+            .to_possible_value()
+            .expect("Every stage has a possible value");
+
+        write!(f, "{}", value.get_name())
+    }
+}
+
+/// A data source, exploration, model or product in the project
+///
+/// Components are written as the stage folder and name joined with a `/`,
+/// e.g `data_sources/iris`, which is also their path from the project root.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct ComponentId {
+    pub stage: Stage,
+    pub name: String,
+}
+
+impl ComponentId {
+    /// Creates the id of a component
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The stage the component belongs to
+    /// * `name` - The name of the component
+    pub fn new(stage: Stage, name: &str) -> ComponentId {
+        ComponentId {
+            stage,
+            name: name.to_string(),
+        }
+    }
+
+    /// The folder of the component relative to the project root
+    pub fn path(&self) -> path::PathBuf {
+        path::Path::new(self.stage.folder()).join(&self.name)
+    }
+
+    /// The folder the component is moved to when it's archived
+    pub fn archive_path(&self) -> path::PathBuf {
+        path::Path::new(self.stage.folder())
+            .join(ARCHIVE_FOLDER)
+            .join(&self.name)
+    }
+
+    /// The document holding the component's front matter, metadata.md for
+    /// data sources and README.md for everything else
+    pub fn document(&self) -> path::PathBuf {
+        match self.stage {
+            Stage::DataSource => self.path().join("metadata.md"),
+            _ => self.path().join("README.md"),
+        }
+    }
+
+    /// Whether the component's folder exists
+    pub fn exists(&self) -> bool {
+        self.path().is_dir()
+    }
+
+    /// Finds the component a name refers to
+    ///
+    /// Names can either be written with their stage folder, e.g
+    /// `data_sources/iris`, or on their own if only one component has that
+    /// name. A name that isn't a component is taken to be the start of one
+    /// if only one component starts with it, e.g `ir` for `iris`.
+    ///
+    /// # Arguments
+    ///
+    /// * `reference` - The component as written by the user
+    ///
+    /// # Errors
+    ///
+    /// * The component doesn't exist, in which case similar names are
+    ///   suggested
+    /// * More than one component has or starts with the name
+    pub fn resolve(reference: &str) -> ComponentId {
+        match reference.parse::<ComponentId>() {
+            Ok(component) => ComponentId::find(component.stage, &component.name),
+            Err(_) => _lookup(
+                reference,
+                reference,
+                Stage::ALL
+                    .iter()
+                    .flat_map(|stage| list_components(*stage))
+                    .collect(),
+                "isn't a component in this project",
+            ),
+        }
+    }
+
+    /// Finds the component of a stage a name refers to, see
+    /// [ComponentId::resolve]
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The stage the component belongs to
+    /// * `name` - The name as written by the user
+    ///
+    /// # Errors
+    ///
+    /// * The component doesn't exist, in which case similar names are
+    ///   suggested
+    /// * More than one component starts with the name
+    pub fn find(stage: Stage, name: &str) -> ComponentId {
+        _lookup(
+            &ComponentId::new(stage, name).to_string(),
+            name,
+            list_components(stage),
+            "isn't a component in this project",
+        )
+    }
+
+    /// Finds the archived component of a stage a name refers to, see
+    /// [ComponentId::find]
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The stage the component belongs to
+    /// * `name` - The name as written by the user
+    ///
+    /// # Errors
+    ///
+    /// * The component isn't archived, in which case similar names are
+    ///   suggested
+    /// * More than one archived component starts with the name
+    pub fn find_archived(stage: Stage, name: &str) -> ComponentId {
+        _lookup(
+            &ComponentId::new(stage, name).to_string(),
+            name,
+            list_archived(stage),
+            "isn't an archived component in this project",
+        )
+    }
+}
+
+/// The most edits a name can be from a component's name for the component
+/// to be suggested
+fn _max_distance(name: &str) -> usize {
+    (name.chars().count() / 3).max(2)
+}
+
+/// Finds the components whose names are close to a name that doesn't exist
+///
+/// # Arguments
+///
+/// * `name` - The name as written by the user
+/// * `components` - The components it could have meant
+///
+/// # Return value
+///
+/// Up to 3 components, closest first
+pub fn suggest(name: &str, components: &[ComponentId]) -> Vec<ComponentId> {
+    let mut ranked: Vec<(usize, &ComponentId)> = components
+        .iter()
+        .map(|component| (strsim::levenshtein(name, &component.name), component))
+        .filter(|(distance, _)| *distance <= _max_distance(name))
+        .collect();
+    ranked.sort();
+
+    ranked
+        .into_iter()
+        .take(3)
+        .map(|(_, component)| component.clone())
+        .collect()
+}
+
+/// Finds the one component a name refers to
+///
+/// # Arguments
+///
+/// * `reference` - The component as written by the user, used in errors
+/// * `name` - The name to match
+/// * `components` - The components it could refer to
+/// * `missing` - Why the component wasn't found, e.g isn't a component in
+///   this project
+fn _lookup(
+    reference: &str,
+    name: &str,
+    components: Vec<ComponentId>,
+    missing: &str,
+) -> ComponentId {
+    let red = Style::new().color256(9);
+    let list = |components: &[ComponentId]| {
+        components
+            .iter()
+            .map(|component| component.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let exact: Vec<ComponentId> = components
+        .iter()
+        .filter(|component| component.name == name)
+        .cloned()
+        .collect();
+    if exact.len() > 1 {
+        eprintln!(
+            "{} {} {}",
+            reference,
+            red.apply_to("is the name of more than one component, use one of"),
+            list(&exact).join(", ")
+        );
+        panic!("{} is ambiguous", reference);
+    }
+    if let [component] = exact.as_slice() {
+        return component.clone();
+    }
+
+    let starting: Vec<ComponentId> = components
+        .iter()
+        .filter(|component| !name.is_empty() && component.name.starts_with(name))
+        .cloned()
+        .collect();
+    match starting.as_slice() {
+        [component] => component.clone(),
+        [_, _, ..] => {
+            eprintln!(
+                "{} {} {}",
+                reference,
+                red.apply_to("is the start of more than one component, use one of"),
+                list(&starting).join(", ")
+            );
+            panic!("{} is ambiguous", reference);
+        }
+        [] => {
+            let mut suggestions = list(&suggest(name, &components));
+            let hint = match suggestions.pop() {
+                Some(last) if suggestions.is_empty() => format!(", did you mean {}?", last),
+                Some(last) => format!(", did you mean {} or {}?", suggestions.join(", "), last),
+                None => String::new(),
+            };
+
+            eprintln!(
+                "{} {}",
+                reference,
+                red.apply_to(format!("{}{}", missing, hint))
+            );
+            panic!("{} doesn't exist", reference);
+        }
+    }
+}
+
+impl fmt::Display for ComponentId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.stage.folder(), self.name)
+    }
+}
+
+impl str::FromStr for ComponentId {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<ComponentId, String> {
+        let (folder, name) = value
+            .split_once('/')
+            .ok_or_else(|| format!("{} isn't written as <stage folder>/<name>", value))?;
+
+        let stage =
+            Stage::from_folder(folder).ok_or_else(|| format!("{} isn't a stage folder", folder))?;
+
+        if name.is_empty() || name.contains('/') {
+            return Err(format!("{} isn't a valid component name", name));
+        }
+
+        Ok(ComponentId::new(stage, name))
+    }
+}
+
+impl TryFrom<String> for ComponentId {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<ComponentId, String> {
+        value.parse()
+    }
+}
+
+impl From<ComponentId> for String {
+    fn from(component: ComponentId) -> String {
+        component.to_string()
+    }
+}
+
+/// Lists the names of the folders within a folder
+fn _folder_names(folder: &path::Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(folder)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    names.sort();
+    names
+}
+
+/// Lists the components of a stage
+///
+/// Archived components are left out.
+///
+/// # Arguments
+///
+/// * `stage` - The stage to list
+///
+/// # Return value
+///
+/// The components in the stage's folder, sorted by name
+pub fn list_components(stage: Stage) -> Vec<ComponentId> {
+    _folder_names(path::Path::new(stage.folder()))
+        .into_iter()
+        .filter(|name| name != ARCHIVE_FOLDER)
+        .map(|name| ComponentId::new(stage, &name))
+        .collect()
+}
+
+/// Lists the archived components of a stage
+///
+/// # Arguments
+///
+/// * `stage` - The stage to list
+///
+/// # Return value
+///
+/// The components in the stage's archive folder, sorted by name
+pub fn list_archived(stage: Stage) -> Vec<ComponentId> {
+    _folder_names(&path::Path::new(stage.folder()).join(ARCHIVE_FOLDER))
+        .into_iter()
+        .map(|name| ComponentId::new(stage, &name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
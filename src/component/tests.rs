@@ -0,0 +1,265 @@
+use rstest::rstest;
+use std::fs;
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+
+// +++++ //
+// Stage //
+// +++++ //
+#[rstest]
+#[case(Stage::DataSource, "data_sources")]
+#[case(Stage::Exploration, "explorations")]
+#[case(Stage::Model, "models")]
+#[case(Stage::Product, "products")]
+fn stage_folders_round_trip(#[case] stage: Stage, #[case] folder: &str) {
+    assert_eq!(stage.folder(), folder);
+    assert_eq!(Stage::from_folder(folder), Some(stage));
+}
+
+#[test]
+fn stage_displays_as_argument() {
+    assert_eq!(Stage::DataSource.to_string(), "data-source");
+}
+
+// +++++++++++ //
+// ComponentId //
+// +++++++++++ //
+#[test]
+fn parses_component_id() {
+    assert_eq!(
+        "explorations/eda".parse::<ComponentId>(),
+        Ok(ComponentId::new(Stage::Exploration, "eda"))
+    );
+}
+
+#[rstest]
+#[case("eda")]
+#[case("notebooks/eda")]
+#[case("explorations/")]
+#[case("explorations/eda/plots")]
+fn invalid_component_id_errors(#[case] value: &str) {
+    assert!(value.parse::<ComponentId>().is_err());
+}
+
+#[test]
+fn component_id_displays_as_path() {
+    let component = ComponentId::new(Stage::DataSource, "iris");
+
+    assert_eq!(component.to_string(), "data_sources/iris");
+    assert_eq!(component.path(), path::PathBuf::from("data_sources/iris"));
+}
+
+#[test]
+fn resolves_component_with_stage() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+            fs::create_dir_all("models/iris").unwrap();
+
+            assert_eq!(
+                ComponentId::resolve("models/iris"),
+                ComponentId::new(Stage::Model, "iris")
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn resolves_unique_name() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("explorations/eda").unwrap();
+
+            assert_eq!(
+                ComponentId::resolve("eda"),
+                ComponentId::new(Stage::Exploration, "eda")
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "iris is ambiguous")]
+fn resolve_ambiguous_name_panics() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+            fs::create_dir_all("models/iris").unwrap();
+
+            ComponentId::resolve("iris");
+        },
+        true,
+    )
+}
+
+#[rstest]
+#[case("iris")]
+#[case("models/iris")]
+#[should_panic(expected = "doesn't exist")]
+fn resolve_missing_component_panics(#[case] reference: &str) {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+            fs::remove_dir("data_sources/iris").unwrap();
+
+            ComponentId::resolve(reference);
+        },
+        true,
+    )
+}
+
+#[test]
+fn resolves_start_of_name() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+            fs::create_dir_all("models/classifier").unwrap();
+
+            assert_eq!(
+                ComponentId::resolve("ir"),
+                ComponentId::new(Stage::DataSource, "iris")
+            );
+            assert_eq!(
+                ComponentId::resolve("models/class"),
+                ComponentId::new(Stage::Model, "classifier")
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn exact_name_beats_start_of_name() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+            fs::create_dir_all("data_sources/iris_2022").unwrap();
+
+            assert_eq!(
+                ComponentId::find(Stage::DataSource, "iris"),
+                ComponentId::new(Stage::DataSource, "iris")
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "data_sources/ir is ambiguous")]
+fn ambiguous_start_of_name_panics() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+            fs::create_dir_all("data_sources/irises").unwrap();
+
+            ComponentId::find(Stage::DataSource, "ir");
+        },
+        true,
+    )
+}
+
+#[test]
+#[should_panic(expected = "data_sources/ doesn't exist")]
+fn empty_name_doesnt_match_start() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+
+            ComponentId::find(Stage::DataSource, "");
+        },
+        true,
+    )
+}
+
+#[test]
+fn finds_archived_by_start_of_name() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("explorations/churn").unwrap();
+            fs::create_dir_all("explorations/_archive/churn_2021").unwrap();
+
+            assert_eq!(
+                ComponentId::find_archived(Stage::Exploration, "churn_"),
+                ComponentId::new(Stage::Exploration, "churn_2021")
+            );
+        },
+        false,
+    )
+}
+
+// +++++++ //
+// suggest //
+// +++++++ //
+#[test]
+fn suggests_closest_names_first() {
+    let components = vec![
+        ComponentId::new(Stage::DataSource, "iris"),
+        ComponentId::new(Stage::DataSource, "irises"),
+        ComponentId::new(Stage::DataSource, "penguins"),
+        ComponentId::new(Stage::Model, "iris"),
+    ];
+
+    assert_eq!(
+        suggest("irsi", &components),
+        vec![
+            ComponentId::new(Stage::DataSource, "iris"),
+            ComponentId::new(Stage::Model, "iris"),
+        ]
+    );
+    assert_eq!(
+        suggest("irise", &components),
+        vec![
+            ComponentId::new(Stage::DataSource, "iris"),
+            ComponentId::new(Stage::DataSource, "irises"),
+            ComponentId::new(Stage::Model, "iris"),
+        ]
+    );
+    assert!(suggest("sales", &components).is_empty());
+}
+
+// +++++++++++++++ //
+// list_components //
+// +++++++++++++++ //
+#[test]
+fn lists_components_sorted() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("models/b").unwrap();
+            fs::create_dir_all("models/a").unwrap();
+            fs::create_dir_all("models/_archive/c").unwrap();
+            fs::write("models/notes.md", "").unwrap();
+
+            assert_eq!(
+                list_components(Stage::Model),
+                vec![
+                    ComponentId::new(Stage::Model, "a"),
+                    ComponentId::new(Stage::Model, "b"),
+                ]
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn lists_archived_components() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("models/a").unwrap();
+            fs::create_dir_all("models/_archive/c").unwrap();
+
+            assert_eq!(
+                list_archived(Stage::Model),
+                vec![ComponentId::new(Stage::Model, "c")]
+            );
+            assert_eq!(
+                ComponentId::new(Stage::Model, "c").archive_path(),
+                path::PathBuf::from("models/_archive/c")
+            );
+        },
+        false,
+    )
+}
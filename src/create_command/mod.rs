@@ -2,11 +2,19 @@ use clap::Args;
 use console::Style;
 use log::{debug, info};
 use ptree::{item, TreeBuilder};
-use std::{collections, fs, io, path};
+use std::{
+    collections::{self, BTreeMap},
+    fs, io, path,
+};
 
+use crate::front_matter::{self, ProjectFrontMatter};
+use crate::interview::{self, Interview};
+use crate::manifest::{Manifest, MANIFEST_FILE};
+use crate::naming::{self, NamingConfig};
 use crate::pleasant_error::PleasantErrorHandler;
+use crate::template_command;
 
-#[derive(Args)]
+#[derive(Args, Clone)]
 pub struct Create {
     /// The name of the project to create
     #[clap(value_parser)]
@@ -15,6 +23,17 @@ pub struct Create {
     /// Whether to create the parent directories in the project name
     #[clap(short, long)]
     pub parents: bool,
+
+    /// Makes a name from the one given, keeping it as the title
+    #[clap(long)]
+    pub slugify: bool,
+
+    /// The name as it was written, set when the name is made from it
+    #[clap(skip)]
+    pub title: Option<String>,
+
+    #[clap(flatten)]
+    pub interview: Interview,
 }
 
 impl PleasantErrorHandler for Create {}
@@ -34,6 +53,47 @@ impl Create {
         Style::new().color256(col_256)
     }
 
+    /// The name of the project, the last part of the path it's created at
+    fn _project_name(&self) -> &str {
+        self.name.file_name().unwrap().to_str().unwrap()
+    }
+
+    /// The title of the project, which is its name unless the name was made
+    /// from it
+    fn _title(&self) -> &str {
+        self.title
+            .as_deref()
+            .unwrap_or_else(|| self._project_name())
+    }
+
+    /// Checks the name of the project can be used
+    ///
+    /// Only the last part of the path is checked, the parents are left as
+    /// they are.
+    ///
+    /// # Return value
+    ///
+    /// The project with the name made from the one given if `--slugify` was
+    /// passed, with the name given kept as its title
+    ///
+    /// # Errors
+    ///
+    /// * The name breaks one of the rules
+    pub fn named(&self) -> Create {
+        let input = self
+            .name
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        let checked = naming::check(input, None, &NamingConfig::default(), self.slugify);
+
+        Create {
+            name: self.name.with_file_name(checked.name),
+            title: checked.title,
+            ..self.clone()
+        }
+    }
+
     /// Replaces placeholder tags in the template files
     ///
     /// Each of the template files has tags that are replaced with information
@@ -44,16 +104,23 @@ impl Create {
     /// # Arguments
     ///
     /// * `file_contents` - Refernce to a string literal containing the contents
-    ///                     of a template file
+    ///   of a template file
     ///
     /// # Returns
     ///
     /// * A String containing the file_contents with placeholders replaced
     fn _update_placeholders(&self, file_contents: &&str) -> String {
-        file_contents.replace(
-            "<<<project_name>>>",
-            self.name.file_stem().unwrap().to_str().unwrap(),
-        )
+        file_contents.replace("<<<project_name>>>", self._title())
+    }
+
+    /// Creates the front matter added to the top of the root documents
+    fn _front_matter(&self) -> ProjectFrontMatter {
+        ProjectFrontMatter {
+            project: self._project_name().to_string(),
+            title: self.title.clone(),
+            created: front_matter::today(),
+            extra: collections::BTreeMap::new(),
+        }
     }
 
     /// Creates the root directory of a new project
@@ -89,50 +156,102 @@ impl Create {
     /// * models
     /// * products
     pub fn create_subdirectories(&self) {
-        let subdirs = vec!["data_sources", "explorations", "models", "products"];
+        let subdirs = ["data_sources", "explorations", "models", "products"];
 
         for subdir in subdirs.iter() {
             info!("Creating project sub directory: {}", &subdir);
 
             let full_subdir = &format!("{}/{}/", &self.name.display(), &subdir);
-            fs::create_dir(&full_subdir)
+            fs::create_dir(full_subdir)
                 .unwrap_or_else(|_| panic!("Unable to create {}", &full_subdir));
         }
     }
 
-    /// Creates the files within the project root
+    /// Lists the files created in the project root with their contents
+    ///
+    /// The markdown documents start with a front matter block holding the
+    /// project name and the date it was created.
     ///
-    /// Creates 3 files
+    /// There are 3 files
     /// * README.md - General introduction to the project
     /// * project_scoping.md - The project scoping template to be filled out at the start of each project
-    /// * .geoff - A blank file to indicate this directory is managed by geoff
-    pub fn create_files(&self) {
-        let files = collections::HashMap::from([
+    /// * .geoff - Indicates this directory is managed by geoff and records the format of the project
+    pub fn root_files(&self) -> Vec<(&'static str, String)> {
+        let files = [
             ("README.md", include_str!("../templates/root/README.md")),
             (
                 "project_scoping.md",
                 include_str!("../templates/root/project_scoping.md"),
             ),
             (".geoff", include_str!("../templates/root/.geoff")),
-        ]);
+        ];
+
+        files
+            .iter()
+            .map(|(filename, contents)| {
+                debug!("Replacing placeholders in {}", filename);
+
+                let updated_contents: String = if !filename.starts_with('.') {
+                    front_matter::replace(
+                        &self._update_placeholders(contents),
+                        &self._front_matter(),
+                    )
+                } else {
+                    contents.to_string()
+                };
+
+                (*filename, updated_contents)
+            })
+            .collect()
+    }
 
-        for (filename, contents) in files.iter() {
-            debug!("Replacing placeholders in {}", filename);
+    /// Creates the files within the project root
+    ///
+    /// See [Create::root_files] for the files that are created.
+    ///
+    /// # Arguments
+    ///
+    /// * `answers` - The answers to the project questions, which are put into
+    ///   the markdown documents
+    pub fn create_files(&self, answers: &BTreeMap<String, String>) {
+        let questions = interview::project_questions();
 
-            let updated_contents: String = if !filename.starts_with('.') {
-                self._update_placeholders(contents)
+        for (filename, contents) in self.root_files().iter() {
+            info!("Writing {} to root folder", filename);
+
+            let contents = if filename.ends_with(".md") {
+                interview::apply(contents, &questions, answers)
             } else {
                 contents.to_string()
             };
 
-            info!("Writing {} to root folder", filename);
-
             let root_path: &String = &format!("{}/{}", &self.name.display(), filename);
-            fs::write(&root_path, &updated_contents)
+            fs::write(root_path, contents)
                 .unwrap_or_else(|_| panic!("Unable to copy to {}", &root_path));
         }
     }
 
+    /// Records the templates the root documents were generated from
+    ///
+    /// The records are written to a new manifest in the project root so the
+    /// documents can be updated when the templates change.
+    pub fn remember_templates(&self) {
+        let mut manifest = Manifest::default();
+        let project = self._title();
+
+        for document in ["README.md", "project_scoping.md"] {
+            template_command::remember(
+                &mut manifest,
+                &self.name,
+                document,
+                &format!("root/{}", document),
+                project,
+            );
+        }
+
+        manifest.save_to(&self.name.join(MANIFEST_FILE));
+    }
+
     /// Creates a tree showing the files and folders created
     ///
     /// The tree shows all the files and folders that have been created when running
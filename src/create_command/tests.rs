@@ -9,10 +9,13 @@ use super::*;
 // +++++++++++++++++++++++ //
 #[test]
 #[should_panic(expected = "test_project/ exists")]
-fn project_name_already_exists_error_raised() -> () {
+fn project_name_already_exists_error_raised() {
     let create: Create = Create {
         name: path::PathBuf::from("./test_project/"),
         parents: false,
+        slugify: false,
+        title: None,
+        interview: Interview::default(),
     };
 
     let already_exists_err = io::Error::new(io::ErrorKind::AlreadyExists, "test_error");
@@ -23,10 +26,13 @@ fn project_name_already_exists_error_raised() -> () {
 
 #[test]
 #[should_panic(expected = "Parents don't exist")]
-fn parents_dont_exist_error_raised() -> () {
+fn parents_dont_exist_error_raised() {
     let create: Create = Create {
         name: path::PathBuf::from("./path/to/test_project/"),
         parents: false,
+        slugify: false,
+        title: None,
+        interview: Interview::default(),
     };
 
     let not_found_err = io::Error::new(io::ErrorKind::NotFound, "test_error");
@@ -37,10 +43,13 @@ fn parents_dont_exist_error_raised() -> () {
 
 #[test]
 #[should_panic(expected = "Invalid permissions")]
-fn invalid_permissions_error_raised() -> () {
+fn invalid_permissions_error_raised() {
     let create: Create = Create {
         name: path::PathBuf::from("./test_project"),
         parents: false,
+        slugify: false,
+        title: None,
+        interview: Interview::default(),
     };
 
     let permission_denied_err = io::Error::new(io::ErrorKind::PermissionDenied, "test_error");
@@ -51,10 +60,13 @@ fn invalid_permissions_error_raised() -> () {
 
 #[test]
 #[should_panic(expected = "Unknown error")]
-fn generic_error_raised() -> () {
+fn generic_error_raised() {
     let create: Create = Create {
         name: path::PathBuf::from("./test_project"),
         parents: false,
+        slugify: false,
+        title: None,
+        interview: Interview::default(),
     };
 
     let invalid_data_err = io::Error::new(io::ErrorKind::InvalidData, "test_error");
@@ -67,12 +79,15 @@ fn generic_error_raised() -> () {
 // create_root //
 // +++++++++++ //
 #[test]
-fn creates_new_directory_no_parents() -> () {
+fn creates_new_directory_no_parents() {
     test_in_tmp_dir(
         || {
             let create: Create = Create {
                 name: path::PathBuf::from("./test_project/"),
                 parents: false,
+                slugify: false,
+                title: None,
+                interview: Interview::default(),
             };
 
             create.create_root();
@@ -84,12 +99,15 @@ fn creates_new_directory_no_parents() -> () {
 }
 
 #[test]
-fn creates_new_directory_with_parents() -> () {
+fn creates_new_directory_with_parents() {
     test_in_tmp_dir(
         || {
             let create: Create = Create {
                 name: path::PathBuf::from("./path/to/test_project"),
                 parents: true,
+                slugify: false,
+                title: None,
+                interview: Interview::default(),
             };
 
             create.create_root();
@@ -102,7 +120,7 @@ fn creates_new_directory_with_parents() -> () {
 
 #[test]
 #[should_panic(expected = "test_project/ exists")]
-fn errors_on_dir_already_exists() -> () {
+fn errors_on_dir_already_exists() {
     test_in_tmp_dir(
         || {
             fs::create_dir("./test_project/").unwrap();
@@ -110,6 +128,9 @@ fn errors_on_dir_already_exists() -> () {
             let create: Create = Create {
                 name: path::PathBuf::from("./test_project/"),
                 parents: false,
+                slugify: false,
+                title: None,
+                interview: Interview::default(),
             };
 
             create.create_root()
@@ -120,12 +141,15 @@ fn errors_on_dir_already_exists() -> () {
 
 #[test]
 #[should_panic(expected = "Parents don't exist")]
-fn errors_on_parents_dont_exist() -> () {
+fn errors_on_parents_dont_exist() {
     test_in_tmp_dir(
         || {
             let create: Create = Create {
                 name: path::PathBuf::from("./path/to/test_project/"),
                 parents: false,
+                slugify: false,
+                title: None,
+                interview: Interview::default(),
             };
 
             create.create_root()
@@ -136,7 +160,7 @@ fn errors_on_parents_dont_exist() -> () {
 
 #[test]
 #[should_panic(expected = "Invalid permissions")]
-fn errors_on_permission_denied() -> () {
+fn errors_on_permission_denied() {
     test_in_tmp_dir(
         || {
             if env::consts::OS == "windows" {
@@ -151,6 +175,9 @@ fn errors_on_permission_denied() -> () {
             let create: Create = Create {
                 name: path::PathBuf::from("./test_project/"),
                 parents: false,
+                slugify: false,
+                title: None,
+                interview: Interview::default(),
             };
 
             create.create_root()
@@ -163,7 +190,7 @@ fn errors_on_permission_denied() -> () {
 // create_subdirectories //
 // +++++++++++++++++++++ //
 #[test]
-fn subdirectories_created() -> () {
+fn subdirectories_created() {
     test_in_tmp_dir(
         || {
             fs::create_dir("test_project").unwrap();
@@ -171,25 +198,31 @@ fn subdirectories_created() -> () {
             let create: Create = Create {
                 name: path::PathBuf::from("./test_project/"),
                 parents: false,
+                slugify: false,
+                title: None,
+                interview: Interview::default(),
             };
 
             create.create_subdirectories();
 
-            let mut expected_dirs = vec![
+            let mut expected_dirs = [
                 path::PathBuf::from("test_project/data_sources"),
                 path::PathBuf::from("test_project/explorations"),
                 path::PathBuf::from("test_project/models"),
                 path::PathBuf::from("test_project/products"),
             ];
 
-            let mut actual_dirs = fs::read_dir("./test_project/")
+            let mut actual_dirs = fs::read_dir("test_project/")
                 .unwrap()
                 .filter_map(|e| e.ok())
                 .filter(|e| e.file_type().unwrap().is_dir())
                 .map(|e| e.path())
                 .collect::<Vec<_>>();
 
-            assert_eq!(expected_dirs.sort(), actual_dirs.sort())
+            expected_dirs.sort();
+            actual_dirs.sort();
+
+            assert_eq!(expected_dirs.to_vec(), actual_dirs)
         },
         false,
     )
@@ -199,7 +232,7 @@ fn subdirectories_created() -> () {
 // create_files //
 // ++++++++++++ //
 #[test]
-fn files_created() -> () {
+fn files_created() {
     test_in_tmp_dir(
         || {
             fs::create_dir("test_project").unwrap();
@@ -207,24 +240,83 @@ fn files_created() -> () {
             let create: Create = Create {
                 name: path::PathBuf::from("./test_project/"),
                 parents: false,
+                slugify: false,
+                title: None,
+                interview: Interview::default(),
             };
 
-            create.create_files();
+            create.create_files(&BTreeMap::new());
 
-            let mut expected_files = vec![
+            let mut expected_files = [
                 path::PathBuf::from("test_project/README.md"),
                 path::PathBuf::from("test_project/project_scoping.md"),
-                path::PathBuf::from("test_project/geoff.toml"),
+                path::PathBuf::from("test_project/.geoff"),
             ];
 
-            let mut actual_files = fs::read_dir("./test_project/")
+            let mut actual_files = fs::read_dir("test_project/")
                 .unwrap()
                 .filter_map(|e| e.ok())
                 .filter(|e| e.file_type().unwrap().is_file())
                 .map(|e| e.path())
                 .collect::<Vec<_>>();
 
-            assert_eq!(expected_files.sort(), actual_files.sort())
+            expected_files.sort();
+            actual_files.sort();
+
+            assert_eq!(expected_files.to_vec(), actual_files)
+        },
+        false,
+    )
+}
+
+#[test]
+fn root_documents_start_with_front_matter() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir("test_project").unwrap();
+
+            let create: Create = Create {
+                name: path::PathBuf::from("./test_project/"),
+                parents: false,
+                slugify: false,
+                title: None,
+                interview: Interview::default(),
+            };
+
+            create.create_files(&BTreeMap::new());
+
+            for filename in ["README.md", "project_scoping.md"] {
+                let contents = fs::read_to_string(format!("test_project/{}", filename)).unwrap();
+                let parsed: ProjectFrontMatter = front_matter::parse(&contents).unwrap();
+
+                assert_eq!(parsed.project, "test_project");
+                assert_eq!(parsed.created, front_matter::today());
+            }
+        },
+        false,
+    )
+}
+
+#[test]
+fn dotted_project_name_kept_whole() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir("iris.v2").unwrap();
+
+            let create: Create = Create {
+                name: path::PathBuf::from("iris.v2"),
+                parents: false,
+                slugify: false,
+                title: None,
+                interview: Interview::default(),
+            };
+
+            create.create_files(&BTreeMap::new());
+
+            let contents = fs::read_to_string("iris.v2/README.md").unwrap();
+            let parsed: ProjectFrontMatter = front_matter::parse(&contents).unwrap();
+
+            assert_eq!(parsed.project, "iris.v2");
         },
         false,
     )
@@ -234,7 +326,7 @@ fn files_created() -> () {
 // replace_placeholders //
 // ++++++++++++++++++++ //
 #[test]
-fn readme_proj_scope_placeholders_replaced() -> () {
+fn readme_proj_scope_placeholders_replaced() {
     test_in_tmp_dir(
         || {
             fs::create_dir("test_project").unwrap();
@@ -242,6 +334,9 @@ fn readme_proj_scope_placeholders_replaced() -> () {
             let create: Create = Create {
                 name: path::PathBuf::from("./test_project/"),
                 parents: false,
+                slugify: false,
+                title: None,
+                interview: Interview::default(),
             };
 
             let files = collections::HashMap::from([
@@ -268,7 +363,7 @@ fn readme_proj_scope_placeholders_replaced() -> () {
 // create_tree //
 // +++++++++++ //
 #[test]
-fn creates_tree() -> () {
+fn creates_tree() {
     test_in_tmp_dir(
         || {
             let gold = Style::new().color256(220);
@@ -278,10 +373,13 @@ fn creates_tree() -> () {
             let create: Create = Create {
                 name: path::PathBuf::from("./test_project/"),
                 parents: false,
+                slugify: false,
+                title: None,
+                interview: Interview::default(),
             };
 
             let tree = create.create_tree();
-            let leaves = vec![
+            let leaves = [
                 format!("{} data_sources", gold.apply_to("\u{1F5BF}")),
                 format!("{} explorations", gold.apply_to("\u{1F5BF}")),
                 format!("{} models", gold.apply_to("\u{1F5BF}")),
@@ -291,8 +389,8 @@ fn creates_tree() -> () {
             ];
 
             assert_eq!(tree.text, format!("{}", bold.apply_to("./test_project/")));
-            for i in 0..leaves.len() {
-                assert_eq!(tree.children[i].text, leaves[i])
+            for (child, leaf) in tree.children.iter().zip(leaves.iter()) {
+                assert_eq!(&child.text, leaf)
             }
         },
         false,
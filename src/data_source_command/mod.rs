@@ -0,0 +1,860 @@
+use chrono::NaiveDate;
+use clap::{AppSettings, Args, Subcommand};
+use console::Style;
+use log::info;
+use ptree::{item, TreeBuilder};
+use std::{collections::BTreeMap, fs, io::Read, path};
+
+use crate::checksum;
+use crate::component::{list_components, ComponentId, Stage};
+use crate::front_matter;
+use crate::manifest::{Download, ExtractVersion, Manifest};
+use crate::metadata;
+
+#[derive(Args)]
+pub struct DataSourceManager {
+    #[clap(subcommand)]
+    pub command: Option<DataSourceCommands>,
+}
+
+#[derive(Subcommand)]
+#[clap(setting = AppSettings::SubcommandRequiredElseHelp)]
+pub enum DataSourceCommands {
+    /// Stores a newly received version of an extract
+    Receive(Receive),
+    /// Records checksums of the data files so changes can be detected
+    Lock(Lock),
+    /// Downloads a file into a web data source
+    Fetch(Fetch),
+}
+
+/// The columns added and removed between two versions of an extract
+#[derive(Debug, Default, PartialEq)]
+pub struct SchemaDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl SchemaDiff {
+    /// Whether the two versions have the same columns
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Formats the diff with added columns in green and removed in red
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return String::from("No changes to the columns");
+        }
+
+        let added = self.added.iter().map(|column| {
+            format!(
+                "{}",
+                Style::new().color256(10).apply_to(format!("+ {}", column))
+            )
+        });
+        let removed = self.removed.iter().map(|column| {
+            format!(
+                "{}",
+                Style::new().color256(9).apply_to(format!("- {}", column))
+            )
+        });
+
+        added.chain(removed).collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// Gets the folder of an existing data source
+///
+/// # Arguments
+///
+/// * `name` - The name of the data source
+///
+/// # Errors
+///
+/// * The name isn't a single folder within data_sources, e.g `..`
+/// * The data source doesn't exist in the data_sources folder
+pub fn data_source_path(name: &path::Path) -> path::PathBuf {
+    let mut parts = name.components();
+    if !matches!(
+        (parts.next(), parts.next()),
+        (Some(path::Component::Normal(_)), None)
+    ) {
+        eprintln!(
+            "{:?} {}",
+            name,
+            Style::new()
+                .color256(9)
+                .apply_to("isn't the name of a data source")
+        );
+        panic!("{:?} isn't a data source", name);
+    }
+
+    let data_source_path = path::Path::new("data_sources").join(name);
+
+    if !data_source_path.is_dir() {
+        eprintln!(
+            "{} {}",
+            name.display(),
+            Style::new()
+                .color256(9)
+                .apply_to("isn't a data source in this project")
+        );
+        panic!("{} doesn't exist", data_source_path.display());
+    }
+
+    data_source_path
+}
+
+/// Finds the data source a name refers to, see [ComponentId::find]
+///
+/// # Arguments
+///
+/// * `name` - The name as written by the user
+///
+/// # Return value
+///
+/// The name of the data source that was found
+///
+/// # Errors
+///
+/// * The data source doesn't exist or the name is ambiguous
+pub fn find_data_source(name: &path::Path) -> path::PathBuf {
+    path::PathBuf::from(ComponentId::find(Stage::DataSource, &name.to_string_lossy()).name)
+}
+
+/// Lists the names of the data sources in the project
+///
+/// # Return value
+///
+/// The sorted names of the folders in the data_sources folder
+pub fn list_data_sources() -> Vec<String> {
+    list_components(Stage::DataSource)
+        .into_iter()
+        .map(|component| component.name)
+        .collect()
+}
+
+/// Lists the data files within a data source folder
+///
+/// Every file in the folder and its subfolders is a data file apart from the
+/// metadata document.
+///
+/// # Arguments
+///
+/// * `data_source` - The folder of the data source
+///
+/// # Return value
+///
+/// The sorted paths of the data files relative to the data source folder,
+/// separated with forward slashes so they're the same on every platform
+pub fn list_data_files(data_source: &path::Path) -> Vec<String> {
+    let mut data_files = Vec::new();
+    let mut to_visit = vec![data_source.to_path_buf()];
+
+    while let Some(dir) = to_visit.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let entry_path = entry.path();
+
+            if entry_path.is_dir() {
+                to_visit.push(entry_path);
+                continue;
+            }
+
+            let relative = entry_path
+                .strip_prefix(data_source)
+                .unwrap()
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            if relative != "metadata.md" {
+                data_files.push(relative);
+            }
+        }
+    }
+
+    data_files.sort();
+    data_files
+}
+
+/// Reads the column names from the header of a delimited file
+///
+/// The delimiter is a tab for .tsv files and a comma for everything else.
+///
+/// # Arguments
+///
+/// * `file_path` - The delimited file to read the header from
+pub fn read_columns(file_path: &path::Path) -> Vec<String> {
+    let delimiter = match file_path.extension().and_then(|ext| ext.to_str()) {
+        Some("tsv") => '\t',
+        _ => ',',
+    };
+
+    let contents = fs::read_to_string(file_path).unwrap_or_default();
+
+    contents
+        .lines()
+        .next()
+        .map(|header| {
+            header
+                .split(delimiter)
+                .map(|column| column.trim().trim_matches('"').to_string())
+                .filter(|column| !column.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Compares the columns of two versions of an extract
+///
+/// # Arguments
+///
+/// * `previous` - The columns in the previous version
+/// * `current` - The columns in the new version
+pub fn schema_diff(previous: &[String], current: &[String]) -> SchemaDiff {
+    SchemaDiff {
+        added: current
+            .iter()
+            .filter(|column| !previous.contains(column))
+            .cloned()
+            .collect(),
+        removed: previous
+            .iter()
+            .filter(|column| !current.contains(column))
+            .cloned()
+            .collect(),
+    }
+}
+
+#[derive(Args, Clone)]
+pub struct Receive {
+    /// The name of the data source the extract belongs to
+    #[clap(value_parser)]
+    pub name: path::PathBuf,
+    /// The extract file that has been received
+    #[clap(value_parser)]
+    pub file: path::PathBuf,
+    /// The date the extract was received (YYYY-MM-DD), defaults to today
+    #[clap(long, value_parser)]
+    pub date: Option<NaiveDate>,
+    /// Show the columns that changed since the previous version
+    #[clap(long)]
+    pub diff: bool,
+}
+
+impl Receive {
+    /// The receipt with the name of the data source it refers to, see
+    /// [find_data_source]
+    pub fn resolved(&self) -> Receive {
+        Receive {
+            name: find_data_source(&self.name),
+            ..self.clone()
+        }
+    }
+
+    /// The date the extract was received formatted as YYYY-MM-DD
+    pub fn received_on(&self) -> String {
+        self.date
+            .unwrap_or_else(|| chrono::Local::now().date_naive())
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+
+    /// The name of the data source as a str
+    fn _name_str(&self) -> &str {
+        self.name
+            .to_str()
+            .expect("Unable to convert data source name to str")
+    }
+
+    /// The file name of the extract as a str
+    fn _file_name_str(&self) -> &str {
+        self.file
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .expect("Unable to get the extract file name")
+    }
+
+    /// The location of the new version relative to the data source folder
+    pub fn version_file(&self) -> String {
+        format!("versions/{}/{}", self.received_on(), self._file_name_str())
+    }
+
+    /// Copies the extract into a dated folder in the data source
+    ///
+    /// The extract is copied to `data_sources/<name>/versions/<date>/`.
+    ///
+    /// # Errors
+    ///
+    /// * The data source doesn't exist
+    /// * The extract file doesn't exist
+    /// * A file with the same name has already been received on that date
+    ///
+    /// # Return value
+    ///
+    /// The path the extract was copied to
+    pub fn copy_extract(&self) -> path::PathBuf {
+        let destination = data_source_path(&self.name).join(self.version_file());
+
+        if !self.file.is_file() {
+            panic!("{} doesn't exist", self.file.display());
+        }
+
+        if destination.exists() {
+            eprintln!(
+                "{} {}",
+                self.version_file(),
+                Style::new()
+                    .color256(9)
+                    .apply_to("has already been received, please check the date")
+            );
+            panic!("{} exists", destination.display());
+        }
+
+        let version_dir = destination.parent().unwrap();
+        fs::create_dir_all(version_dir)
+            .unwrap_or_else(|_| panic!("Unable to create {}", version_dir.display()));
+
+        info!(
+            "Copying {} to {}",
+            self.file.display(),
+            destination.display()
+        );
+        fs::copy(&self.file, &destination)
+            .unwrap_or_else(|_| panic!("Unable to copy to {}", destination.display()));
+
+        destination
+    }
+
+    /// Summarises a received extract
+    ///
+    /// Records the checksum, size and number of rows in the extract. The
+    /// header row isn't counted for csv and tsv files.
+    ///
+    /// # Arguments
+    ///
+    /// * `extract_path` - The location of the received extract
+    pub fn summarise(&self, extract_path: &path::Path) -> ExtractVersion {
+        let bytes = fs::read(extract_path)
+            .unwrap_or_else(|_| panic!("Unable to read {}", extract_path.display()));
+
+        let lines = String::from_utf8_lossy(&bytes)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .count() as u64;
+
+        let has_header = matches!(
+            extract_path.extension().and_then(|ext| ext.to_str()),
+            Some("csv") | Some("tsv")
+        );
+
+        ExtractVersion {
+            file: self.version_file(),
+            received: self.received_on(),
+            sha256: checksum::sha256_hex(&bytes),
+            size: bytes.len() as u64,
+            rows: if has_header {
+                lines.saturating_sub(1)
+            } else {
+                lines
+            },
+        }
+    }
+
+    /// Records the new version in the manifest
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest
+    /// * `version` - The summary of the received extract
+    ///
+    /// # Return value
+    ///
+    /// The version that was received before this one, if there is one
+    pub fn record_version(
+        &self,
+        manifest: &mut Manifest,
+        version: ExtractVersion,
+    ) -> Option<ExtractVersion> {
+        let entry = manifest.data_source_mut(self._name_str());
+        let previous = entry.versions.last().cloned();
+
+        entry.versions.push(version);
+
+        previous
+    }
+
+    /// Adds a row for the new version to the extract details table
+    ///
+    /// If the metadata doesn't have an extract details table, one is added to
+    /// the end of the file. The refreshed date in the front matter is set to
+    /// the date the extract was received.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The summary of the received extract
+    pub fn update_metadata(&self, version: &ExtractVersion) {
+        let metadata_path = data_source_path(&self.name).join("metadata.md");
+        let contents = fs::read_to_string(&metadata_path).unwrap_or_default();
+
+        let row = metadata::format_table_row(&[&version.file, "", "", &version.received]);
+
+        let updated_contents =
+            match metadata::append_table_row(&contents, "## Extract details", &row) {
+                Some(updated_contents) => updated_contents,
+                None => format!(
+                    "{}\n## Extract details\n\n\
+                    | extract file | extract query | extractor | date received |\n\
+                    | :----------- | :------------ | :-------- | :------------ |\n\
+                    {}\n",
+                    contents, row
+                ),
+            };
+
+        fs::write(&metadata_path, updated_contents)
+            .unwrap_or_else(|_| panic!("Unable to update {}", metadata_path.display()));
+
+        front_matter::update_data_source(&data_source_path(&self.name), |front_matter| {
+            front_matter.refreshed = Some(version.received.clone())
+        });
+    }
+
+    /// Compares the columns in the new version with the previous one
+    ///
+    /// # Arguments
+    ///
+    /// * `previous` - The version received before this one
+    /// * `current` - The version that has just been received
+    pub fn compare_versions(
+        &self,
+        previous: &ExtractVersion,
+        current: &ExtractVersion,
+    ) -> SchemaDiff {
+        let data_source = data_source_path(&self.name);
+
+        schema_diff(
+            &read_columns(&data_source.join(&previous.file)),
+            &read_columns(&data_source.join(&current.file)),
+        )
+    }
+
+    /// Creates a tree showing the received version
+    pub fn create_tree(&self, version: &ExtractVersion) -> item::StringItem {
+        let gold = Style::new().color256(220);
+        let hd = Style::new().color256(194);
+
+        TreeBuilder::new(format!(
+            "{} {}",
+            gold.apply_to("\u{1F5BF}"),
+            self.name.display()
+        ))
+        .begin_child(format!("{} versions", gold.apply_to("\u{1F5BF}")))
+        .begin_child(format!(
+            "{} {}",
+            gold.apply_to("\u{1F5BF}"),
+            version.received
+        ))
+        .add_empty_child(format!(
+            "{} {} ({} bytes, {} rows)",
+            hd.apply_to("\u{1F5CE}"),
+            self._file_name_str(),
+            version.size,
+            version.rows
+        ))
+        .end_child()
+        .end_child()
+        .build()
+    }
+}
+
+#[derive(Args, Clone)]
+pub struct Lock {
+    /// The name of the data source to lock
+    #[clap(value_parser)]
+    pub name: path::PathBuf,
+}
+
+impl Lock {
+    /// The lock with the name of the data source it refers to, see
+    /// [find_data_source]
+    pub fn resolved(&self) -> Lock {
+        Lock {
+            name: find_data_source(&self.name),
+        }
+    }
+
+    /// Calculates the checksum of every data file in the data source
+    ///
+    /// # Return value
+    ///
+    /// The SHA-256 hash of each data file keyed by its path within the data
+    /// source folder
+    pub fn hash_data_files(&self) -> BTreeMap<String, String> {
+        let data_source = data_source_path(&self.name);
+
+        list_data_files(&data_source)
+            .into_iter()
+            .map(|data_file| {
+                let file_path = data_source.join(&data_file);
+                let hash = checksum::file_sha256(&file_path)
+                    .unwrap_or_else(|_| panic!("Unable to read {}", file_path.display()));
+
+                (data_file, hash)
+            })
+            .collect()
+    }
+
+    /// Replaces the checksums recorded for the data source in the manifest
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest
+    /// * `checksums` - The checksums of the data files
+    pub fn record_checksums(&self, manifest: &mut Manifest, checksums: BTreeMap<String, String>) {
+        let name_str = self
+            .name
+            .to_str()
+            .expect("Unable to convert data source name to str");
+
+        manifest.data_source_mut(name_str).checksums = checksums;
+    }
+
+    /// Creates a tree showing the files that have been locked
+    pub fn create_tree(&self, checksums: &BTreeMap<String, String>) -> item::StringItem {
+        let gold = Style::new().color256(220);
+        let hd = Style::new().color256(194);
+
+        let mut tree = TreeBuilder::new(format!(
+            "{} {}",
+            gold.apply_to("\u{1F5BF}"),
+            self.name.display()
+        ));
+
+        for (data_file, hash) in checksums.iter() {
+            tree.add_empty_child(format!(
+                "{} {} ({})",
+                hd.apply_to("\u{1F5CE}"),
+                data_file,
+                &hash[..12]
+            ));
+        }
+
+        tree.build()
+    }
+}
+
+/// The outcome of fetching a file
+#[derive(Debug, PartialEq)]
+pub enum FetchResult {
+    /// The file was downloaded
+    Downloaded {
+        bytes: Vec<u8>,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    },
+    /// The remote file hasn't changed since it was last downloaded
+    NotModified,
+}
+
+#[derive(Args, Clone)]
+pub struct Fetch {
+    /// The name of the data source to download the file into
+    #[clap(value_parser)]
+    pub name: path::PathBuf,
+    /// The URL of the file, either file:// or http(s)://
+    #[clap(value_parser)]
+    pub url: String,
+    /// Download the file again only if it has changed since the last download
+    #[clap(short, long)]
+    pub refresh: bool,
+}
+
+impl Fetch {
+    /// The fetch with the name of the data source it refers to, see
+    /// [find_data_source]
+    pub fn resolved(&self) -> Fetch {
+        Fetch {
+            name: find_data_source(&self.name),
+            ..self.clone()
+        }
+    }
+
+    /// The name of the file to save the download as
+    ///
+    /// The name is the final segment of the URL path.
+    ///
+    /// # Errors
+    ///
+    /// * The URL doesn't end with a file name
+    /// * The file name is the data source's metadata.md, `.` or `..`
+    pub fn file_name(&self) -> String {
+        let without_query = self.url.split(['?', '#']).next().unwrap_or_default();
+        let file_name = without_query
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        if file_name.is_empty() || without_query.ends_with("://") {
+            panic!("{} doesn't end with a file name", self.url);
+        }
+        if matches!(file_name.as_str(), "." | ".." | "metadata.md") {
+            eprintln!(
+                "{} {}",
+                self.url,
+                Style::new()
+                    .color256(9)
+                    .apply_to("would be saved over the data source's folder or metadata")
+            );
+            panic!("{} can't be saved as {}", self.url, file_name);
+        }
+
+        file_name
+    }
+
+    /// Finds the most recent download of this URL in the manifest
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest
+    pub fn previous_download<'a>(&self, manifest: &'a Manifest) -> Option<&'a Download> {
+        manifest
+            .data_sources
+            .get(self.name.to_str()?)?
+            .downloads
+            .iter()
+            .rev()
+            .find(|download| download.url == self.url)
+    }
+
+    /// Downloads the file from the URL
+    ///
+    /// When refreshing, the server is asked to only send the file if it has
+    /// changed since the previous download and the file is also treated as
+    /// unchanged when its checksum matches the previous download.
+    ///
+    /// # Arguments
+    ///
+    /// * `previous` - The previous download of this URL, if refreshing
+    ///
+    /// # Errors
+    ///
+    /// * The URL isn't a file:// or http(s):// URL
+    /// * The file can't be read or the server returns an error
+    pub fn download(&self, previous: Option<&Download>) -> FetchResult {
+        let result = if let Some(file_path) = self.url.strip_prefix("file://") {
+            info!("Reading {}", file_path);
+
+            FetchResult::Downloaded {
+                bytes: fs::read(file_path)
+                    .unwrap_or_else(|_| panic!("Unable to read {}", file_path)),
+                etag: None,
+                last_modified: None,
+            }
+        } else if self.url.starts_with("http://") || self.url.starts_with("https://") {
+            self._http_get(previous)
+        } else {
+            eprintln!(
+                "{} {}",
+                self.url,
+                Style::new()
+                    .color256(9)
+                    .apply_to("isn't supported, please use a file:// or http(s):// URL")
+            );
+            panic!("Unsupported URL {}", self.url);
+        };
+
+        match (result, previous) {
+            (FetchResult::Downloaded { bytes, .. }, Some(previous))
+                if checksum::sha256_hex(&bytes) == previous.sha256 =>
+            {
+                FetchResult::NotModified
+            }
+            (result, _) => result,
+        }
+    }
+
+    /// Sends a GET request for the URL
+    fn _http_get(&self, previous: Option<&Download>) -> FetchResult {
+        info!("Downloading {}", self.url);
+
+        let mut request = ureq::get(&self.url);
+
+        if let Some(previous) = previous {
+            if let Some(etag) = &previous.etag {
+                request = request.set("If-None-Match", etag);
+            }
+            if let Some(last_modified) = &previous.last_modified {
+                request = request.set("If-Modified-Since", last_modified);
+            }
+        }
+
+        let response = request
+            .call()
+            .unwrap_or_else(|err| panic!("Unable to download {}\n{:?}", self.url, err));
+
+        if response.status() == 304 {
+            return FetchResult::NotModified;
+        }
+
+        let etag = response.header("ETag").map(String::from);
+        let last_modified = response.header("Last-Modified").map(String::from);
+
+        let mut bytes = Vec::new();
+        response
+            .into_reader()
+            .read_to_end(&mut bytes)
+            .unwrap_or_else(|_| panic!("Unable to download {}", self.url));
+
+        FetchResult::Downloaded {
+            bytes,
+            etag,
+            last_modified,
+        }
+    }
+
+    /// Saves the downloaded file in the data source folder
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The contents of the downloaded file
+    /// * `etag` - The ETag header returned by the server
+    /// * `last_modified` - The Last-Modified header returned by the server
+    ///
+    /// # Errors
+    ///
+    /// * The data source doesn't exist
+    /// * The file already exists and the download isn't a refresh
+    ///
+    /// # Return value
+    ///
+    /// The details of the download to record
+    pub fn save(
+        &self,
+        bytes: &[u8],
+        etag: Option<String>,
+        last_modified: Option<String>,
+    ) -> Download {
+        let file_name = self.file_name();
+        let destination = data_source_path(&self.name).join(&file_name);
+
+        if destination.exists() && !self.refresh {
+            eprintln!(
+                "{} {}",
+                destination.display(),
+                Style::new()
+                    .color256(9)
+                    .apply_to("already exists, pass `--refresh` to download it again")
+            );
+            panic!("{} exists", destination.display());
+        }
+
+        fs::write(&destination, bytes)
+            .unwrap_or_else(|_| panic!("Unable to write {}", destination.display()));
+
+        Download {
+            file: file_name,
+            url: self.url.clone(),
+            downloaded: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            etag,
+            last_modified,
+            size: bytes.len() as u64,
+            sha256: checksum::sha256_hex(bytes),
+        }
+    }
+
+    /// Records the download in the manifest
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest
+    /// * `download` - The details of the download
+    pub fn record_download(&self, manifest: &mut Manifest, download: Download) {
+        let name_str = self
+            .name
+            .to_str()
+            .expect("Unable to convert data source name to str");
+
+        manifest.data_source_mut(name_str).downloads.push(download);
+    }
+
+    /// Adds a row for the download to the website details table
+    ///
+    /// The ETag, Last-Modified, size and checksum are added to the additional
+    /// details column. If the metadata doesn't have a website details table,
+    /// one is added to the end of the file. The refreshed date in the front
+    /// matter is set to the date of the download.
+    ///
+    /// # Arguments
+    ///
+    /// * `download` - The details of the download
+    pub fn update_metadata(&self, download: &Download) {
+        let metadata_path = data_source_path(&self.name).join("metadata.md");
+        let contents = fs::read_to_string(&metadata_path).unwrap_or_default();
+
+        let mut details = Vec::new();
+        if let Some(etag) = &download.etag {
+            details.push(format!("ETag: {}", etag));
+        }
+        if let Some(last_modified) = &download.last_modified {
+            details.push(format!("Last-Modified: {}", last_modified));
+        }
+        details.push(format!("size: {} bytes", download.size));
+        details.push(format!("sha256: {}", download.sha256));
+
+        let row = metadata::format_table_row(&[
+            &download.file,
+            &download.url,
+            &download.downloaded,
+            &details.join(", "),
+        ]);
+
+        let updated_contents =
+            match metadata::append_table_row(&contents, "## Website details", &row) {
+                Some(updated_contents) => updated_contents,
+                None => format!(
+                    "{}\n## Website details\n\n\
+                    | download file | download link | downloaded on | additional details |\n\
+                    | :------------ | :------------ | :------------ | :----------------- |\n\
+                    {}\n",
+                    contents, row
+                ),
+            };
+
+        fs::write(&metadata_path, updated_contents)
+            .unwrap_or_else(|_| panic!("Unable to update {}", metadata_path.display()));
+
+        front_matter::update_data_source(&data_source_path(&self.name), |front_matter| {
+            front_matter.refreshed = Some(download.downloaded[..10].to_string())
+        });
+    }
+
+    /// Creates a tree showing the downloaded file
+    pub fn create_tree(&self, download: &Download) -> item::StringItem {
+        let gold = Style::new().color256(220);
+        let hd = Style::new().color256(194);
+
+        TreeBuilder::new(format!(
+            "{} {}",
+            gold.apply_to("\u{1F5BF}"),
+            self.name.display()
+        ))
+        .add_empty_child(format!(
+            "{} {} ({} bytes)",
+            hd.apply_to("\u{1F5CE}"),
+            download.file,
+            download.size
+        ))
+        .build()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,519 @@
+use rstest::rstest;
+use std::{env, fs, path};
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+
+fn receive(name: &str, file: &str) -> Receive {
+    Receive {
+        name: path::PathBuf::from(name),
+        file: path::PathBuf::from(file),
+        date: Some(NaiveDate::from_ymd_opt(2022, 4, 1).unwrap()),
+        diff: false,
+    }
+}
+
+fn setup_data_source() {
+    fs::create_dir_all("data_sources/iris").unwrap();
+    fs::write(
+        "data_sources/iris/metadata.md",
+        include_str!("../templates/data_sources/extract_metadata.md"),
+    )
+    .unwrap();
+    fs::write("iris.csv", "id,sepal_width\n1,3.5\n2,3.0\n").unwrap();
+}
+
+// ++++++++++++++++ //
+// data_source_path //
+// ++++++++++++++++ //
+#[test]
+#[should_panic(expected = "data_sources/iris doesn't exist")]
+fn missing_data_source_errors() {
+    test_in_tmp_dir(
+        || {
+            data_source_path(path::Path::new("iris"));
+        },
+        true,
+    )
+}
+
+#[rstest]
+#[case("..")]
+#[case("")]
+#[case("/tmp")]
+#[case("iris/versions")]
+#[should_panic(expected = "isn't a data source")]
+fn data_source_outside_data_sources_errors(#[case] name: &str) {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris/versions").unwrap();
+
+            data_source_path(path::Path::new(name));
+        },
+        true,
+    )
+}
+
+// ++++++++++++ //
+// read_columns //
+// ++++++++++++ //
+#[test]
+fn reads_csv_columns() {
+    test_in_tmp_dir(
+        || {
+            fs::write("iris.csv", "id,\"sepal_width\", species\n1,3.5,setosa\n").unwrap();
+
+            assert_eq!(
+                read_columns(path::Path::new("iris.csv")),
+                vec!["id", "sepal_width", "species"]
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn reads_tsv_columns() {
+    test_in_tmp_dir(
+        || {
+            fs::write("iris.tsv", "id\tsepal_width\n1\t3.5\n").unwrap();
+
+            assert_eq!(
+                read_columns(path::Path::new("iris.tsv")),
+                vec!["id", "sepal_width"]
+            );
+        },
+        false,
+    )
+}
+
+// +++++++++++ //
+// schema_diff //
+// +++++++++++ //
+#[test]
+fn finds_added_and_removed_columns() {
+    let previous = vec![String::from("id"), String::from("sepal_width")];
+    let current = vec![String::from("id"), String::from("petal_width")];
+
+    let diff = schema_diff(&previous, &current);
+
+    assert_eq!(diff.added, vec!["petal_width"]);
+    assert_eq!(diff.removed, vec!["sepal_width"]);
+}
+
+#[test]
+fn same_columns_is_empty_diff() {
+    let columns = vec![String::from("id")];
+
+    assert!(schema_diff(&columns, &columns).is_empty());
+}
+
+// ++++++++++++ //
+// copy_extract //
+// ++++++++++++ //
+#[test]
+fn extract_copied_into_dated_folder() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source();
+
+            let destination = receive("iris", "iris.csv").copy_extract();
+
+            assert_eq!(
+                destination,
+                path::PathBuf::from("data_sources/iris/versions/2022-04-01/iris.csv")
+            );
+            assert!(destination.exists());
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "iris.csv exists")]
+fn errors_on_extract_already_received() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source();
+
+            receive("iris", "iris.csv").copy_extract();
+            receive("iris", "iris.csv").copy_extract();
+        },
+        true,
+    )
+}
+
+#[test]
+#[should_panic(expected = "missing.csv doesn't exist")]
+fn errors_on_missing_extract() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source();
+
+            receive("iris", "missing.csv").copy_extract();
+        },
+        true,
+    )
+}
+
+// +++++++++ //
+// summarise //
+// +++++++++ //
+#[test]
+fn summary_excludes_header_row() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source();
+
+            let version = receive("iris", "iris.csv").summarise(path::Path::new("iris.csv"));
+
+            assert_eq!(version.file, "versions/2022-04-01/iris.csv");
+            assert_eq!(version.received, "2022-04-01");
+            assert_eq!(version.size, 27);
+            assert_eq!(version.rows, 2);
+            assert_eq!(
+                version.sha256,
+                checksum::sha256_hex(b"id,sepal_width\n1,3.5\n2,3.0\n")
+            );
+        },
+        false,
+    )
+}
+
+// ++++++++++++++ //
+// record_version //
+// ++++++++++++++ //
+#[test]
+fn returns_previous_version() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source();
+
+            let receive = receive("iris", "iris.csv");
+            let version = receive.summarise(path::Path::new("iris.csv"));
+            let mut manifest = Manifest::default();
+
+            assert_eq!(receive.record_version(&mut manifest, version.clone()), None);
+            assert_eq!(
+                receive.record_version(&mut manifest, version.clone()),
+                Some(version)
+            );
+            assert_eq!(manifest.data_sources["iris"].versions.len(), 2);
+        },
+        false,
+    )
+}
+
+// +++++++++++++++ //
+// update_metadata //
+// +++++++++++++++ //
+#[test]
+fn row_added_to_extract_details() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source();
+
+            let receive = receive("iris", "iris.csv");
+            let version = receive.summarise(path::Path::new("iris.csv"));
+
+            receive.update_metadata(&version);
+
+            let contents = fs::read_to_string("data_sources/iris/metadata.md").unwrap();
+            assert!(contents.contains(
+                "| *e.g iris.csv* | *e.g SELECT * FROM iris_table* | *e.g Ronald Fisher - data collection team* | *e.g 2022-04-01* |\n\
+                | versions/2022-04-01/iris.csv |  |  | 2022-04-01 |\n"
+            ));
+        },
+        false,
+    )
+}
+
+#[test]
+fn extract_details_added_when_missing() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source();
+            fs::write("data_sources/iris/metadata.md", "# iris\n").unwrap();
+
+            let receive = receive("iris", "iris.csv");
+            let version = receive.summarise(path::Path::new("iris.csv"));
+
+            receive.update_metadata(&version);
+
+            let contents = fs::read_to_string("data_sources/iris/metadata.md").unwrap();
+            assert!(contents.starts_with("# iris\n\n## Extract details\n"));
+            assert!(contents.ends_with("| versions/2022-04-01/iris.csv |  |  | 2022-04-01 |\n"));
+        },
+        false,
+    )
+}
+
+// +++++++++++++++ //
+// list_data_files //
+// +++++++++++++++ //
+#[test]
+fn lists_nested_data_files_without_metadata() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source();
+            fs::create_dir_all("data_sources/iris/versions/2022-04-01").unwrap();
+            fs::write("data_sources/iris/versions/2022-04-01/iris.csv", "id\n").unwrap();
+            fs::write("data_sources/iris/iris.csv", "id\n").unwrap();
+
+            assert_eq!(
+                list_data_files(path::Path::new("data_sources/iris")),
+                vec!["iris.csv", "versions/2022-04-01/iris.csv"]
+            );
+        },
+        false,
+    )
+}
+
+// +++++++++++++++ //
+// hash_data_files //
+// +++++++++++++++ //
+#[test]
+fn hashes_every_data_file() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source();
+            fs::write("data_sources/iris/iris.csv", "id\n").unwrap();
+
+            let lock = Lock {
+                name: path::PathBuf::from("iris"),
+            };
+
+            let checksums = lock.hash_data_files();
+
+            assert_eq!(checksums.len(), 1);
+            assert_eq!(checksums["iris.csv"], checksum::sha256_hex(b"id\n"));
+        },
+        false,
+    )
+}
+
+// ++++++++++++++++ //
+// record_checksums //
+// ++++++++++++++++ //
+#[test]
+fn checksums_replaced_in_manifest() {
+    let lock = Lock {
+        name: path::PathBuf::from("iris"),
+    };
+    let mut manifest = Manifest::default();
+    manifest
+        .data_source_mut("iris")
+        .checksums
+        .insert(String::from("old.csv"), String::from("abc"));
+
+    let checksums = BTreeMap::from([(String::from("iris.csv"), String::from("def"))]);
+    lock.record_checksums(&mut manifest, checksums.clone());
+
+    assert_eq!(manifest.data_sources["iris"].checksums, checksums);
+}
+
+fn fetch(url: &str, refresh: bool) -> Fetch {
+    Fetch {
+        name: path::PathBuf::from("iris"),
+        url: String::from(url),
+        refresh,
+    }
+}
+
+// +++++++++ //
+// file_name //
+// +++++++++ //
+#[rstest]
+#[case("http://example.com/data/iris.csv", "iris.csv")]
+#[case("http://example.com/iris.csv?version=2#top", "iris.csv")]
+#[case("file:///tmp/iris.data", "iris.data")]
+fn file_name_from_url(#[case] url: &str, #[case] expected: &str) {
+    assert_eq!(fetch(url, false).file_name(), expected);
+}
+
+#[rstest]
+#[case("http://example.com/")]
+#[case("http://")]
+#[should_panic(expected = "doesn't end with a file name")]
+fn errors_on_url_without_file_name(#[case] url: &str) {
+    fetch(url, false).file_name();
+}
+
+#[rstest]
+#[case("http://example.com/iris/metadata.md")]
+#[case("http://example.com/iris/..")]
+#[case("http://example.com/.")]
+#[should_panic(expected = "can't be saved as")]
+fn errors_on_reserved_file_name(#[case] url: &str) {
+    fetch(url, false).file_name();
+}
+
+// ++++++++ //
+// download //
+// ++++++++ //
+#[test]
+fn downloads_file_url() {
+    test_in_tmp_dir(
+        || {
+            fs::write("iris.csv", "id\n").unwrap();
+            let url = format!(
+                "file://{}",
+                env::current_dir().unwrap().join("iris.csv").display()
+            );
+
+            assert_eq!(
+                fetch(&url, false).download(None),
+                FetchResult::Downloaded {
+                    bytes: b"id\n".to_vec(),
+                    etag: None,
+                    last_modified: None,
+                }
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn unchanged_file_is_not_modified() {
+    test_in_tmp_dir(
+        || {
+            fs::write("iris.csv", "id\n").unwrap();
+            let url = format!(
+                "file://{}",
+                env::current_dir().unwrap().join("iris.csv").display()
+            );
+            let previous = Download {
+                file: String::from("iris.csv"),
+                url: url.clone(),
+                downloaded: String::from("2022-04-01T00:00:00Z"),
+                etag: None,
+                last_modified: None,
+                size: 3,
+                sha256: checksum::sha256_hex(b"id\n"),
+            };
+
+            assert_eq!(
+                fetch(&url, true).download(Some(&previous)),
+                FetchResult::NotModified
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "Unsupported URL ftp://example.com/iris.csv")]
+fn errors_on_unsupported_url() {
+    fetch("ftp://example.com/iris.csv", false).download(None);
+}
+
+// ++++ //
+// save //
+// ++++ //
+#[test]
+fn download_saved_in_data_source() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source();
+
+            let download = fetch("http://example.com/iris.csv", false).save(
+                b"id\n",
+                Some(String::from("\"abc\"")),
+                None,
+            );
+
+            assert_eq!(
+                fs::read_to_string("data_sources/iris/iris.csv").unwrap(),
+                "id\n"
+            );
+            assert_eq!(download.file, "iris.csv");
+            assert_eq!(download.size, 3);
+            assert_eq!(download.etag, Some(String::from("\"abc\"")));
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "iris.csv exists")]
+fn errors_on_download_already_exists() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source();
+            fs::write("data_sources/iris/iris.csv", "id\n").unwrap();
+
+            fetch("http://example.com/iris.csv", false).save(b"id\n", None, None);
+        },
+        true,
+    )
+}
+
+// +++++++++++++++ //
+// update_metadata //
+// +++++++++++++++ //
+#[test]
+fn row_added_to_website_details() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+            fs::write(
+                "data_sources/iris/metadata.md",
+                include_str!("../templates/data_sources/web_metadata.md"),
+            )
+            .unwrap();
+
+            let download = Download {
+                file: String::from("iris.csv"),
+                url: String::from("http://example.com/iris.csv"),
+                downloaded: String::from("2022-04-01T00:00:00Z"),
+                etag: Some(String::from("\"abc\"")),
+                last_modified: None,
+                size: 3,
+                sha256: String::from("def"),
+            };
+
+            fetch("http://example.com/iris.csv", false).update_metadata(&download);
+
+            let contents = fs::read_to_string("data_sources/iris/metadata.md").unwrap();
+            assert!(contents.contains(
+                "| iris.csv | http://example.com/iris.csv | 2022-04-01T00:00:00Z | \
+                ETag: \"abc\", size: 3 bytes, sha256: def |\n\n## Data dictionary"
+            ));
+        },
+        false,
+    )
+}
+
+#[test]
+fn website_details_added_when_missing() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+            fs::write("data_sources/iris/metadata.md", "# iris\n").unwrap();
+
+            let download = Download {
+                file: String::from("iris.csv"),
+                url: String::from("http://example.com/iris.csv"),
+                downloaded: String::from("2022-04-01T00:00:00Z"),
+                etag: None,
+                last_modified: None,
+                size: 3,
+                sha256: String::from("def"),
+            };
+
+            fetch("http://example.com/iris.csv", false).update_metadata(&download);
+
+            let contents = fs::read_to_string("data_sources/iris/metadata.md").unwrap();
+            assert!(contents.contains(
+                "## Website details\n\n\
+                | download file | download link | downloaded on | additional details |\n"
+            ));
+            assert!(contents.contains(
+                "| iris.csv | http://example.com/iris.csv | 2022-04-01T00:00:00Z | \
+                size: 3 bytes, sha256: def |\n"
+            ));
+        },
+        false,
+    )
+}
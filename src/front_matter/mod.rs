@@ -0,0 +1,254 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, fs, path};
+
+use crate::add_command::{DataSourceKind, Sensitivity};
+use crate::component::Stage;
+
+/// The fields at the top of a data source's metadata.md
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct DataSourceFrontMatter {
+    /// The name of the data source
+    pub name: String,
+    /// The name as it was written, if the name was made from it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The kind of data source
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<DataSourceKind>,
+    /// The person responsible for the data source
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// How sensitive the data is
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sensitivity: Option<Sensitivity>,
+    /// The date the data source was added, formatted as YYYY-MM-DD
+    pub created: String,
+    /// The date the data was last received or downloaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refreshed: Option<String>,
+    /// The date the data files must be deleted by, formatted as YYYY-MM-DD
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+    /// Why the data source needs reviewing, set when something it uses changes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub needs_review: Option<String>,
+    /// Any other fields added by hand, kept so they survive updates
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// The fields at the top of an exploration, model or product's README.md
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ComponentFrontMatter {
+    /// The name of the component
+    pub name: String,
+    /// The name as it was written, if the name was made from it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The stage the component belongs to
+    pub stage: Stage,
+    /// The person responsible for the component
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// The date the component was added, formatted as YYYY-MM-DD
+    pub created: String,
+    /// Why the component needs reviewing, set when something it uses changes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub needs_review: Option<String>,
+    /// Any other fields added by hand, kept so they survive updates
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// The fields at the top of the README and project scoping documents
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ProjectFrontMatter {
+    /// The name of the project
+    pub project: String,
+    /// The name as it was written, if the name was made from it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The date the project was created, formatted as YYYY-MM-DD
+    pub created: String,
+    /// Any other fields added by hand, kept so they survive updates
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// The reasons front matter couldn't be read
+#[derive(Debug)]
+pub enum FrontMatterError {
+    /// The document doesn't start with a front matter block
+    Missing,
+    /// The front matter isn't valid YAML or is missing required fields
+    Invalid(serde_yaml::Error),
+    /// The document couldn't be read
+    Io(std::io::Error),
+}
+
+impl fmt::Display for FrontMatterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FrontMatterError::Missing => write!(f, "the document has no front matter"),
+            FrontMatterError::Invalid(err) => write!(f, "the front matter is invalid: {}", err),
+            FrontMatterError::Io(err) => write!(f, "the document can't be read: {}", err),
+        }
+    }
+}
+
+/// Today's date formatted as YYYY-MM-DD
+pub fn today() -> String {
+    chrono::Local::now()
+        .date_naive()
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+/// Formats some fields as a front matter block
+///
+/// # Arguments
+///
+/// * `front_matter` - The fields to write
+///
+/// # Return value
+///
+/// The YAML for the fields between two `---` lines
+pub fn render<T: Serialize>(front_matter: &T) -> String {
+    let yaml = serde_yaml::to_string(front_matter).expect("Unable to serialise the front matter");
+
+    format!("---\n{}---\n", yaml)
+}
+
+/// Splits a document into its front matter and the rest of the document
+///
+/// # Arguments
+///
+/// * `contents` - The contents of a markdown document
+///
+/// # Return value
+///
+/// The YAML between the `---` lines and the body after them, or None if the
+/// document doesn't start with front matter
+pub fn split(contents: &str) -> Option<(&str, &str)> {
+    let first_line_end = contents.find('\n')?;
+    if contents[..first_line_end].trim_end() != "---" {
+        return None;
+    }
+
+    let yaml_start = first_line_end + 1;
+    let mut line_start = yaml_start;
+
+    while line_start <= contents.len() {
+        let line_end = contents[line_start..]
+            .find('\n')
+            .map(|idx| line_start + idx)
+            .unwrap_or(contents.len());
+
+        if contents[line_start..line_end].trim_end() == "---" {
+            let body_start = (line_end + 1).min(contents.len());
+
+            return Some((&contents[yaml_start..line_start], &contents[body_start..]));
+        }
+
+        line_start = line_end + 1;
+    }
+
+    None
+}
+
+/// Reads the front matter of a document into a typed struct
+///
+/// # Arguments
+///
+/// * `contents` - The contents of a markdown document
+///
+/// # Errors
+///
+/// * The document doesn't start with front matter
+/// * The front matter can't be parsed into the struct
+pub fn parse<T: DeserializeOwned>(contents: &str) -> Result<T, FrontMatterError> {
+    let (yaml, _) = split(contents).ok_or(FrontMatterError::Missing)?;
+
+    serde_yaml::from_str(yaml).map_err(FrontMatterError::Invalid)
+}
+
+/// Replaces the front matter of a document, adding it if there isn't any
+///
+/// # Arguments
+///
+/// * `contents` - The contents of a markdown document
+/// * `front_matter` - The fields to write
+pub fn replace<T: Serialize>(contents: &str, front_matter: &T) -> String {
+    let body = split(contents).map(|(_, body)| body).unwrap_or(contents);
+
+    format!(
+        "{}\n{}",
+        render(front_matter),
+        body.trim_start_matches(['\r', '\n'])
+    )
+}
+
+/// Reads the front matter of a data source's metadata.md
+///
+/// # Arguments
+///
+/// * `data_source` - The folder of the data source
+pub fn read_data_source(
+    data_source: &path::Path,
+) -> Result<DataSourceFrontMatter, FrontMatterError> {
+    let contents =
+        fs::read_to_string(data_source.join("metadata.md")).map_err(FrontMatterError::Io)?;
+
+    parse(&contents)
+}
+
+/// Updates the front matter of a document
+///
+/// Documents without front matter, or with front matter that can't be
+/// parsed, are left alone.
+///
+/// # Arguments
+///
+/// * `document` - The path of the document
+/// * `update` - Changes the fields
+///
+/// # Return value
+///
+/// Whether the front matter was updated
+pub fn update<T, F>(document: &path::Path, update: F) -> bool
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce(&mut T),
+{
+    let contents = fs::read_to_string(document).unwrap_or_default();
+
+    match parse::<T>(&contents) {
+        Ok(mut front_matter) => {
+            update(&mut front_matter);
+
+            fs::write(document, replace(&contents, &front_matter))
+                .unwrap_or_else(|_| panic!("Unable to update {}", document.display()));
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Updates the front matter of a data source's metadata.md
+///
+/// Documents without front matter, or with front matter that can't be
+/// parsed, are left alone.
+///
+/// # Arguments
+///
+/// * `data_source` - The folder of the data source
+/// * `update` - Changes the fields
+pub fn update_data_source<F>(data_source: &path::Path, update_fields: F)
+where
+    F: FnOnce(&mut DataSourceFrontMatter),
+{
+    update(&data_source.join("metadata.md"), update_fields);
+}
+
+#[cfg(test)]
+mod tests;
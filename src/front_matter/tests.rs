@@ -0,0 +1,220 @@
+use std::fs;
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+use crate::component::Stage;
+
+fn example_front_matter() -> DataSourceFrontMatter {
+    DataSourceFrontMatter {
+        name: String::from("iris"),
+        title: None,
+        kind: Some(DataSourceKind::Extract),
+        owner: Some(String::from("Ronald Fisher")),
+        sensitivity: Some(Sensitivity::Restricted),
+        created: String::from("2022-04-01"),
+        refreshed: None,
+        expires: None,
+        needs_review: None,
+        extra: BTreeMap::new(),
+    }
+}
+
+// ++++++ //
+// render //
+// ++++++ //
+#[test]
+fn renders_fields_between_delimiters() {
+    assert_eq!(
+        render(&example_front_matter()),
+        "---\n\
+        name: iris\n\
+        kind: extract\n\
+        owner: Ronald Fisher\n\
+        sensitivity: restricted\n\
+        created: 2022-04-01\n\
+        ---\n"
+    );
+}
+
+// +++++ //
+// split //
+// +++++ //
+#[test]
+fn splits_front_matter_from_body() {
+    let contents = "---\nname: iris\n---\n\n# iris\n";
+
+    assert_eq!(split(contents), Some(("name: iris\n", "\n# iris\n")));
+}
+
+#[test]
+fn splits_windows_line_endings() {
+    let contents = "---\r\nname: iris\r\n---\r\n# iris\r\n";
+
+    assert_eq!(split(contents), Some(("name: iris\r\n", "# iris\r\n")));
+}
+
+#[test]
+fn document_without_front_matter_isnt_split() {
+    assert_eq!(split("# iris\n---\n"), None);
+    assert_eq!(split("---\nname: iris\n"), None);
+}
+
+// +++++ //
+// parse //
+// +++++ //
+#[test]
+fn parses_into_typed_struct() {
+    let contents = format!("{}\n# iris\n", render(&example_front_matter()));
+
+    let parsed: DataSourceFrontMatter = parse(&contents).unwrap();
+
+    assert_eq!(parsed, example_front_matter());
+}
+
+#[test]
+fn keeps_extra_fields() {
+    let contents = "---\nproject: iris\ncreated: 2022-04-01\nsponsor: Ronald Fisher\n---\n";
+
+    let parsed: ProjectFrontMatter = parse(contents).unwrap();
+
+    assert_eq!(
+        parsed.extra.get("sponsor"),
+        Some(&serde_yaml::Value::from("Ronald Fisher"))
+    );
+    assert!(render(&parsed).contains("sponsor: Ronald Fisher\n"));
+}
+
+#[test]
+fn missing_front_matter_errors() {
+    let result = parse::<DataSourceFrontMatter>("# iris\n");
+
+    assert!(matches!(result, Err(FrontMatterError::Missing)));
+}
+
+#[test]
+fn invalid_front_matter_errors() {
+    let result = parse::<DataSourceFrontMatter>("---\nkind: spreadsheet\n---\n");
+
+    assert!(matches!(result, Err(FrontMatterError::Invalid(_))));
+}
+
+// +++++++ //
+// replace //
+// +++++++ //
+#[test]
+fn adds_front_matter_to_document() {
+    assert_eq!(
+        replace("# iris\n", &example_front_matter()),
+        format!("{}\n# iris\n", render(&example_front_matter()))
+    );
+}
+
+#[test]
+fn replaces_existing_front_matter() {
+    let mut front_matter = example_front_matter();
+    let contents = replace("# iris\n", &front_matter);
+
+    front_matter.refreshed = Some(String::from("2022-05-01"));
+
+    assert_eq!(
+        replace(&contents, &front_matter),
+        format!("{}\n# iris\n", render(&front_matter))
+    );
+}
+
+// ++++++++++++++++++ //
+// update_data_source //
+// ++++++++++++++++++ //
+#[test]
+fn updates_data_source_front_matter() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+            fs::write(
+                "data_sources/iris/metadata.md",
+                replace("# iris\n", &example_front_matter()),
+            )
+            .unwrap();
+
+            let data_source = path::Path::new("data_sources/iris");
+            update_data_source(data_source, |front_matter| {
+                front_matter.refreshed = Some(String::from("2022-05-01"))
+            });
+
+            let updated = read_data_source(data_source).unwrap();
+            assert_eq!(updated.refreshed, Some(String::from("2022-05-01")));
+            assert_eq!(updated.owner, Some(String::from("Ronald Fisher")));
+        },
+        false,
+    )
+}
+
+#[test]
+fn documents_without_front_matter_left_alone() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+            fs::write("data_sources/iris/metadata.md", "# iris\n").unwrap();
+
+            update_data_source(path::Path::new("data_sources/iris"), |front_matter| {
+                front_matter.refreshed = Some(String::from("2022-05-01"))
+            });
+
+            assert_eq!(
+                fs::read_to_string("data_sources/iris/metadata.md").unwrap(),
+                "# iris\n"
+            );
+        },
+        false,
+    )
+}
+
+// ++++++ //
+// update //
+// ++++++ //
+#[test]
+fn updates_component_front_matter() {
+    test_in_tmp_dir(
+        || {
+            let front_matter = ComponentFrontMatter {
+                name: String::from("eda"),
+                title: None,
+                stage: Stage::Exploration,
+                owner: None,
+                created: String::from("2022-04-01"),
+                needs_review: None,
+                extra: BTreeMap::new(),
+            };
+            fs::write("README.md", replace("# eda\n", &front_matter)).unwrap();
+
+            let updated = update(
+                "README.md".as_ref(),
+                |front_matter: &mut ComponentFrontMatter| {
+                    front_matter.needs_review = Some(String::from("iris changed"))
+                },
+            );
+
+            assert!(updated);
+            assert_eq!(
+                fs::read_to_string("README.md").unwrap(),
+                "---\nname: eda\nstage: exploration\ncreated: 2022-04-01\nneeds_review: iris changed\n---\n\n# eda\n"
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn update_reports_missing_front_matter() {
+    test_in_tmp_dir(
+        || {
+            fs::write("README.md", "# eda\n").unwrap();
+
+            assert!(!update(
+                "README.md".as_ref(),
+                |_: &mut ComponentFrontMatter| {}
+            ));
+        },
+        false,
+    )
+}
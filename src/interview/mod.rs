@@ -0,0 +1,332 @@
+use clap::Args;
+use console::Style;
+use std::{collections::BTreeMap, fs, io, path};
+
+use crate::add_command::DataSourceKind;
+use crate::component::Stage;
+use crate::front_matter;
+use crate::prompt;
+
+/// Where an answer goes in a generated document
+#[derive(Debug, PartialEq)]
+pub enum Target {
+    /// Replaces a line of the template, e.g an instruction in italics
+    Line(&'static str),
+    /// Replaces a cell in a table row of the template, the row being the one
+    /// that starts with the given text and the column counting from 0
+    Cell(&'static str, usize),
+    /// Sets a field in the front matter
+    FrontMatter(&'static str),
+}
+
+/// A question asked when a document is generated
+#[derive(Debug, PartialEq)]
+pub struct Question {
+    /// The key of the answer in an answers file
+    pub key: &'static str,
+    /// The question asked in the terminal
+    pub prompt: &'static str,
+    /// Where the answer goes
+    pub targets: &'static [Target],
+}
+
+/// The questions asked when a project is created
+const PROJECT_QUESTIONS: &[Question] = &[
+    Question {
+        key: "introduction",
+        prompt: "Briefly, what is the project about?",
+        targets: &[Target::Line("*brief intro to project*")],
+    },
+    Question {
+        key: "problem",
+        prompt: "What is the business problem?",
+        targets: &[
+            Target::Line("*buisness problem in business terms*"),
+            Target::Line("*In depth description of the problem*"),
+        ],
+    },
+    Question {
+        key: "stakeholders",
+        prompt: "Who are the key stakeholders?",
+        targets: &[Target::Line(
+            "*Who are the important stakeholders (product owner, SMEs, sponsor, ...)*",
+        )],
+    },
+];
+
+/// Gets the questions asked when a project is created
+///
+/// The answers go into both the README and the project scoping document.
+pub fn project_questions() -> Vec<&'static Question> {
+    PROJECT_QUESTIONS.iter().collect()
+}
+
+/// Questions asked for every component
+const COMPONENT_QUESTIONS: &[Question] = &[
+    Question {
+        key: "owner",
+        prompt: "Who is responsible for it?",
+        targets: &[Target::FrontMatter("owner")],
+    },
+    Question {
+        key: "description",
+        prompt: "Describe it in one line",
+        targets: &[Target::FrontMatter("description")],
+    },
+];
+
+/// Gets the questions asked when a data source is added
+///
+/// # Arguments
+///
+/// * `kind` - The kind of data source, which decides the details asked for
+pub fn data_source_questions(kind: Option<DataSourceKind>) -> Vec<&'static Question> {
+    const DATABASE: &[Question] = &[
+        Question {
+            key: "database",
+            prompt: "Which database is the data in?",
+            targets: &[Target::Cell("| *e.g datasets* |", 0)],
+        },
+        Question {
+            key: "tables",
+            prompt: "Which tables are accessed?",
+            targets: &[Target::Cell("| *e.g datasets* |", 1)],
+        },
+    ];
+    const EXTRACT: &[Question] = &[
+        Question {
+            key: "extract_file",
+            prompt: "What is the extract file called?",
+            targets: &[Target::Cell("| *e.g iris.csv* |", 0)],
+        },
+        Question {
+            key: "extractor",
+            prompt: "Who extracted the data?",
+            targets: &[Target::Cell("| *e.g iris.csv* |", 2)],
+        },
+    ];
+    const WEB: &[Question] = &[
+        Question {
+            key: "download_url",
+            prompt: "What is the download URL?",
+            targets: &[Target::Cell("| *e.g iris.data* |", 1)],
+        },
+        Question {
+            key: "download_file",
+            prompt: "What is the downloaded file called?",
+            targets: &[Target::Cell("| *e.g iris.data* |", 0)],
+        },
+    ];
+    const API: &[Question] = &[Question {
+        key: "endpoint",
+        prompt: "What is the API endpoint?",
+        targets: &[Target::Cell("| *e.g https://api.example.com", 0)],
+    }];
+    const SURVEY: &[Question] = &[Question {
+        key: "instrument",
+        prompt: "How were the responses collected?",
+        targets: &[Target::Cell("| *e.g Online questionnaire v2* |", 0)],
+    }];
+    const SYNTHETIC: &[Question] = &[Question {
+        key: "generator",
+        prompt: "What generated the data?",
+        targets: &[Target::Cell("| *e.g generate_iris.py* |", 0)],
+    }];
+
+    let details = match kind {
+        Some(DataSourceKind::Database) => DATABASE,
+        Some(DataSourceKind::Extract) => EXTRACT,
+        Some(DataSourceKind::Web) => WEB,
+        Some(DataSourceKind::Api) => API,
+        Some(DataSourceKind::Survey) => SURVEY,
+        Some(DataSourceKind::Synthetic) => SYNTHETIC,
+        None => &[],
+    };
+
+    COMPONENT_QUESTIONS.iter().chain(details.iter()).collect()
+}
+
+/// Gets the questions asked when an exploration, model or product is added
+///
+/// # Arguments
+///
+/// * `stage` - The stage of the component
+pub fn component_questions(stage: Stage) -> Vec<&'static Question> {
+    const EXPLORATION: &[Question] = &[Question {
+        key: "purpose",
+        prompt: "What question is the exploration trying to answer?",
+        targets: &[Target::Line(
+            "*What question is this exploration trying to answer*",
+        )],
+    }];
+    const MODEL: &[Question] = &[Question {
+        key: "purpose",
+        prompt: "What does the model predict?",
+        targets: &[Target::Line(
+            "*What the model predicts and how the business will use it*",
+        )],
+    }];
+    const PRODUCT: &[Question] = &[Question {
+        key: "purpose",
+        prompt: "What does the product deliver?",
+        targets: &[Target::Line("*What the product delivers and who uses it*")],
+    }];
+
+    let details = match stage {
+        Stage::Exploration => EXPLORATION,
+        Stage::Model => MODEL,
+        Stage::Product => PRODUCT,
+        Stage::DataSource => &[],
+    };
+
+    COMPONENT_QUESTIONS.iter().chain(details.iter()).collect()
+}
+
+/// Replaces a cell in a markdown table row
+fn _replace_cell(row: &str, column: usize, value: &str) -> String {
+    let mut cells: Vec<String> = row
+        .trim()
+        .trim_matches('|')
+        .split('|')
+        .map(|cell| cell.trim().to_string())
+        .collect();
+
+    if let Some(cell) = cells.get_mut(column) {
+        *cell = value.replace('|', "\\|");
+    }
+
+    format!("| {} |", cells.join(" | "))
+}
+
+/// Puts the answers into a generated document
+///
+/// Questions that weren't answered leave the template's text in place.
+///
+/// # Arguments
+///
+/// * `contents` - The generated document
+/// * `questions` - The questions that could have been answered
+/// * `answers` - The answers, keyed by the question's key
+pub fn apply(
+    contents: &str,
+    questions: &[&Question],
+    answers: &BTreeMap<String, String>,
+) -> String {
+    let mut lines: Vec<String> = contents.lines().map(|line| line.to_string()).collect();
+    let mut fields: Vec<(&str, &str)> = Vec::new();
+
+    for question in questions.iter() {
+        let answer = match answers.get(question.key) {
+            Some(answer) => answer,
+            None => continue,
+        };
+
+        for target in question.targets.iter() {
+            match target {
+                Target::Line(text) => {
+                    for line in lines.iter_mut().filter(|line| line.trim() == *text) {
+                        *line = answer.clone();
+                    }
+                }
+                Target::Cell(row, column) => {
+                    for line in lines.iter_mut().filter(|line| line.starts_with(row)) {
+                        *line = _replace_cell(line, *column, answer);
+                    }
+                }
+                Target::FrontMatter(field) => fields.push((field, answer)),
+            }
+        }
+    }
+
+    let mut applied = lines.join("\n");
+    if contents.ends_with('\n') {
+        applied.push('\n');
+    }
+
+    if fields.is_empty() {
+        return applied;
+    }
+
+    let mut front_matter = front_matter::parse::<serde_yaml::Mapping>(&applied).unwrap_or_default();
+    for (field, answer) in fields {
+        front_matter.insert(field.into(), answer.into());
+    }
+
+    front_matter::replace(&applied, &front_matter)
+}
+
+/// How the answers to the questions are gathered
+#[derive(Args, Clone, Default)]
+pub struct Interview {
+    /// Asks for the key details in the terminal
+    #[clap(short, long)]
+    pub interactive: bool,
+    /// A YAML file of answers to the questions, keyed by the question
+    #[clap(short, long, value_parser)]
+    pub answers: Option<path::PathBuf>,
+}
+
+impl Interview {
+    /// Reads the answers file
+    ///
+    /// # Errors
+    ///
+    /// * The file can't be read or isn't a YAML mapping of strings
+    pub fn load_answers(&self) -> BTreeMap<String, String> {
+        let answers_path = match &self.answers {
+            Some(answers_path) => answers_path,
+            None => return BTreeMap::new(),
+        };
+
+        let contents = fs::read_to_string(answers_path).unwrap_or_else(|_| {
+            eprintln!(
+                "{} {}",
+                answers_path.display(),
+                Style::new()
+                    .color256(9)
+                    .apply_to("doesn't exist or can't be read")
+            );
+            panic!("{} doesn't exist", answers_path.display())
+        });
+
+        serde_yaml::from_str(&contents)
+            .unwrap_or_else(|err| panic!("Unable to parse {}\n{}", answers_path.display(), err))
+    }
+
+    /// Gathers the answers from the answers file and the terminal
+    ///
+    /// Questions answered in the file, or skipped because the answer was
+    /// given another way, aren't asked. Blank answers are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `questions` - The questions to answer
+    /// * `skip` - The keys of questions that don't need asking
+    /// * `input` - Where the terminal answers are read from
+    pub fn gather<R: io::BufRead>(
+        &self,
+        questions: &[&Question],
+        skip: &[&str],
+        mut input: R,
+    ) -> BTreeMap<String, String> {
+        let mut answers = self.load_answers();
+        answers.retain(|key, answer| !skip.contains(&key.as_str()) && !answer.trim().is_empty());
+
+        if self.interactive {
+            for question in questions.iter() {
+                if answers.contains_key(question.key) || skip.contains(&question.key) {
+                    continue;
+                }
+
+                if let Some(answer) = prompt::ask(question.prompt, None, &mut input) {
+                    answers.insert(question.key.to_string(), answer);
+                }
+            }
+        }
+
+        answers
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,175 @@
+use rstest::rstest;
+use std::fs;
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+use crate::add_command;
+
+fn answers(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    pairs
+        .iter()
+        .map(|(key, answer)| (key.to_string(), answer.to_string()))
+        .collect()
+}
+
+// +++++++++ //
+// questions //
+// +++++++++ //
+#[rstest]
+#[case(Some(DataSourceKind::Database))]
+#[case(Some(DataSourceKind::Extract))]
+#[case(Some(DataSourceKind::Web))]
+#[case(Some(DataSourceKind::Api))]
+#[case(Some(DataSourceKind::Survey))]
+#[case(Some(DataSourceKind::Synthetic))]
+fn data_source_questions_target_their_template(#[case] kind: Option<DataSourceKind>) {
+    let template = add_command::metadata_template(kind);
+
+    for question in data_source_questions(kind) {
+        for target in question.targets.iter() {
+            match target {
+                Target::Line(text) => assert!(template.lines().any(|line| line.trim() == *text)),
+                Target::Cell(row, _) => assert!(template.lines().any(|line| line.starts_with(row))),
+                Target::FrontMatter(_) => {}
+            }
+        }
+    }
+}
+
+#[rstest]
+#[case(Stage::Exploration)]
+#[case(Stage::Model)]
+#[case(Stage::Product)]
+fn component_questions_target_their_template(#[case] stage: Stage) {
+    let template = add_command::readme_template(stage);
+
+    for question in component_questions(stage) {
+        for target in question.targets.iter() {
+            if let Target::Line(text) = target {
+                assert!(template.lines().any(|line| line.trim() == *text));
+            }
+        }
+    }
+}
+
+// +++++ //
+// apply //
+// +++++ //
+#[test]
+fn replaces_lines_and_cells() {
+    let contents = "# iris\n\
+        ## Database details\n\
+        | database name(s) | tables accessed | tables_created |\n\
+        | *e.g datasets* | *e.g iris* | *e.g iris_transformed* |\n";
+
+    assert_eq!(
+        apply(
+            contents,
+            &data_source_questions(Some(DataSourceKind::Database)),
+            &answers(&[("tables", "flowers, petals")])
+        ),
+        "# iris\n\
+        ## Database details\n\
+        | database name(s) | tables accessed | tables_created |\n\
+        | *e.g datasets* | flowers, petals | *e.g iris_transformed* |\n"
+    );
+}
+
+#[test]
+fn unanswered_questions_leave_template_text() {
+    let contents = "# iris\n## Introduction\n*brief intro to project*\n";
+
+    assert_eq!(
+        apply(
+            contents,
+            &project_questions(),
+            &answers(&[("problem", "Sort flowers")])
+        ),
+        contents
+    );
+}
+
+#[test]
+fn sets_front_matter_fields() {
+    let contents = "---\nname: eda\nstage: exploration\n---\n\n# eda\n";
+
+    assert_eq!(
+        apply(
+            contents,
+            &component_questions(Stage::Exploration),
+            &answers(&[("owner", "Ronald Fisher"), ("description", "Looks at petals")])
+        ),
+        "---\nname: eda\nstage: exploration\nowner: Ronald Fisher\ndescription: Looks at petals\n---\n\n# eda\n"
+    );
+}
+
+// ++++++ //
+// gather //
+// ++++++ //
+#[test]
+fn asks_questions_not_in_answers_file() {
+    test_in_tmp_dir(
+        || {
+            fs::write("answers.yaml", "owner: Ronald Fisher\nendpoint: ''\n").unwrap();
+
+            let interview = Interview {
+                interactive: true,
+                answers: Some(path::PathBuf::from("answers.yaml")),
+            };
+
+            assert_eq!(
+                interview.gather(
+                    &data_source_questions(Some(DataSourceKind::Api)),
+                    &[],
+                    "\nhttps://api.example.com/v2\n".as_bytes()
+                ),
+                answers(&[
+                    ("owner", "Ronald Fisher"),
+                    ("endpoint", "https://api.example.com/v2")
+                ])
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn skipped_questions_arent_asked() {
+    let interview = Interview {
+        interactive: true,
+        answers: None,
+    };
+
+    assert_eq!(
+        interview.gather(
+            &component_questions(Stage::Model),
+            &["owner"],
+            "Flowers\nPredicts species\n".as_bytes()
+        ),
+        answers(&[("description", "Flowers"), ("purpose", "Predicts species")])
+    );
+}
+
+#[test]
+fn nothing_asked_without_interactive() {
+    assert_eq!(
+        Interview::default().gather(&project_questions(), &[], "Sort flowers\n".as_bytes()),
+        BTreeMap::new()
+    );
+}
+
+#[test]
+#[should_panic(expected = "answers.yaml doesn't exist")]
+fn missing_answers_file_panics() {
+    test_in_tmp_dir(
+        || {
+            let interview = Interview {
+                interactive: false,
+                answers: Some(path::PathBuf::from("answers.yaml")),
+            };
+
+            interview.load_answers();
+        },
+        true,
+    )
+}
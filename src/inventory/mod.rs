@@ -0,0 +1,449 @@
+use clap::ValueEnum;
+use console::Style;
+use ptree::{item, TreeBuilder};
+use std::{collections::BTreeMap, fmt, fs, path};
+
+use crate::add_command::{self, DataSource, DataSourceKind};
+use crate::component::{list_archived, list_components, ComponentId, Stage};
+use crate::front_matter;
+use crate::interview::Interview;
+use crate::naming::{self, NamingConfig};
+
+/// The columns read from an inventory, any others are ignored
+pub const COLUMNS: [&str; 5] = ["name", "kind", "owner", "description", "url"];
+
+/// A data source listed in an inventory
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InventoryRow {
+    /// The line of the inventory the row is on, counting the header as 1
+    pub line: u64,
+    pub name: String,
+    /// The name as it's written in the inventory, set when the name is made
+    /// from it
+    pub title: Option<String>,
+    pub kind: Option<DataSourceKind>,
+    pub owner: Option<String>,
+    pub description: Option<String>,
+    pub url: Option<String>,
+}
+
+/// A row of an inventory that failed validation
+#[derive(Debug, PartialEq)]
+pub struct Rejection {
+    /// The line of the inventory the row is on, counting the header as 1
+    pub line: u64,
+    /// Why the row failed
+    pub reason: String,
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {} {}",
+            self.line,
+            Style::new().color256(9).apply_to(&self.reason)
+        )
+    }
+}
+
+/// Finds the kind of data source a cell names, matching it ignoring case
+fn _kind(value: &str) -> Result<Option<DataSourceKind>, String> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    DataSourceKind::from_str(value, true)
+        .map(Some)
+        .map_err(|_| {
+            format!(
+                "has the kind {}, which isn't one of {}",
+                value,
+                DataSourceKind::value_variants()
+                    .iter()
+                    .map(|kind| kind.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+}
+
+/// Checks a data source name against the project's naming rules
+fn _validate_name(
+    name: &str,
+    config: &NamingConfig,
+    slugify: bool,
+) -> Result<naming::Name, String> {
+    if name.is_empty() {
+        return Err(String::from("doesn't have a name"));
+    }
+
+    naming::name(name, Some(Stage::DataSource), config, slugify).map_err(|err| {
+        format!(
+            "has the name {}, which {}",
+            name,
+            naming::explain(name, Some(Stage::DataSource), config, &err)
+        )
+    })
+}
+
+/// Reads the data sources listed in an inventory
+///
+/// The inventory is a CSV file with a header row. Only the `name` column is
+/// required, see [COLUMNS] for the rest. Headers are matched ignoring case
+/// and surrounding spaces, and empty cells are treated as missing.
+///
+/// # Arguments
+///
+/// * `contents` - The contents of the inventory
+/// * `config` - The project's naming rules
+/// * `slugify` - Whether to make names from the ones in the inventory
+///
+/// # Return value
+///
+/// The rows that passed validation and the ones that didn't
+///
+/// # Errors
+///
+/// * The inventory doesn't have a header row with a name column
+pub fn parse(
+    contents: &str,
+    config: &NamingConfig,
+    slugify: bool,
+) -> Result<(Vec<InventoryRow>, Vec<Rejection>), String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(false)
+        .from_reader(contents.as_bytes());
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|err| format!("doesn't have a header row: {}", err))?
+        .iter()
+        .map(|header| header.trim().to_lowercase())
+        .collect();
+    let columns: BTreeMap<&str, usize> = COLUMNS
+        .iter()
+        .filter_map(|column| {
+            headers
+                .iter()
+                .position(|header| header == column)
+                .map(|position| (*column, position))
+        })
+        .collect();
+
+    if !columns.contains_key("name") {
+        return Err(String::from("doesn't have a name column"));
+    }
+
+    let mut rows = Vec::new();
+    let mut rejections = Vec::new();
+
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(err) => {
+                rejections.push(Rejection {
+                    line: err.position().map(|position| position.line()).unwrap_or(0),
+                    reason: format!("can't be read: {}", err),
+                });
+                continue;
+            }
+        };
+        let line = record
+            .position()
+            .map(|position| position.line())
+            .unwrap_or(0);
+        let cell = |column: &str| -> Option<String> {
+            columns
+                .get(column)
+                .and_then(|position| record.get(*position))
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        let url = cell("url");
+        let validated = _validate_name(&cell("name").unwrap_or_default(), config, slugify)
+            .and_then(|name| Ok((name, _kind(&cell("kind").unwrap_or_default())?)));
+
+        match validated {
+            Ok((name, kind)) => rows.push(InventoryRow {
+                line,
+                name: name.name,
+                title: name.title,
+                kind,
+                owner: cell("owner"),
+                description: cell("description"),
+                url,
+            }),
+            Err(reason) => rejections.push(Rejection { line, reason }),
+        }
+    }
+
+    Ok((rows, rejections))
+}
+
+/// Reads the data sources listed in an inventory file
+///
+/// See [parse] for the format of the inventory.
+///
+/// # Arguments
+///
+/// * `inventory` - The path of the inventory
+/// * `config` - The project's naming rules
+/// * `slugify` - Whether to make names from the ones in the inventory
+///
+/// # Errors
+///
+/// * The inventory can't be read
+/// * The inventory doesn't have a header row with a name column
+pub fn read(
+    inventory: &path::Path,
+    config: &NamingConfig,
+    slugify: bool,
+) -> (Vec<InventoryRow>, Vec<Rejection>) {
+    let red = Style::new().color256(9);
+
+    let contents = fs::read_to_string(inventory).unwrap_or_else(|_| {
+        eprintln!(
+            "{} {}",
+            inventory.display(),
+            red.apply_to("doesn't exist or can't be read")
+        );
+        panic!("{} doesn't exist", inventory.display())
+    });
+
+    parse(&contents, config, slugify).unwrap_or_else(|reason| {
+        eprintln!("{} {}", inventory.display(), red.apply_to(&reason));
+        panic!("{} {}", inventory.display(), reason)
+    })
+}
+
+/// Adds a data source for each row that doesn't already have one
+///
+/// Rows are added in order, so a name listed twice is only added once. The
+/// stage's prefix is added to each name, just as it is by `geoff add`.
+///
+/// # Arguments
+///
+/// * `rows` - The rows that passed validation
+/// * `defaults` - The data source passed on the command line
+/// * `uses` - The components every data source is derived from
+/// * `naming` - The project's naming rules
+///
+/// # Return value
+///
+/// The rows that were added, with the names they were added under, and the
+/// ones that were skipped
+pub fn add_all(
+    rows: Vec<InventoryRow>,
+    defaults: &DataSource,
+    uses: &[ComponentId],
+    naming: &NamingConfig,
+) -> (Vec<InventoryRow>, Vec<InventoryRow>) {
+    let mut added = Vec::new();
+    let mut skipped = Vec::new();
+
+    for row in rows.into_iter() {
+        if row.exists(naming) {
+            skipped.push(row);
+            continue;
+        }
+
+        let row = row.prefixed(naming);
+        let data_source = row.data_source(defaults);
+        data_source.add(uses, &row.answers(data_source.kind));
+        row.record_url(data_source.kind);
+
+        added.push(row);
+    }
+
+    (added, skipped)
+}
+
+impl InventoryRow {
+    /// The id of the data source as a component
+    pub fn id(&self) -> ComponentId {
+        ComponentId::new(Stage::DataSource, &self.name)
+    }
+
+    /// Whether the data source already exists, including in the archive
+    ///
+    /// When data sources are prefixed one with the same name once its prefix
+    /// is left off counts, so an inventory can be added again.
+    ///
+    /// # Arguments
+    ///
+    /// * `naming` - The project's naming rules
+    pub fn exists(&self, naming: &NamingConfig) -> bool {
+        if self.id().exists() || self.id().archive_path().is_dir() {
+            return true;
+        }
+
+        naming.prefix(Stage::DataSource).is_some()
+            && list_components(Stage::DataSource)
+                .into_iter()
+                .chain(list_archived(Stage::DataSource))
+                .any(|component| naming::unprefixed(&component.name) == self.name)
+    }
+
+    /// The row with the stage's prefix added to its name, see
+    /// [add_command::prefixed]
+    ///
+    /// # Arguments
+    ///
+    /// * `naming` - The project's naming rules
+    pub fn prefixed(self, naming: &NamingConfig) -> InventoryRow {
+        let named = add_command::prefixed(
+            naming::Name {
+                name: self.name.clone(),
+                title: self.title.clone(),
+            },
+            Stage::DataSource,
+            naming,
+        );
+
+        InventoryRow {
+            name: named.name,
+            title: named.title,
+            ..self
+        }
+    }
+
+    /// Creates the data source to add for the row
+    ///
+    /// Anything the row leaves out is taken from the options passed with
+    /// `--from-csv`. A row without a kind but with a URL is taken to be a web
+    /// data source.
+    ///
+    /// # Arguments
+    ///
+    /// * `defaults` - The data source passed on the command line
+    pub fn data_source(&self, defaults: &DataSource) -> DataSource {
+        DataSource {
+            name: Some(path::PathBuf::from(&self.name)),
+            kind: self
+                .kind
+                .or(defaults.kind)
+                .or_else(|| self.url.as_ref().map(|_| DataSourceKind::Web)),
+            owner: self.owner.clone().or_else(|| defaults.owner.clone()),
+            sensitivity: defaults.sensitivity,
+            expires: defaults.expires,
+            uses: Vec::new(),
+            from_csv: None,
+            slugify: false,
+            title: self.title.clone(),
+            interview: Interview::default(),
+        }
+    }
+
+    /// The answers the row gives to the data source questions
+    ///
+    /// The URL goes into the metadata template of web and API data sources.
+    /// Other kinds of data source don't have a place for it, see
+    /// [InventoryRow::record_url].
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of data source being added
+    pub fn answers(&self, kind: Option<DataSourceKind>) -> BTreeMap<String, String> {
+        let mut answers = BTreeMap::new();
+
+        if let Some(description) = &self.description {
+            answers.insert(String::from("description"), description.clone());
+        }
+
+        if let Some(url) = &self.url {
+            match kind {
+                Some(DataSourceKind::Web) => {
+                    answers.insert(String::from("download_url"), url.clone());
+                }
+                Some(DataSourceKind::Api) => {
+                    answers.insert(String::from("endpoint"), url.clone());
+                }
+                _ => (),
+            }
+        }
+
+        answers
+    }
+
+    /// Records the URL in the front matter of data sources whose metadata
+    /// template doesn't have a place for it
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of data source that was added
+    pub fn record_url(&self, kind: Option<DataSourceKind>) {
+        let url = match (&self.url, kind) {
+            (_, Some(DataSourceKind::Web)) | (_, Some(DataSourceKind::Api)) | (None, _) => return,
+            (Some(url), _) => url,
+        };
+
+        front_matter::update_data_source(&self.id().path(), |front_matter| {
+            front_matter
+                .extra
+                .insert(String::from("url"), url.as_str().into());
+        });
+    }
+}
+
+/// Creates a tree showing the data sources that were added
+///
+/// # Arguments
+///
+/// * `added` - The rows that were added
+pub fn create_tree(added: &[InventoryRow]) -> item::StringItem {
+    let gold = Style::new().color256(220);
+    let hd = Style::new().color256(194);
+
+    let mut tree = TreeBuilder::new(format!("{} data_sources", gold.apply_to("\u{1F5BF}")));
+
+    for row in added.iter() {
+        tree.begin_child(format!("{} {}", gold.apply_to("\u{1F5BF}"), row.name))
+            .add_empty_child(format!("{} metadata.md", hd.apply_to("\u{1F5CE}")))
+            .end_child();
+    }
+
+    tree.build()
+}
+
+/// Formats the rows that weren't added with a line for each, in the order
+/// they are in the inventory
+///
+/// # Arguments
+///
+/// * `skipped` - The rows whose data source already exists
+/// * `rejections` - The rows that failed validation
+pub fn summary(skipped: &[InventoryRow], rejections: &[Rejection]) -> String {
+    let yellow = Style::new().color256(220);
+
+    let mut lines: Vec<(u64, String)> = skipped
+        .iter()
+        .map(|row| {
+            (
+                row.line,
+                format!(
+                    "line {} {} {}",
+                    row.line,
+                    row.id(),
+                    yellow.apply_to("already exists, skipped")
+                ),
+            )
+        })
+        .chain(
+            rejections
+                .iter()
+                .map(|rejection| (rejection.line, rejection.to_string())),
+        )
+        .collect();
+    lines.sort();
+
+    lines
+        .into_iter()
+        .map(|(_, line)| line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests;
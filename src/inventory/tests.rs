@@ -0,0 +1,304 @@
+use rstest::rstest;
+use std::fs;
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+use crate::add_command::Sensitivity;
+use crate::naming::Prefix;
+
+fn defaults() -> DataSource {
+    DataSource {
+        name: None,
+        kind: None,
+        owner: None,
+        sensitivity: None,
+        expires: None,
+        uses: vec![],
+        from_csv: Some(path::PathBuf::from("inventory.csv")),
+        slugify: false,
+        title: None,
+        interview: Interview::default(),
+    }
+}
+
+fn row(name: &str) -> InventoryRow {
+    InventoryRow {
+        line: 2,
+        name: String::from(name),
+        ..InventoryRow::default()
+    }
+}
+
+// +++++ //
+// parse //
+// +++++ //
+#[test]
+fn parses_rows() {
+    let (rows, rejections) = parse(
+        "name,kind,owner,description,url\n\
+        iris,web,Ronald Fisher,Iris flowers,https://example.com/iris.data\n\
+        sales,,,,\n",
+        &NamingConfig::default(),
+        false,
+    )
+    .unwrap();
+
+    assert!(rejections.is_empty());
+    assert_eq!(
+        rows,
+        vec![
+            InventoryRow {
+                line: 2,
+                name: String::from("iris"),
+                title: None,
+                kind: Some(DataSourceKind::Web),
+                owner: Some(String::from("Ronald Fisher")),
+                description: Some(String::from("Iris flowers")),
+                url: Some(String::from("https://example.com/iris.data")),
+            },
+            InventoryRow {
+                line: 3,
+                ..row("sales")
+            },
+        ]
+    );
+}
+
+#[test]
+fn matches_headers_ignoring_case_and_order() {
+    let (rows, _) = parse(
+        " Owner ,Notes,NAME,Kind\nRonald Fisher,ignored,iris,Database\n",
+        &NamingConfig::default(),
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(rows[0].name, "iris");
+    assert_eq!(rows[0].owner, Some(String::from("Ronald Fisher")));
+    assert_eq!(rows[0].kind, Some(DataSourceKind::Database));
+}
+
+#[test]
+fn missing_name_column_errors() {
+    assert_eq!(
+        parse(
+            "kind,owner\nweb,Ronald Fisher\n",
+            &NamingConfig::default(),
+            false
+        ),
+        Err(String::from("doesn't have a name column"))
+    );
+}
+
+#[test]
+fn rejects_invalid_rows() {
+    let (rows, rejections) = parse(
+        "name,kind\n\
+        ,web\n\
+        raw/iris,web\n\
+        iris,spreadsheet\n\
+        sales\n\
+        crm,extract\n",
+        &NamingConfig::default(),
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![InventoryRow {
+            line: 6,
+            kind: Some(DataSourceKind::Extract),
+            ..row("crm")
+        }]
+    );
+    assert_eq!(
+        rejections
+            .iter()
+            .map(|rejection| rejection.line)
+            .collect::<Vec<_>>(),
+        vec![2, 3, 4, 5]
+    );
+    assert_eq!(rejections[0].reason, "doesn't have a name");
+    assert_eq!(
+        rejections[1].reason,
+        "has the name raw/iris, which contains a path separator, try raw_iris"
+    );
+    assert_eq!(
+        rejections[2].reason,
+        "has the kind spreadsheet, which isn't one of database, extract, web, api, survey, synthetic"
+    );
+    assert!(rejections[3].reason.starts_with("can't be read"));
+}
+
+#[test]
+fn slugifies_names() {
+    let (rows, rejections) =
+        parse("name\nSales Data 2022\n", &NamingConfig::default(), true).unwrap();
+
+    assert!(rejections.is_empty());
+    assert_eq!(rows[0].name, "sales_data_2022");
+    assert_eq!(rows[0].title, Some(String::from("Sales Data 2022")));
+    assert_eq!(
+        rows[0].data_source(&defaults()).title,
+        Some(String::from("Sales Data 2022"))
+    );
+}
+
+// +++++++++++ //
+// data_source //
+// +++++++++++ //
+#[test]
+fn row_overrides_defaults() {
+    let mut defaults = defaults();
+    defaults.kind = Some(DataSourceKind::Extract);
+    defaults.owner = Some(String::from("Data team"));
+    defaults.sensitivity = Some(Sensitivity::Internal);
+
+    let data_source = InventoryRow {
+        kind: Some(DataSourceKind::Database),
+        ..row("sales")
+    }
+    .data_source(&defaults);
+
+    assert_eq!(data_source.name(), path::Path::new("sales"));
+    assert_eq!(data_source.kind, Some(DataSourceKind::Database));
+    assert_eq!(data_source.owner, Some(String::from("Data team")));
+    assert_eq!(data_source.sensitivity, Some(Sensitivity::Internal));
+}
+
+#[test]
+fn url_without_kind_is_web() {
+    let data_source = InventoryRow {
+        url: Some(String::from("https://example.com/iris.data")),
+        ..row("iris")
+    }
+    .data_source(&defaults());
+
+    assert_eq!(data_source.kind, Some(DataSourceKind::Web));
+}
+
+// +++++++ //
+// answers //
+// +++++++ //
+#[rstest]
+#[case(Some(DataSourceKind::Web), Some("download_url"))]
+#[case(Some(DataSourceKind::Api), Some("endpoint"))]
+#[case(Some(DataSourceKind::Database), None)]
+#[case(None, None)]
+fn url_answers_question_for_kind(#[case] kind: Option<DataSourceKind>, #[case] key: Option<&str>) {
+    let answers = InventoryRow {
+        description: Some(String::from("Iris flowers")),
+        url: Some(String::from("https://example.com/iris")),
+        ..row("iris")
+    }
+    .answers(kind);
+
+    assert_eq!(
+        answers.get("description"),
+        Some(&String::from("Iris flowers"))
+    );
+    assert_eq!(
+        key.and_then(|key| answers.get(key)),
+        key.map(|_| String::from("https://example.com/iris"))
+            .as_ref()
+    );
+    assert_eq!(answers.len(), if key.is_some() { 2 } else { 1 });
+}
+
+// +++++++ //
+// add_all //
+// +++++++ //
+#[test]
+fn adds_rows_and_skips_existing() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/_archive/species").unwrap();
+            fs::create_dir_all("data_sources/sales").unwrap();
+
+            let rows = vec![
+                InventoryRow {
+                    kind: Some(DataSourceKind::Database),
+                    url: Some(String::from("postgres://warehouse")),
+                    ..row("crm")
+                },
+                row("sales"),
+                row("species"),
+                InventoryRow {
+                    line: 5,
+                    ..row("crm")
+                },
+            ];
+
+            let (added, skipped) = add_all(rows, &defaults(), &[], &NamingConfig::default());
+
+            assert_eq!(
+                added
+                    .iter()
+                    .map(|row| row.name.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["crm"]
+            );
+            assert_eq!(
+                skipped
+                    .iter()
+                    .map(|row| row.name.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["sales", "species", "crm"]
+            );
+
+            let metadata =
+                front_matter::read_data_source(path::Path::new("data_sources/crm")).unwrap();
+            assert_eq!(metadata.kind, Some(DataSourceKind::Database));
+            assert_eq!(
+                metadata.extra.get("url"),
+                Some(&serde_yaml::Value::from("postgres://warehouse"))
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn adds_prefix_and_skips_prefixed() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/001_sales").unwrap();
+            let naming = NamingConfig {
+                prefixes: [(String::from("data_sources"), Prefix::Number)]
+                    .into_iter()
+                    .collect(),
+                ..NamingConfig::default()
+            };
+
+            let (added, skipped) =
+                add_all(vec![row("crm"), row("sales")], &defaults(), &[], &naming);
+
+            assert_eq!(added[0].name, "002_crm");
+            assert!(path::Path::new("data_sources/002_crm/metadata.md").exists());
+            assert_eq!(skipped[0].name, "sales");
+        },
+        false,
+    )
+}
+
+// +++++++ //
+// summary //
+// +++++++ //
+#[test]
+fn summary_is_in_inventory_order() {
+    let summary = summary(
+        &[InventoryRow {
+            line: 4,
+            ..row("iris")
+        }],
+        &[Rejection {
+            line: 3,
+            reason: String::from("doesn't have a name"),
+        }],
+    );
+
+    let lines: Vec<&str> = summary.lines().collect();
+    assert!(lines[0].starts_with("line 3 "));
+    assert!(lines[1].starts_with("line 4 data_sources/iris "));
+}
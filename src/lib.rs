@@ -1,3 +1,29 @@
 pub mod add_command;
+pub mod adopt_command;
+pub mod apply_command;
+pub mod archive_command;
+pub mod audit_command;
+pub mod check_command;
+pub mod checksum;
+pub mod component;
 pub mod create_command;
+pub mod data_source_command;
+pub mod front_matter;
+pub mod interview;
+pub mod inventory;
+pub mod lineage_command;
+pub mod list_command;
+pub mod manifest;
+pub mod metadata;
+pub mod migrate_command;
+pub mod naming;
 pub mod pleasant_error;
+pub mod promote_command;
+pub mod prompt;
+pub mod remove_command;
+pub mod rename_command;
+pub mod retention_command;
+pub mod show_command;
+pub mod sync_command;
+pub mod template_command;
+pub mod verify_command;
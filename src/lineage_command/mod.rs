@@ -0,0 +1,417 @@
+use clap::{Args, ValueEnum};
+use console::Style;
+use ptree::{item, TreeBuilder};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::add_command::resolve_uses;
+use crate::component::{list_components, ComponentId, Stage};
+use crate::front_matter::{self, ComponentFrontMatter, DataSourceFrontMatter};
+use crate::manifest::Manifest;
+
+/// Lists every component a component uses, directly or through others
+///
+/// # Arguments
+///
+/// * `manifest` - The project manifest holding the dependencies
+/// * `component` - The component to start from
+pub fn upstream_of(manifest: &Manifest, component: &ComponentId) -> BTreeSet<ComponentId> {
+    let mut upstream = BTreeSet::new();
+    let mut to_visit = vec![component.clone()];
+
+    while let Some(current) = to_visit.pop() {
+        for used in manifest.dependencies.get(&current).into_iter().flatten() {
+            if upstream.insert(used.clone()) {
+                to_visit.push(used.clone());
+            }
+        }
+    }
+
+    upstream
+}
+
+/// Lists the components that use a component directly
+///
+/// # Arguments
+///
+/// * `manifest` - The project manifest holding the dependencies
+/// * `component` - The upstream component
+pub fn used_by(manifest: &Manifest, component: &ComponentId) -> BTreeSet<ComponentId> {
+    manifest
+        .dependencies
+        .iter()
+        .filter(|(_, uses)| uses.contains(component))
+        .map(|(downstream, _)| downstream.clone())
+        .collect()
+}
+
+/// Lists every component that uses a component, directly or through others
+///
+/// # Arguments
+///
+/// * `manifest` - The project manifest holding the dependencies
+/// * `component` - The component to start from
+pub fn downstream_of(manifest: &Manifest, component: &ComponentId) -> BTreeSet<ComponentId> {
+    let mut downstream = BTreeSet::new();
+    let mut to_visit = vec![component.clone()];
+
+    while let Some(current) = to_visit.pop() {
+        for user in used_by(manifest, &current) {
+            if downstream.insert(user.clone()) {
+                to_visit.push(user);
+            }
+        }
+    }
+
+    downstream
+}
+
+#[derive(Args)]
+pub struct Link {
+    /// The component that uses the others
+    #[clap(value_parser)]
+    pub component: String,
+    /// A component it uses, can be repeated
+    #[clap(short, long, value_parser, required = true)]
+    pub uses: Vec<String>,
+}
+
+impl Link {
+    /// Records the dependencies in the manifest
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest
+    ///
+    /// # Errors
+    ///
+    /// * Any of the components don't exist or are ambiguous
+    /// * The component would use itself, directly or through others
+    ///
+    /// # Return value
+    ///
+    /// The component and the components it now uses
+    pub fn link(&self, manifest: &mut Manifest) -> (ComponentId, Vec<ComponentId>) {
+        let component = ComponentId::resolve(&self.component);
+        let uses = resolve_uses(&self.uses);
+
+        for used in uses.iter() {
+            if used == &component || upstream_of(manifest, used).contains(&component) {
+                eprintln!(
+                    "{} {} {}",
+                    component,
+                    Style::new()
+                        .color256(9)
+                        .apply_to("can't use a component that already uses it:"),
+                    used
+                );
+                panic!("Linking {} to {} creates a cycle", component, used);
+            }
+        }
+
+        manifest.add_dependencies(&component, &uses);
+
+        (component, uses)
+    }
+}
+
+#[derive(Args)]
+pub struct Impact {
+    /// The component that has changed or been withdrawn
+    #[clap(value_parser)]
+    pub component: String,
+    /// Mark every affected component as needing review in its front matter
+    #[clap(short, long)]
+    pub mark_review: bool,
+}
+
+impl Impact {
+    /// Finds the component the reference is to
+    ///
+    /// Components whose folder has been deleted are found from the ids
+    /// recorded in the manifest when no live component matches, so the
+    /// impact of withdrawing one can still be seen.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest holding the dependencies
+    fn _component(&self, manifest: &Manifest) -> ComponentId {
+        let missing: BTreeSet<&ComponentId> = manifest
+            .dependencies
+            .iter()
+            .flat_map(|(component, uses)| std::iter::once(component).chain(uses.iter()))
+            .chain(manifest.archived.keys())
+            .filter(|component| {
+                !component.exists()
+                    && (component.to_string() == self.component || component.name == self.component)
+            })
+            .collect();
+
+        let live = match self.component.parse::<ComponentId>() {
+            Ok(component) => component.exists(),
+            Err(_) => Stage::ALL
+                .iter()
+                .flat_map(|stage| list_components(*stage))
+                .any(|component| component.name.starts_with(&self.component)),
+        };
+
+        match missing.len() {
+            1 if !live => missing.into_iter().next().expect("There is one").clone(),
+            _ => ComponentId::resolve(&self.component),
+        }
+    }
+
+    /// Finds the components affected by a change to the component
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest holding the dependencies
+    ///
+    /// # Errors
+    ///
+    /// * The component doesn't exist or is ambiguous
+    ///
+    /// # Return value
+    ///
+    /// The component and every component downstream of it, ordered by stage
+    pub fn affected(&self, manifest: &Manifest) -> (ComponentId, Vec<ComponentId>) {
+        let component = self._component(manifest);
+        let affected = downstream_of(manifest, &component).into_iter().collect();
+
+        (component, affected)
+    }
+
+    /// Sets the needs_review field in the front matter of each component
+    ///
+    /// # Arguments
+    ///
+    /// * `component` - The component that changed
+    /// * `affected` - The components downstream of it
+    ///
+    /// # Return value
+    ///
+    /// The components that were marked, which leaves out any without front
+    /// matter
+    pub fn mark_for_review(
+        &self,
+        component: &ComponentId,
+        affected: &[ComponentId],
+    ) -> Vec<ComponentId> {
+        let reason = format!("{} changed on {}", component, front_matter::today());
+
+        affected
+            .iter()
+            .filter(|downstream| match downstream.stage {
+                Stage::DataSource => front_matter::update(
+                    &downstream.document(),
+                    |fields: &mut DataSourceFrontMatter| fields.needs_review = Some(reason.clone()),
+                ),
+                _ => front_matter::update(
+                    &downstream.document(),
+                    |fields: &mut ComponentFrontMatter| fields.needs_review = Some(reason.clone()),
+                ),
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Creates a tree of the components that use the component
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest holding the dependencies
+    /// * `component` - The component that changed
+    pub fn create_tree(&self, manifest: &Manifest, component: &ComponentId) -> item::StringItem {
+        let mut tree = TreeBuilder::new(format!(
+            "{} {}",
+            Style::new().color256(220).apply_to("\u{1F5BF}"),
+            component
+        ));
+
+        self._add_users(&mut tree, manifest, component, &mut vec![component.clone()]);
+
+        tree.build()
+    }
+
+    /// Adds a child for each component that uses the component, recursively
+    fn _add_users(
+        &self,
+        tree: &mut TreeBuilder,
+        manifest: &Manifest,
+        component: &ComponentId,
+        visiting: &mut Vec<ComponentId>,
+    ) {
+        for user in used_by(manifest, component) {
+            if visiting.contains(&user) {
+                continue;
+            }
+
+            tree.begin_child(format!(
+                "{} {}",
+                Style::new().color256(194).apply_to("\u{1F5CE}"),
+                user
+            ));
+            visiting.push(user.clone());
+            self._add_users(tree, manifest, &user, visiting);
+            visiting.pop();
+            tree.end_child();
+        }
+    }
+}
+
+/// The formats the lineage graph can be written in
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum GraphFormat {
+    /// Graphviz DOT
+    Dot,
+    /// A Mermaid flowchart
+    Mermaid,
+}
+
+#[derive(Args)]
+pub struct Graph {
+    /// The format to write the graph in
+    #[clap(short, long, value_enum, default_value = "dot")]
+    pub format: GraphFormat,
+}
+
+/// Escapes a string to go between the double quotes of a DOT id or label
+fn _dot_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl Graph {
+    /// Lists every component in the graph grouped by stage
+    ///
+    /// Components that only appear in the manifest, e.g because they've been
+    /// deleted, are included so their dependencies aren't lost.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest holding the dependencies
+    pub fn components(&self, manifest: &Manifest) -> Vec<(Stage, BTreeSet<ComponentId>)> {
+        let mut recorded: BTreeSet<ComponentId> = BTreeSet::new();
+        for (component, uses) in manifest.dependencies.iter() {
+            recorded.insert(component.clone());
+            recorded.extend(uses.iter().cloned());
+        }
+
+        Stage::ALL
+            .iter()
+            .map(|stage| {
+                let mut components: BTreeSet<ComponentId> =
+                    list_components(*stage).into_iter().collect();
+                components.extend(
+                    recorded
+                        .iter()
+                        .filter(|component| component.stage == *stage)
+                        .cloned(),
+                );
+
+                (*stage, components)
+            })
+            .filter(|(_, components)| !components.is_empty())
+            .collect()
+    }
+
+    /// Writes the lineage in the requested format
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest holding the dependencies
+    pub fn render(&self, manifest: &Manifest) -> String {
+        let components = self.components(manifest);
+        let edges: Vec<(&ComponentId, &ComponentId)> = manifest
+            .dependencies
+            .iter()
+            .flat_map(|(component, uses)| uses.iter().map(move |used| (used, component)))
+            .collect();
+
+        match self.format {
+            GraphFormat::Dot => self._render_dot(&components, &edges),
+            GraphFormat::Mermaid => self._render_mermaid(&components, &edges),
+        }
+    }
+
+    /// Writes the lineage as a Graphviz digraph with a cluster for each stage
+    fn _render_dot(
+        &self,
+        components: &[(Stage, BTreeSet<ComponentId>)],
+        edges: &[(&ComponentId, &ComponentId)],
+    ) -> String {
+        let mut lines = vec![
+            String::from("digraph lineage {"),
+            String::from("    rankdir=LR;"),
+        ];
+
+        for (stage, stage_components) in components.iter() {
+            lines.push(format!("    subgraph cluster_{} {{", stage.folder()));
+            lines.push(format!(
+                "        label=\"{}\";",
+                stage.folder().replace('_', " ")
+            ));
+            for component in stage_components.iter() {
+                lines.push(format!(
+                    "        \"{}\" [label=\"{}\"];",
+                    _dot_escape(&component.to_string()),
+                    _dot_escape(&component.name)
+                ));
+            }
+            lines.push(String::from("    }"));
+        }
+
+        for (from, to) in edges.iter() {
+            lines.push(format!(
+                "    \"{}\" -> \"{}\";",
+                _dot_escape(&from.to_string()),
+                _dot_escape(&to.to_string())
+            ));
+        }
+
+        lines.push(String::from("}"));
+        lines.join("\n")
+    }
+
+    /// Writes the lineage as a Mermaid flowchart with a subgraph for each stage
+    fn _render_mermaid(
+        &self,
+        components: &[(Stage, BTreeSet<ComponentId>)],
+        edges: &[(&ComponentId, &ComponentId)],
+    ) -> String {
+        // Numbered ids can't collide the way ids made from names can, e.g
+        // a-b and a_b
+        let node_ids: BTreeMap<&ComponentId, String> = components
+            .iter()
+            .flat_map(|(_, stage_components)| stage_components.iter())
+            .enumerate()
+            .map(|(index, component)| (component, format!("n{}", index)))
+            .collect();
+        let node_id = |component: &ComponentId| node_ids[component].clone();
+
+        let mut lines = vec![String::from("flowchart LR")];
+
+        for (stage, stage_components) in components.iter() {
+            lines.push(format!(
+                "    subgraph {} [{}]",
+                stage.folder(),
+                stage.folder().replace('_', " ")
+            ));
+            for component in stage_components.iter() {
+                lines.push(format!(
+                    "        {}[\"{}\"]",
+                    node_id(component),
+                    component.name.replace('"', "#quot;")
+                ));
+            }
+            lines.push(String::from("    end"));
+        }
+
+        for (from, to) in edges.iter() {
+            lines.push(format!("    {} --> {}", node_id(from), node_id(to)));
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,387 @@
+use std::fs;
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+
+fn id(value: &str) -> ComponentId {
+    value.parse().unwrap()
+}
+
+fn setup_components() {
+    fs::create_dir_all("data_sources/iris").unwrap();
+    fs::create_dir_all("explorations/eda").unwrap();
+    fs::create_dir_all("models/classifier").unwrap();
+}
+
+fn linked_manifest() -> Manifest {
+    let mut manifest = Manifest::default();
+    manifest.add_dependencies(&id("explorations/eda"), &[id("data_sources/iris")]);
+    manifest.add_dependencies(&id("models/classifier"), &[id("explorations/eda")]);
+
+    manifest
+}
+
+// +++++++++++ //
+// upstream_of //
+// +++++++++++ //
+#[test]
+fn upstream_includes_indirect_dependencies() {
+    assert_eq!(
+        upstream_of(&linked_manifest(), &id("models/classifier")),
+        BTreeSet::from([id("data_sources/iris"), id("explorations/eda")])
+    );
+}
+
+#[test]
+fn upstream_of_data_source_is_empty() {
+    assert!(upstream_of(&linked_manifest(), &id("data_sources/iris")).is_empty());
+}
+
+// ++++ //
+// link //
+// ++++ //
+#[test]
+fn link_records_dependencies() {
+    test_in_tmp_dir(
+        || {
+            setup_components();
+
+            let mut manifest = Manifest::default();
+            let (component, uses) = Link {
+                component: String::from("classifier"),
+                uses: vec![String::from("eda"), String::from("data_sources/iris")],
+            }
+            .link(&mut manifest);
+
+            assert_eq!(component, id("models/classifier"));
+            assert_eq!(uses, vec![id("explorations/eda"), id("data_sources/iris")]);
+            assert_eq!(
+                manifest.dependencies[&component],
+                BTreeSet::from([id("data_sources/iris"), id("explorations/eda")])
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "Linking data_sources/iris to models/classifier creates a cycle")]
+fn link_rejects_cycles() {
+    test_in_tmp_dir(
+        || {
+            setup_components();
+
+            Link {
+                component: String::from("iris"),
+                uses: vec![String::from("classifier")],
+            }
+            .link(&mut linked_manifest());
+        },
+        true,
+    )
+}
+
+#[test]
+#[should_panic(expected = "Linking explorations/eda to explorations/eda creates a cycle")]
+fn link_rejects_self() {
+    test_in_tmp_dir(
+        || {
+            setup_components();
+
+            Link {
+                component: String::from("eda"),
+                uses: vec![String::from("eda")],
+            }
+            .link(&mut Manifest::default());
+        },
+        true,
+    )
+}
+
+#[test]
+#[should_panic(expected = "penguins doesn't exist")]
+fn link_rejects_unknown_component() {
+    test_in_tmp_dir(
+        || {
+            setup_components();
+
+            Link {
+                component: String::from("eda"),
+                uses: vec![String::from("penguins")],
+            }
+            .link(&mut Manifest::default());
+        },
+        true,
+    )
+}
+
+// +++++ //
+// graph //
+// +++++ //
+#[test]
+fn renders_dot() {
+    test_in_tmp_dir(
+        || {
+            setup_components();
+
+            let graph = Graph {
+                format: GraphFormat::Dot,
+            };
+
+            assert_eq!(
+                graph.render(&linked_manifest()),
+                "digraph lineage {\n    \
+                rankdir=LR;\n    \
+                subgraph cluster_data_sources {\n        \
+                label=\"data sources\";\n        \
+                \"data_sources/iris\" [label=\"iris\"];\n    \
+                }\n    \
+                subgraph cluster_explorations {\n        \
+                label=\"explorations\";\n        \
+                \"explorations/eda\" [label=\"eda\"];\n    \
+                }\n    \
+                subgraph cluster_models {\n        \
+                label=\"models\";\n        \
+                \"models/classifier\" [label=\"classifier\"];\n    \
+                }\n    \
+                \"data_sources/iris\" -> \"explorations/eda\";\n    \
+                \"explorations/eda\" -> \"models/classifier\";\n\
+                }"
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn renders_mermaid() {
+    test_in_tmp_dir(
+        || {
+            setup_components();
+
+            let graph = Graph {
+                format: GraphFormat::Mermaid,
+            };
+
+            assert_eq!(
+                graph.render(&linked_manifest()),
+                "flowchart LR\n    \
+                subgraph data_sources [data sources]\n        \
+                n0[\"iris\"]\n    \
+                end\n    \
+                subgraph explorations [explorations]\n        \
+                n1[\"eda\"]\n    \
+                end\n    \
+                subgraph models [models]\n        \
+                n2[\"classifier\"]\n    \
+                end\n    \
+                n0 --> n1\n    \
+                n1 --> n2"
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn mermaid_ids_dont_collide() {
+    test_in_tmp_dir(
+        || {
+            let mut manifest = Manifest::default();
+            manifest.add_dependencies(&id("models/a_b"), &[id("explorations/a-b")]);
+            manifest.add_dependencies(&id("models/a-b"), &[id("explorations/a_b")]);
+
+            let graph = Graph {
+                format: GraphFormat::Mermaid,
+            };
+
+            assert!(graph.render(&manifest).ends_with(
+                "n1 --> n2\n    \
+                n0 --> n3"
+            ));
+        },
+        false,
+    )
+}
+
+#[test]
+fn dot_escapes_ids_and_labels() {
+    assert_eq!(_dot_escape("say \"hi\" \\o/"), "say \\\"hi\\\" \\\\o/");
+}
+
+#[test]
+fn graph_includes_components_only_in_manifest() {
+    test_in_tmp_dir(
+        || {
+            let components = Graph {
+                format: GraphFormat::Dot,
+            }
+            .components(&linked_manifest());
+
+            assert_eq!(components.len(), 3);
+            assert_eq!(components[2].0, Stage::Model);
+        },
+        false,
+    )
+}
+
+// +++++++++++++ //
+// downstream_of //
+// +++++++++++++ //
+#[test]
+fn downstream_includes_indirect_users() {
+    assert_eq!(
+        downstream_of(&linked_manifest(), &id("data_sources/iris")),
+        BTreeSet::from([id("explorations/eda"), id("models/classifier")])
+    );
+}
+
+#[test]
+fn used_by_only_includes_direct_users() {
+    assert_eq!(
+        used_by(&linked_manifest(), &id("data_sources/iris")),
+        BTreeSet::from([id("explorations/eda")])
+    );
+}
+
+// ++++++ //
+// impact //
+// ++++++ //
+#[test]
+fn impact_lists_affected_components_by_stage() {
+    test_in_tmp_dir(
+        || {
+            setup_components();
+            fs::create_dir_all("products/dashboard").unwrap();
+
+            let mut manifest = linked_manifest();
+            manifest.add_dependencies(&id("products/dashboard"), &[id("models/classifier")]);
+            manifest.add_dependencies(&id("models/classifier"), &[id("data_sources/iris")]);
+
+            let (component, affected) = Impact {
+                component: String::from("iris"),
+                mark_review: false,
+            }
+            .affected(&manifest);
+
+            assert_eq!(component, id("data_sources/iris"));
+            assert_eq!(
+                affected,
+                vec![
+                    id("explorations/eda"),
+                    id("models/classifier"),
+                    id("products/dashboard"),
+                ]
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn impact_finds_deleted_component_in_manifest() {
+    test_in_tmp_dir(
+        || {
+            let (component, affected) = Impact {
+                component: String::from("iris"),
+                mark_review: false,
+            }
+            .affected(&linked_manifest());
+
+            assert_eq!(component, id("data_sources/iris"));
+            assert_eq!(
+                affected,
+                vec![id("explorations/eda"), id("models/classifier")]
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn impact_prefers_live_component_to_deleted_one() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("explorations/churn").unwrap();
+
+            let mut manifest = Manifest::default();
+            manifest.add_dependencies(&id("models/churn"), &[id("data_sources/iris")]);
+
+            let (component, affected) = Impact {
+                component: String::from("churn"),
+                mark_review: false,
+            }
+            .affected(&manifest);
+
+            assert_eq!(component, id("explorations/churn"));
+            assert!(affected.is_empty());
+        },
+        false,
+    )
+}
+
+#[test]
+fn impact_marks_components_for_review() {
+    test_in_tmp_dir(
+        || {
+            setup_components();
+            fs::write(
+                "explorations/eda/README.md",
+                "---\nname: eda\nstage: exploration\ncreated: 2022-04-01\n---\n\n# eda\n",
+            )
+            .unwrap();
+            fs::write("models/classifier/README.md", "# classifier\n").unwrap();
+
+            let marked = Impact {
+                component: String::from("iris"),
+                mark_review: true,
+            }
+            .mark_for_review(
+                &id("data_sources/iris"),
+                &[id("explorations/eda"), id("models/classifier")],
+            );
+
+            assert_eq!(marked, vec![id("explorations/eda")]);
+
+            let readme = fs::read_to_string("explorations/eda/README.md").unwrap();
+            assert!(readme.contains(&format!(
+                "needs_review: data_sources/iris changed on {}\n",
+                front_matter::today()
+            )));
+            assert_eq!(
+                fs::read_to_string("models/classifier/README.md").unwrap(),
+                "# classifier\n"
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn impact_tree_follows_dependencies() {
+    test_in_tmp_dir(
+        || {
+            setup_components();
+
+            let impact = Impact {
+                component: String::from("iris"),
+                mark_review: false,
+            };
+            let tree = impact.create_tree(&linked_manifest(), &id("data_sources/iris"));
+
+            assert_eq!(
+                console::strip_ansi_codes(&tree.text),
+                "\u{1F5BF} data_sources/iris"
+            );
+            assert_eq!(
+                console::strip_ansi_codes(&tree.children[0].text),
+                "\u{1F5CE} explorations/eda"
+            );
+            assert_eq!(
+                console::strip_ansi_codes(&tree.children[0].children[0].text),
+                "\u{1F5CE} models/classifier"
+            );
+        },
+        false,
+    )
+}
@@ -0,0 +1,81 @@
+use clap::Args;
+use console::Style;
+use ptree::{item, TreeBuilder};
+
+use crate::component::{list_archived, list_components, Stage, ARCHIVE_FOLDER};
+use crate::manifest::Manifest;
+
+#[derive(Args)]
+pub struct List {
+    /// Only list the components of this stage
+    #[clap(value_enum)]
+    pub stage: Option<Stage>,
+    /// Also list archived components
+    #[clap(short, long)]
+    pub archived: bool,
+}
+
+impl List {
+    /// The stages to list
+    pub fn stages(&self) -> Vec<Stage> {
+        match self.stage {
+            Some(stage) => vec![stage],
+            None => Stage::ALL.to_vec(),
+        }
+    }
+
+    /// Creates a tree of the components in each stage
+    ///
+    /// Archived components are shown in an `_archive` folder with the date
+    /// and reason they were archived when `--archived` is passed.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest holding the archive details
+    pub fn create_tree(&self, manifest: &Manifest) -> item::StringItem {
+        let gold = Style::new().color256(220);
+        let grey = Style::new().color256(245);
+
+        let mut tree = TreeBuilder::new(format!("{}", Style::new().bold().apply_to("components")));
+
+        for stage in self.stages() {
+            tree.begin_child(format!("{} {}", gold.apply_to("\u{1F5BF}"), stage.folder()));
+
+            for component in list_components(stage) {
+                tree.add_empty_child(format!("{} {}", gold.apply_to("\u{1F5BF}"), component.name));
+            }
+
+            let archived = list_archived(stage);
+            if self.archived && !archived.is_empty() {
+                tree.begin_child(format!("{} {}", gold.apply_to("\u{1F5BF}"), ARCHIVE_FOLDER));
+
+                for component in archived {
+                    let details = manifest
+                        .archived
+                        .get(&component)
+                        .map(|entry| match &entry.reason {
+                            Some(reason) => format!(" ({}: {})", entry.archived, reason),
+                            None => format!(" ({})", entry.archived),
+                        })
+                        .unwrap_or_default();
+
+                    tree.add_empty_child(format!(
+                        "{} {}{}",
+                        gold.apply_to("\u{1F5BF}"),
+                        component.name,
+                        grey.apply_to(details)
+                    ));
+                }
+
+                tree.end_child();
+            }
+
+            tree.end_child();
+        }
+
+        tree.build()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,83 @@
+use std::fs;
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+use crate::component::ComponentId;
+use crate::manifest::ArchiveEntry;
+
+fn setup_components() -> Manifest {
+    fs::create_dir_all("data_sources/iris").unwrap();
+    fs::create_dir_all("data_sources/_archive/penguins").unwrap();
+    fs::create_dir_all("models/classifier").unwrap();
+
+    let mut manifest = Manifest::default();
+    manifest.archived.insert(
+        ComponentId::new(Stage::DataSource, "penguins"),
+        ArchiveEntry {
+            archived: String::from("2022-04-01"),
+            reason: Some(String::from("Agreement ended")),
+        },
+    );
+
+    manifest
+}
+
+fn texts(tree: &item::StringItem) -> Vec<String> {
+    tree.children
+        .iter()
+        .map(|child| console::strip_ansi_codes(&child.text).to_string())
+        .collect()
+}
+
+#[test]
+fn lists_every_stage_without_archived() {
+    test_in_tmp_dir(
+        || {
+            let manifest = setup_components();
+
+            let tree = List {
+                stage: None,
+                archived: false,
+            }
+            .create_tree(&manifest);
+
+            assert_eq!(
+                texts(&tree),
+                vec![
+                    "\u{1F5BF} data_sources",
+                    "\u{1F5BF} explorations",
+                    "\u{1F5BF} models",
+                    "\u{1F5BF} products",
+                ]
+            );
+            assert_eq!(texts(&tree.children[0]), vec!["\u{1F5BF} iris"]);
+        },
+        false,
+    )
+}
+
+#[test]
+fn lists_archived_with_reason() {
+    test_in_tmp_dir(
+        || {
+            let manifest = setup_components();
+
+            let tree = List {
+                stage: Some(Stage::DataSource),
+                archived: true,
+            }
+            .create_tree(&manifest);
+
+            assert_eq!(texts(&tree), vec!["\u{1F5BF} data_sources"]);
+            assert_eq!(
+                texts(&tree.children[0]),
+                vec!["\u{1F5BF} iris", "\u{1F5BF} _archive"]
+            );
+            assert_eq!(
+                texts(&tree.children[0].children[1]),
+                vec!["\u{1F5BF} penguins (2022-04-01: Agreement ended)"]
+            );
+        },
+        false,
+    )
+}
@@ -0,0 +1,319 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, path,
+};
+
+use crate::component::{ComponentId, Stage};
+use crate::naming::NamingConfig;
+
+/// The name of the file geoff uses to record details about the project
+pub const MANIFEST_FILE: &str = "geoff.toml";
+
+/// The project manifest
+///
+/// The manifest lives in the root of the project and records the details geoff
+/// needs to remember between commands. Projects that don't have a manifest yet
+/// are treated as having an empty one.
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+pub struct Manifest {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub data_sources: BTreeMap<String, DataSourceEntry>,
+    /// The components each component uses, keyed by the downstream component
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<ComponentId, BTreeSet<ComponentId>>,
+    /// The components that have been archived
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub archived: BTreeMap<ComponentId, ArchiveEntry>,
+    /// The components that have been removed but are still in the trash
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trash: Vec<TrashEntry>,
+    /// The templates documents were generated from, keyed by the document's
+    /// path relative to the project root
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub templates: BTreeMap<String, TemplateRecord>,
+    /// How the project's components must be named
+    #[serde(default, skip_serializing_if = "NamingConfig::is_default")]
+    pub naming: NamingConfig,
+}
+
+/// The template a document was generated from
+///
+/// The template's text is kept in the project so it can be compared with
+/// newer versions of the template.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TemplateRecord {
+    /// The template's path within geoff's templates, e.g explorations/README.md
+    pub template: String,
+    /// The SHA-256 hash of the template's text
+    pub version: String,
+    /// The name that replaced the template's placeholder
+    pub name: String,
+}
+
+/// A component that has been moved to the trash
+///
+/// Everything the manifest recorded about the component is kept so it can
+/// be put back when the component is restored.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct TrashEntry {
+    /// The component that was removed
+    pub component: ComponentId,
+    /// Where the component's folder was moved to, relative to the project root
+    pub path: String,
+    /// The date the component was removed, formatted as YYYY-MM-DD
+    pub removed: String,
+    /// The components it used
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub uses: BTreeSet<ComponentId>,
+    /// The components that used it
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub used_by: BTreeSet<ComponentId>,
+    /// The versions, checksums and downloads recorded for a data source
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_source: Option<DataSourceEntry>,
+    /// Why and when the component was archived, if it was removed from the
+    /// archive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived: Option<ArchiveEntry>,
+    /// The templates of the component's documents, keyed by the document's
+    /// path relative to the project root
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub templates: BTreeMap<String, TemplateRecord>,
+}
+
+/// Why and when a component was archived
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ArchiveEntry {
+    /// The date the component was archived, formatted as YYYY-MM-DD
+    pub archived: String,
+    /// Why the component was archived
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Everything the manifest records about a single data source
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+pub struct DataSourceEntry {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub versions: Vec<ExtractVersion>,
+    /// SHA-256 hashes of the data files, keyed by their path within the data
+    /// source folder
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub checksums: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub downloads: Vec<Download>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub purges: Vec<Purge>,
+}
+
+/// A version of an extract that has been received
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExtractVersion {
+    /// The path of the file relative to the data source folder
+    pub file: String,
+    /// The date the extract was received, formatted as YYYY-MM-DD
+    pub received: String,
+    /// The SHA-256 hash of the file contents
+    pub sha256: String,
+    /// The size of the file in bytes
+    pub size: u64,
+    /// The number of data rows in the file
+    pub rows: u64,
+}
+
+/// A file that has been downloaded into a data source
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Download {
+    /// The path of the file relative to the data source folder
+    pub file: String,
+    /// The URL the file was downloaded from
+    pub url: String,
+    /// When the file was downloaded, formatted as an RFC 3339 timestamp
+    pub downloaded: String,
+    /// The ETag header returned by the server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// The Last-Modified header returned by the server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    /// The size of the file in bytes
+    pub size: u64,
+    /// The SHA-256 hash of the file contents
+    pub sha256: String,
+}
+
+/// A record of data files deleted because their retention period ended
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Purge {
+    /// The date the files were deleted, formatted as YYYY-MM-DD
+    pub purged: String,
+    /// The date the data source expired, formatted as YYYY-MM-DD
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expired: Option<String>,
+    /// The paths of the deleted files relative to the data source folder
+    pub files: Vec<String>,
+}
+
+impl Manifest {
+    /// Reads the manifest from the root of the current project
+    ///
+    /// # Return value
+    ///
+    /// The parsed manifest, or an empty manifest if the project doesn't have
+    /// one yet
+    pub fn load() -> Manifest {
+        Manifest::load_from(path::Path::new(MANIFEST_FILE))
+    }
+
+    /// Reads a manifest from the given path
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest_path` - The location of the manifest file
+    pub fn load_from(manifest_path: &path::Path) -> Manifest {
+        if !manifest_path.exists() {
+            return Manifest::default();
+        }
+
+        let contents = fs::read_to_string(manifest_path)
+            .unwrap_or_else(|_| panic!("Unable to read {}", manifest_path.display()));
+
+        toml::from_str(&contents)
+            .unwrap_or_else(|err| panic!("Unable to parse {}\n{:?}", manifest_path.display(), err))
+    }
+
+    /// Writes the manifest to the root of the current project
+    pub fn save(&self) {
+        self.save_to(path::Path::new(MANIFEST_FILE))
+    }
+
+    /// Writes the manifest to the given path
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest_path` - The location to write the manifest to
+    pub fn save_to(&self, manifest_path: &path::Path) {
+        let contents = toml::to_string_pretty(self).expect("Unable to serialise the manifest");
+
+        fs::write(manifest_path, contents)
+            .unwrap_or_else(|_| panic!("Unable to write {}", manifest_path.display()));
+    }
+
+    /// Gets the entry for a data source, adding an empty one if it's missing
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the data source
+    pub fn data_source_mut(&mut self, name: &str) -> &mut DataSourceEntry {
+        self.data_sources.entry(name.to_string()).or_default()
+    }
+
+    /// Replaces a component's name everywhere the manifest refers to it
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - The component's current id
+    /// * `new` - The component's new id
+    pub fn rename_component(&mut self, old: &ComponentId, new: &ComponentId) {
+        if let Some(uses) = self.dependencies.remove(old) {
+            self.dependencies.insert(new.clone(), uses);
+        }
+        for uses in self.dependencies.values_mut() {
+            if uses.remove(old) {
+                uses.insert(new.clone());
+            }
+        }
+
+        for entry in self.trash.iter_mut() {
+            for components in [&mut entry.uses, &mut entry.used_by] {
+                if components.remove(old) {
+                    components.insert(new.clone());
+                }
+            }
+        }
+
+        if let Some(entry) = self.archived.remove(old) {
+            self.archived.insert(new.clone(), entry);
+        }
+
+        if old.stage == Stage::DataSource {
+            if let Some(entry) = self.data_sources.remove(&old.name) {
+                self.data_sources.insert(new.name.clone(), entry);
+            }
+        }
+
+        self.move_templates(&old.path(), &new.path());
+        let new_prefix = format!("{}/", new);
+        for (_, record) in self
+            .templates
+            .iter_mut()
+            .filter(|(document, _)| document.starts_with(&new_prefix))
+        {
+            // Titles are left alone, just like the document's heading
+            if record.name == old.name {
+                record.name = new.name.clone();
+            }
+        }
+    }
+
+    /// Moves the template records of the documents in a folder to another
+    /// folder, e.g when a component is archived
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The folder the documents were in
+    /// * `to` - The folder the documents are in now
+    pub fn move_templates(&mut self, from: &path::Path, to: &path::Path) {
+        let key = |folder: &path::Path| format!("{}/", folder.to_string_lossy().replace('\\', "/"));
+        let (from, to) = (key(from), key(to));
+
+        let moved: Vec<String> = self
+            .templates
+            .keys()
+            .filter(|document| document.starts_with(&from))
+            .cloned()
+            .collect();
+        for document in moved {
+            let record = self.templates.remove(&document).expect("The key exists");
+            self.templates
+                .insert(format!("{}{}", to, &document[from.len()..]), record);
+        }
+    }
+
+    /// Replaces the project's name in the records of the root documents
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - The project's current name
+    /// * `new` - The project's new name
+    pub fn rename_project(&mut self, old: &str, new: &str) {
+        for record in self
+            .templates
+            .values_mut()
+            .filter(|record| record.template.starts_with("root/") && record.name == old)
+        {
+            record.name = new.to_string();
+        }
+    }
+
+    /// Records that a component uses other components
+    ///
+    /// # Arguments
+    ///
+    /// * `component` - The downstream component
+    /// * `uses` - The components it uses
+    pub fn add_dependencies(&mut self, component: &ComponentId, uses: &[ComponentId]) {
+        if uses.is_empty() {
+            return;
+        }
+
+        self.dependencies
+            .entry(component.clone())
+            .or_default()
+            .extend(uses.iter().cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,249 @@
+use std::{fs, path};
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+
+fn example_version() -> ExtractVersion {
+    ExtractVersion {
+        file: String::from("versions/2022-04-01/iris.csv"),
+        received: String::from("2022-04-01"),
+        sha256: String::from("abc123"),
+        size: 10,
+        rows: 2,
+    }
+}
+
+// ++++ //
+// load //
+// ++++ //
+#[test]
+fn missing_manifest_is_empty() {
+    test_in_tmp_dir(
+        || {
+            assert_eq!(Manifest::load(), Manifest::default());
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "Unable to parse geoff.toml")]
+fn invalid_manifest_errors() {
+    test_in_tmp_dir(
+        || {
+            fs::write(MANIFEST_FILE, "data_sources = [").unwrap();
+
+            Manifest::load();
+        },
+        true,
+    )
+}
+
+// ++++ //
+// save //
+// ++++ //
+#[test]
+fn saved_manifest_round_trips() {
+    test_in_tmp_dir(
+        || {
+            let mut manifest = Manifest::default();
+            manifest
+                .data_source_mut("iris")
+                .versions
+                .push(example_version());
+
+            manifest.save();
+
+            assert!(path::Path::new(MANIFEST_FILE).exists());
+            assert_eq!(Manifest::load(), manifest);
+        },
+        false,
+    )
+}
+
+// +++++++++++++++ //
+// data_source_mut //
+// +++++++++++++++ //
+#[test]
+fn data_source_entry_added_when_missing() {
+    let mut manifest = Manifest::default();
+
+    manifest.data_source_mut("iris");
+
+    assert!(manifest.data_sources.contains_key("iris"));
+}
+
+#[test]
+fn data_source_entry_kept_when_present() {
+    let mut manifest = Manifest::default();
+    manifest
+        .data_source_mut("iris")
+        .versions
+        .push(example_version());
+
+    let entry = manifest.data_source_mut("iris");
+
+    assert_eq!(entry.versions, vec![example_version()]);
+}
+
+// ++++++++++++++++ //
+// add_dependencies //
+// ++++++++++++++++ //
+#[test]
+fn dependencies_round_trip() {
+    test_in_tmp_dir(
+        || {
+            let eda = "explorations/eda".parse::<ComponentId>().unwrap();
+            let iris = "data_sources/iris".parse::<ComponentId>().unwrap();
+
+            let mut manifest = Manifest::default();
+            manifest.add_dependencies(&eda, std::slice::from_ref(&iris));
+            manifest.add_dependencies(&eda, &[iris]);
+            manifest.save();
+
+            let contents = fs::read_to_string(MANIFEST_FILE).unwrap();
+            assert!(
+                contents.contains("[dependencies]\n\"explorations/eda\" = ['data_sources/iris']")
+            );
+            assert_eq!(Manifest::load(), manifest);
+        },
+        false,
+    )
+}
+
+#[test]
+fn no_dependencies_recorded_when_nothing_used() {
+    let mut manifest = Manifest::default();
+    manifest.add_dependencies(&"explorations/eda".parse().unwrap(), &[]);
+
+    assert!(manifest.dependencies.is_empty());
+}
+
+// ++++++++++++++++ //
+// rename_component //
+// ++++++++++++++++ //
+#[test]
+fn rename_updates_every_reference() {
+    let iris = "data_sources/iris".parse::<ComponentId>().unwrap();
+    let flowers = "data_sources/flowers".parse::<ComponentId>().unwrap();
+    let eda = "explorations/eda".parse::<ComponentId>().unwrap();
+
+    let mut manifest = Manifest::default();
+    manifest
+        .data_source_mut("iris")
+        .versions
+        .push(example_version());
+    manifest.add_dependencies(&eda, std::slice::from_ref(&iris));
+    manifest.templates.insert(
+        String::from("data_sources/iris/metadata.md"),
+        TemplateRecord {
+            template: String::from("data_sources/default_metadata.md"),
+            version: String::from("abc123"),
+            name: String::from("iris"),
+        },
+    );
+
+    manifest.rename_component(&iris, &flowers);
+
+    assert!(manifest.data_sources.contains_key("flowers"));
+    assert!(!manifest.data_sources.contains_key("iris"));
+    assert_eq!(manifest.dependencies[&eda], BTreeSet::from([flowers]));
+    assert_eq!(
+        manifest.templates.keys().collect::<Vec<_>>(),
+        vec!["data_sources/flowers/metadata.md"]
+    );
+    assert_eq!(
+        manifest.templates["data_sources/flowers/metadata.md"].name,
+        "flowers"
+    );
+}
+
+#[test]
+fn rename_updates_trash_entries() {
+    let iris = "data_sources/iris".parse::<ComponentId>().unwrap();
+    let flowers = "data_sources/flowers".parse::<ComponentId>().unwrap();
+    let eda = "explorations/eda".parse::<ComponentId>().unwrap();
+    let classifier = "models/classifier".parse::<ComponentId>().unwrap();
+
+    let mut manifest = Manifest::default();
+    manifest.trash.push(TrashEntry {
+        component: eda.clone(),
+        path: String::from(".trash/explorations/eda"),
+        removed: String::from("2022-04-01"),
+        uses: BTreeSet::from([iris.clone()]),
+        used_by: BTreeSet::from([classifier.clone()]),
+        data_source: None,
+        archived: None,
+        templates: BTreeMap::new(),
+    });
+    manifest.trash.push(TrashEntry {
+        component: classifier.clone(),
+        path: String::from(".trash/models/classifier"),
+        removed: String::from("2022-04-01"),
+        uses: BTreeSet::from([eda.clone()]),
+        used_by: BTreeSet::new(),
+        data_source: None,
+        archived: None,
+        templates: BTreeMap::new(),
+    });
+
+    manifest.rename_component(&iris, &flowers);
+    manifest.rename_component(&classifier, &"models/churn".parse().unwrap());
+
+    assert_eq!(manifest.trash[0].uses, BTreeSet::from([flowers]));
+    assert_eq!(
+        manifest.trash[0].used_by,
+        BTreeSet::from(["models/churn".parse().unwrap()])
+    );
+    assert_eq!(manifest.trash[1].uses, BTreeSet::from([eda]));
+}
+
+#[test]
+fn rename_keeps_data_source_sharing_a_name() {
+    let iris = "explorations/iris".parse::<ComponentId>().unwrap();
+    let flowers = "explorations/flowers".parse::<ComponentId>().unwrap();
+
+    let mut manifest = Manifest::default();
+    manifest
+        .data_source_mut("iris")
+        .versions
+        .push(example_version());
+
+    manifest.rename_component(&iris, &flowers);
+
+    assert!(manifest.data_sources.contains_key("iris"));
+    assert!(!manifest.data_sources.contains_key("flowers"));
+}
+
+// ++++++++++++++ //
+// rename_project //
+// ++++++++++++++ //
+#[test]
+fn rename_project_updates_root_records() {
+    let mut manifest = Manifest::default();
+    for (document, template, name) in [
+        ("README.md", "root/README.md", "iris"),
+        (
+            "explorations/iris/README.md",
+            "explorations/README.md",
+            "iris",
+        ),
+    ] {
+        manifest.templates.insert(
+            String::from(document),
+            TemplateRecord {
+                template: String::from(template),
+                version: String::from("abc123"),
+                name: String::from(name),
+            },
+        );
+    }
+
+    manifest.rename_project("iris", "flowers");
+
+    assert_eq!(manifest.templates["README.md"].name, "flowers");
+    assert_eq!(
+        manifest.templates["explorations/iris/README.md"].name,
+        "iris"
+    );
+}
@@ -0,0 +1,422 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::front_matter::{self, DataSourceFrontMatter};
+
+/// A markdown table read from a document
+#[derive(Debug, Default, PartialEq)]
+pub struct Table {
+    /// The column names, lowercased and trimmed
+    pub headers: Vec<String>,
+    /// The cells of each row, excluding the header and alignment rows
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Pairs the cells of each row with their column names
+    ///
+    /// Rows with fewer cells than there are columns are padded with empty
+    /// cells and any cells beyond the last column are dropped.
+    pub fn records(&self) -> Vec<Record> {
+        self.rows
+            .iter()
+            .map(|row| {
+                Record(
+                    self.headers
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, header)| {
+                            (header.clone(), row.get(idx).cloned().unwrap_or_default())
+                        })
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// A row of a table keyed by column name
+#[derive(Debug, Default, PartialEq)]
+pub struct Record(pub BTreeMap<String, String>);
+
+impl Record {
+    /// Gets the first of the given columns that the record has
+    ///
+    /// Several names can be given to allow for columns that have been named
+    /// differently between templates.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The names the column might have
+    pub fn get(&self, columns: &[&str]) -> String {
+        columns
+            .iter()
+            .find_map(|column| self.0.get(*column))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Creates a typed record from a table row
+pub trait FromRecord {
+    fn from_record(record: &Record) -> Self;
+}
+
+/// A row of the data dictionary table
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct DataDictionaryEntry {
+    pub field: String,
+    pub data_type: String,
+    pub description: String,
+    /// Whether the field holds personally identifiable information
+    pub pii: bool,
+}
+
+impl FromRecord for DataDictionaryEntry {
+    fn from_record(record: &Record) -> Self {
+        DataDictionaryEntry {
+            field: record.get(&["data field", "field"]),
+            data_type: record.get(&["data type", "type"]),
+            description: record.get(&["description"]),
+            pii: matches!(
+                record.get(&["pii"]).to_lowercase().as_str(),
+                "yes" | "y" | "true" | "x"
+            ),
+        }
+    }
+}
+
+/// A row of the key people table
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct KeyPerson {
+    pub name: String,
+    pub details: String,
+}
+
+impl FromRecord for KeyPerson {
+    fn from_record(record: &Record) -> Self {
+        KeyPerson {
+            name: record.get(&["name"]),
+            details: record.get(&["details"]),
+        }
+    }
+}
+
+/// A row of the database details table
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct DatabaseDetails {
+    pub databases: String,
+    pub tables_accessed: String,
+    pub tables_created: String,
+}
+
+impl FromRecord for DatabaseDetails {
+    fn from_record(record: &Record) -> Self {
+        DatabaseDetails {
+            databases: record.get(&["database name(s)", "database name", "database"]),
+            tables_accessed: record.get(&["tables accessed"]),
+            tables_created: record.get(&["tables_created", "tables created"]),
+        }
+    }
+}
+
+/// A row of the extract details table
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct ExtractDetails {
+    pub file: String,
+    pub query: String,
+    pub extractor: String,
+    pub date_received: String,
+}
+
+impl FromRecord for ExtractDetails {
+    fn from_record(record: &Record) -> Self {
+        ExtractDetails {
+            file: record.get(&["extract file"]),
+            query: record.get(&["extract query"]),
+            extractor: record.get(&["extractor"]),
+            date_received: record.get(&["date received"]),
+        }
+    }
+}
+
+/// A row of the website details table
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct WebsiteDetails {
+    pub file: String,
+    pub link: String,
+    pub downloaded_on: String,
+    pub additional_details: String,
+}
+
+impl FromRecord for WebsiteDetails {
+    fn from_record(record: &Record) -> Self {
+        WebsiteDetails {
+            file: record.get(&["download file"]),
+            link: record.get(&["download link", "donwload link"]),
+            downloaded_on: record.get(&["downloaded on", "donwloaded on"]),
+            additional_details: record.get(&["additional details"]),
+        }
+    }
+}
+
+/// A row of the API details table
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct ApiDetails {
+    pub endpoint: String,
+    pub auth_method: String,
+    pub rate_limits: String,
+    pub pagination: String,
+}
+
+impl FromRecord for ApiDetails {
+    fn from_record(record: &Record) -> Self {
+        ApiDetails {
+            endpoint: record.get(&["endpoint"]),
+            auth_method: record.get(&["auth method"]),
+            rate_limits: record.get(&["rate limits"]),
+            pagination: record.get(&["pagination"]),
+        }
+    }
+}
+
+/// A row of the survey details table
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct SurveyDetails {
+    pub instrument: String,
+    pub sample_frame: String,
+    pub response_rate: String,
+    pub consent: String,
+}
+
+impl FromRecord for SurveyDetails {
+    fn from_record(record: &Record) -> Self {
+        SurveyDetails {
+            instrument: record.get(&["instrument"]),
+            sample_frame: record.get(&["sample frame"]),
+            response_rate: record.get(&["response rate"]),
+            consent: record.get(&["consent"]),
+        }
+    }
+}
+
+/// A row of the synthetic data details table
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct SyntheticDetails {
+    pub generator: String,
+    pub seed: String,
+    pub source_distribution: String,
+}
+
+impl FromRecord for SyntheticDetails {
+    fn from_record(record: &Record) -> Self {
+        SyntheticDetails {
+            generator: record.get(&["generator"]),
+            seed: record.get(&["seed"]),
+            source_distribution: record.get(&["source distribution"]),
+        }
+    }
+}
+
+/// Everything that can be read back from a data source's metadata.md
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct DataSourceMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub front_matter: Option<DataSourceFrontMatter>,
+    pub title: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub database_details: Vec<DatabaseDetails>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extract_details: Vec<ExtractDetails>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub website_details: Vec<WebsiteDetails>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub api_details: Vec<ApiDetails>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub survey_details: Vec<SurveyDetails>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub synthetic_details: Vec<SyntheticDetails>,
+    pub data_dictionary: Vec<DataDictionaryEntry>,
+    pub key_people: Vec<KeyPerson>,
+}
+
+/// Splits a table line into its cells
+///
+/// Escaped pipes (`\|`) are kept within the cell.
+fn _split_cells(line: &str) -> Vec<String> {
+    let trimmed = line.trim();
+    let inner = trimmed.strip_prefix('|').unwrap_or(trimmed);
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = inner.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '|' => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+
+    cells
+}
+
+/// Whether a row is the alignment row under the header e.g "| :--- | ---: |"
+fn _is_alignment_row(cells: &[String]) -> bool {
+    cells.iter().all(|cell| {
+        let dashes = cell.trim_matches(':');
+        !dashes.is_empty() && dashes.chars().all(|c| c == '-')
+    })
+}
+
+/// Whether a row is an example row left over from the template
+fn _is_example_row(cells: &[String]) -> bool {
+    cells
+        .first()
+        .map(|cell| cell.starts_with("*e.g"))
+        .unwrap_or(false)
+}
+
+/// Reads the first table under a heading
+///
+/// Alignment rows and example rows left over from the template are skipped.
+///
+/// # Arguments
+///
+/// * `contents` - The contents of a markdown document
+/// * `heading` - The full heading line the table sits under e.g "## Key people"
+///
+/// # Return value
+///
+/// The table, or None if there is no table under the heading
+pub fn parse_table(contents: &str, heading: &str) -> Option<Table> {
+    let mut lines = contents
+        .lines()
+        .skip_while(|line| line.trim() != heading)
+        .skip(1)
+        .map(|line| line.trim())
+        .take_while(|line| !line.starts_with('#'))
+        .skip_while(|line| !line.starts_with('|'))
+        .take_while(|line| line.starts_with('|'));
+
+    let headers = _split_cells(lines.next()?)
+        .into_iter()
+        .map(|header| header.to_lowercase())
+        .collect();
+
+    let rows = lines
+        .map(_split_cells)
+        .filter(|cells| !_is_alignment_row(cells) && !_is_example_row(cells))
+        .collect();
+
+    Some(Table { headers, rows })
+}
+
+/// Reads the rows of the table under a heading into typed records
+///
+/// # Arguments
+///
+/// * `contents` - The contents of a markdown document
+/// * `heading` - The full heading line the table sits under
+pub fn parse_records<T: FromRecord>(contents: &str, heading: &str) -> Vec<T> {
+    parse_table(contents, heading)
+        .map(|table| table.records().iter().map(T::from_record).collect())
+        .unwrap_or_default()
+}
+
+/// Reads the front matter, title and tables of a data source's metadata.md
+///
+/// # Arguments
+///
+/// * `contents` - The contents of the metadata document
+pub fn parse_data_source(contents: &str) -> DataSourceMetadata {
+    let body = front_matter::split(contents)
+        .map(|(_, body)| body)
+        .unwrap_or(contents);
+
+    DataSourceMetadata {
+        front_matter: front_matter::parse(contents).ok(),
+        title: body
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("# "))
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+        database_details: parse_records(body, "## Database details"),
+        extract_details: parse_records(body, "## Extract details"),
+        website_details: parse_records(body, "## Website details"),
+        api_details: parse_records(body, "## API details"),
+        survey_details: parse_records(body, "## Survey details"),
+        synthetic_details: parse_records(body, "## Synthetic data details"),
+        data_dictionary: parse_records(body, "## Data dictionary"),
+        key_people: parse_records(body, "## Key people"),
+    }
+}
+
+/// Formats the cells of a markdown table row
+///
+/// # Arguments
+///
+/// * `cells` - The contents of each cell in the row
+///
+/// # Return value
+///
+/// A String containing the row e.g "| iris.csv | 2022-04-01 |"
+pub fn format_table_row(cells: &[&str]) -> String {
+    format!("| {} |", cells.join(" | "))
+}
+
+/// Appends a row to the end of the table under a heading
+///
+/// Finds the first table that follows `heading` and inserts `row` after the
+/// final row of that table. The line endings of the original contents are
+/// kept.
+///
+/// # Arguments
+///
+/// * `contents` - The contents of a markdown document
+/// * `heading` - The full heading line the table sits under e.g "## Key people"
+/// * `row` - The formatted row to add to the table
+///
+/// # Return value
+///
+/// The updated contents, or None if there is no table under the heading
+pub fn append_table_row(contents: &str, heading: &str, row: &str) -> Option<String> {
+    let newline = if contents.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut lines: Vec<&str> = contents.lines().collect();
+
+    let heading_idx = lines.iter().position(|line| line.trim() == heading)?;
+
+    let mut last_row_idx: Option<usize> = None;
+    for (idx, line) in lines.iter().enumerate().skip(heading_idx + 1) {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with('|') {
+            last_row_idx = Some(idx);
+        } else if last_row_idx.is_some() || trimmed.starts_with('#') {
+            break;
+        }
+    }
+
+    lines.insert(last_row_idx? + 1, row);
+
+    let mut updated = lines.join(newline);
+    if contents.ends_with('\n') {
+        updated.push_str(newline);
+    }
+
+    Some(updated)
+}
+
+#[cfg(test)]
+mod tests;
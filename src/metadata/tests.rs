@@ -0,0 +1,230 @@
+use super::*;
+
+// ++++++++++++++++ //
+// format_table_row //
+// ++++++++++++++++ //
+#[test]
+fn formats_row() {
+    assert_eq!(
+        format_table_row(&["iris.csv", "", "2022-04-01"]),
+        "| iris.csv |  | 2022-04-01 |"
+    );
+}
+
+// ++++++++++++++++ //
+// append_table_row //
+// ++++++++++++++++ //
+#[test]
+fn appends_row_to_table_under_heading() {
+    let contents = "# iris\n\
+        \n\
+        ## Extract details\n\
+        \n\
+        | extract file | date received |\n\
+        | :----------- | :------------ |\n\
+        | *e.g iris.csv* | *e.g 2022-04-01* |\n\
+        \n\
+        ## Key people\n\
+        \n\
+        | name | details |\n\
+        | :--- | :------ |\n";
+
+    let updated = append_table_row(contents, "## Extract details", "| iris.csv | 2022-05-01 |");
+
+    assert_eq!(
+        updated.unwrap(),
+        "# iris\n\
+        \n\
+        ## Extract details\n\
+        \n\
+        | extract file | date received |\n\
+        | :----------- | :------------ |\n\
+        | *e.g iris.csv* | *e.g 2022-04-01* |\n\
+        | iris.csv | 2022-05-01 |\n\
+        \n\
+        ## Key people\n\
+        \n\
+        | name | details |\n\
+        | :--- | :------ |\n"
+    );
+}
+
+#[test]
+fn appends_row_to_table_at_end_of_file() {
+    let contents = "## Key people\n\n| name | details |\n| :--- | :------ |";
+
+    let updated = append_table_row(contents, "## Key people", "| Ronald Fisher | SME |");
+
+    assert_eq!(
+        updated.unwrap(),
+        "## Key people\n\n| name | details |\n| :--- | :------ |\n| Ronald Fisher | SME |"
+    );
+}
+
+#[test]
+fn keeps_windows_line_endings() {
+    let contents = "## Key people\r\n\r\n| name |\r\n| :--- |\r\n";
+
+    let updated = append_table_row(contents, "## Key people", "| Ronald Fisher |");
+
+    assert_eq!(
+        updated.unwrap(),
+        "## Key people\r\n\r\n| name |\r\n| :--- |\r\n| Ronald Fisher |\r\n"
+    );
+}
+
+#[test]
+fn missing_heading_returns_none() {
+    let contents = "# iris\n";
+
+    assert_eq!(append_table_row(contents, "## Key people", "| a |"), None);
+}
+
+#[test]
+fn heading_without_table_returns_none() {
+    let contents = "## Key people\n\n## Data dictionary\n\n| field |\n| :---- |\n";
+
+    assert_eq!(append_table_row(contents, "## Key people", "| a |"), None);
+}
+
+// +++++++++++ //
+// parse_table //
+// +++++++++++ //
+#[test]
+fn parses_table_under_heading() {
+    let contents = "## Key people\n\
+        \n\
+        | Name | Details |\n\
+        | :--- | ------: |\n\
+        | Ronald Fisher | SME |\n\
+        \n\
+        ## Data dictionary\n";
+
+    assert_eq!(
+        parse_table(contents, "## Key people"),
+        Some(Table {
+            headers: vec![String::from("name"), String::from("details")],
+            rows: vec![vec![String::from("Ronald Fisher"), String::from("SME")]],
+        })
+    );
+}
+
+#[test]
+fn skips_template_example_rows() {
+    let contents = include_str!("../templates/data_sources/extract_metadata.md");
+
+    let table = parse_table(contents, "## Extract details").unwrap();
+
+    assert_eq!(table.headers.len(), 4);
+    assert!(table.rows.is_empty());
+}
+
+#[test]
+fn keeps_escaped_pipes_in_cells() {
+    let contents = "## Key people\n| name | details |\n| --- | --- |\n| Ron | a \\| b |\n";
+
+    let table = parse_table(contents, "## Key people").unwrap();
+
+    assert_eq!(table.rows[0][1], "a | b");
+}
+
+#[test]
+fn table_missing_returns_none() {
+    assert_eq!(
+        parse_table(
+            "## Key people\n\n## Data dictionary\n| a |\n",
+            "## Key people"
+        ),
+        None
+    );
+    assert_eq!(parse_table("# iris\n", "## Key people"), None);
+}
+
+// +++++++++++++ //
+// parse_records //
+// +++++++++++++ //
+#[test]
+fn records_tolerate_extra_and_missing_columns() {
+    let contents = "## Data dictionary\n\
+        | data field | data type | description | pii | notes |\n\
+        | :--------- | :-------- | :---------- | :-- | :---- |\n\
+        | id | INT | A unique identifier | yes | extra |\n\
+        | sepal_width | FLOAT |\n";
+
+    let entries: Vec<DataDictionaryEntry> = parse_records(contents, "## Data dictionary");
+
+    assert_eq!(
+        entries,
+        vec![
+            DataDictionaryEntry {
+                field: String::from("id"),
+                data_type: String::from("INT"),
+                description: String::from("A unique identifier"),
+                pii: true,
+            },
+            DataDictionaryEntry {
+                field: String::from("sepal_width"),
+                data_type: String::from("FLOAT"),
+                description: String::new(),
+                pii: false,
+            },
+        ]
+    );
+}
+
+#[test]
+fn records_read_misspelt_template_columns() {
+    let contents = "## Website details\n\
+        | download file | donwload link | donwloaded on | additional details |\n\
+        | :------------ | :------------ | :------------ | :----------------- |\n\
+        | iris.data | http://example.com/iris.data | 2022-04-01 | |\n";
+
+    let details: Vec<WebsiteDetails> = parse_records(contents, "## Website details");
+
+    assert_eq!(details[0].link, "http://example.com/iris.data");
+    assert_eq!(details[0].downloaded_on, "2022-04-01");
+}
+
+// +++++++++++++++++ //
+// parse_data_source //
+// +++++++++++++++++ //
+#[test]
+fn parses_data_source_metadata() {
+    let contents = "---\n\
+        name: iris\n\
+        kind: database\n\
+        created: 2022-04-01\n\
+        ---\n\
+        \n\
+        # iris\n\
+        \n\
+        ## Database details\n\
+        \n\
+        | database name(s) | tables accessed | tables_created |\n\
+        | :--------------- | :-------------- | :------------- |\n\
+        | datasets | iris | iris_transformed |\n\
+        \n\
+        ## Data dictionary\n\
+        \n\
+        | data field | data type | description |\n\
+        | :--------- | :-------- | :---------- |\n\
+        | id | INT | A unique identifier |\n\
+        \n\
+        ## Key people\n\
+        \n\
+        | name | details |\n\
+        | :--- | :------ |\n\
+        | Ronald Fisher | SME |\n";
+
+    let parsed = parse_data_source(contents);
+
+    assert_eq!(parsed.title, "iris");
+    assert_eq!(parsed.front_matter.unwrap().name, "iris");
+    assert_eq!(
+        parsed.database_details[0].tables_created,
+        "iris_transformed"
+    );
+    assert_eq!(parsed.data_dictionary[0].field, "id");
+    assert_eq!(parsed.key_people[0].name, "Ronald Fisher");
+    assert!(parsed.extract_details.is_empty());
+}
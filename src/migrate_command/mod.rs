@@ -0,0 +1,357 @@
+use clap::Args;
+use console::Style;
+use ptree::{item, TreeBuilder};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, env, fs, path};
+
+use crate::component::{list_components, Stage};
+use crate::front_matter::{self, ComponentFrontMatter, DataSourceFrontMatter, ProjectFrontMatter};
+use crate::sync_command::{self, end_marker, start_marker, README};
+
+/// The file that marks a folder as a geoff project
+pub const MARKER_FILE: &str = ".geoff";
+
+/// The folder that files are copied into before a migration changes them
+pub const BACKUP_FOLDER: &str = ".geoff_backup";
+
+/// The format of projects created by this version of geoff
+pub const FORMAT_VERSION: u32 = 2;
+
+/// The contents of the `.geoff` marker
+///
+/// Projects created before the format was recorded have an empty marker,
+/// which is read as format 0.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct Marker {
+    /// The format version of the project's layout and documents
+    #[serde(default)]
+    pub format: u32,
+}
+
+impl Marker {
+    /// Reads the marker from the root of the current project
+    pub fn load() -> Marker {
+        let contents = fs::read_to_string(MARKER_FILE)
+            .unwrap_or_else(|_| panic!("Unable to read {}", MARKER_FILE));
+
+        toml::from_str(&contents)
+            .unwrap_or_else(|err| panic!("Unable to parse {}\n{:?}", MARKER_FILE, err))
+    }
+
+    /// Formats the marker as written to `.geoff`
+    pub fn render(&self) -> String {
+        toml::to_string(self).expect("Unable to serialise the marker")
+    }
+}
+
+/// The new contents of the files a migration changes
+///
+/// Migrations read through the changes made by earlier ones, so the whole
+/// upgrade can be planned before anything is written.
+#[derive(Debug, Default)]
+pub struct Changes {
+    pub files: BTreeMap<path::PathBuf, String>,
+}
+
+impl Changes {
+    /// Reads a file as it will be once the earlier changes are made
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The path of the file relative to the project root
+    pub fn read(&self, file: &path::Path) -> Option<String> {
+        self.files
+            .get(file)
+            .cloned()
+            .or_else(|| fs::read_to_string(file).ok())
+    }
+
+    /// Records the new contents of a file if they're different
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The path of the file relative to the project root
+    /// * `contents` - The new contents of the file
+    ///
+    /// # Return value
+    ///
+    /// Whether the file changed
+    pub fn write(&mut self, file: path::PathBuf, contents: String) -> bool {
+        if self.read(&file).as_deref() == Some(contents.as_str()) {
+            return false;
+        }
+
+        self.files.insert(file, contents);
+        true
+    }
+}
+
+/// A step that upgrades a project from one format to the next
+pub struct Migration {
+    /// The format the migration upgrades to
+    pub to: u32,
+    /// What the migration changes
+    pub description: &'static str,
+    /// Records the changes and returns the files that changed
+    pub plan: fn(&mut Changes) -> Vec<path::PathBuf>,
+}
+
+/// Every migration in the order they are applied
+pub const MIGRATIONS: [Migration; 2] = [
+    Migration {
+        to: 1,
+        description: "Add front matter to the project's documents",
+        plan: add_front_matter,
+    },
+    Migration {
+        to: 2,
+        description: "Add managed component lists to the README",
+        plan: add_managed_lists,
+    },
+];
+
+/// Adds front matter to documents written before it was used
+///
+/// The fields geoff can't know, such as the owner, are left out. The
+/// created date is set to today.
+///
+/// # Arguments
+///
+/// * `changes` - The changes made by earlier migrations
+pub fn add_front_matter(changes: &mut Changes) -> Vec<path::PathBuf> {
+    let mut changed = Vec::new();
+    let created = front_matter::today();
+
+    let mut add = |document: path::PathBuf, block: String| {
+        if let Some(contents) = changes.read(&document) {
+            if front_matter::split(&contents).is_none()
+                && changes.write(document.clone(), format!("{}\n{}", block, contents))
+            {
+                changed.push(document);
+            }
+        }
+    };
+
+    let project = ProjectFrontMatter {
+        project: env::current_dir()
+            .expect("Unable to get current directory")
+            .file_name()
+            .expect("The project folder has a name")
+            .to_string_lossy()
+            .to_string(),
+        title: None,
+        created: created.clone(),
+        extra: BTreeMap::new(),
+    };
+    for document in [README, "project_scoping.md"] {
+        add(
+            path::PathBuf::from(document),
+            front_matter::render(&project),
+        );
+    }
+
+    for component in Stage::ALL.into_iter().flat_map(list_components) {
+        let block = match component.stage {
+            Stage::DataSource => front_matter::render(&DataSourceFrontMatter {
+                name: component.name.clone(),
+                created: created.clone(),
+                ..DataSourceFrontMatter::default()
+            }),
+            stage => front_matter::render(&ComponentFrontMatter {
+                name: component.name.clone(),
+                title: None,
+                stage,
+                owner: None,
+                created: created.clone(),
+                needs_review: None,
+                extra: BTreeMap::new(),
+            }),
+        };
+
+        add(component.document(), block);
+    }
+
+    changed
+}
+
+/// The example list items in the README template before the lists were
+/// managed by geoff
+fn _template_items(stage: Stage) -> [&'static str; 2] {
+    match stage {
+        Stage::DataSource => ["* data source 1", "* data source 2"],
+        Stage::Exploration => ["* exploration 1", "* exploration 2"],
+        Stage::Model => ["* model 1", "* model 2"],
+        Stage::Product => ["* product 1", "* product 2"],
+    }
+}
+
+/// Swaps the README's example component lists for managed sections
+///
+/// Lists that have been edited by hand are left alone. Markers can be added
+/// around them afterwards to have geoff manage them.
+///
+/// # Arguments
+///
+/// * `changes` - The changes made by earlier migrations
+pub fn add_managed_lists(changes: &mut Changes) -> Vec<path::PathBuf> {
+    let readme = path::PathBuf::from(README);
+    let contents = match changes.read(&readme) {
+        Some(contents) => contents,
+        None => return Vec::new(),
+    };
+
+    let mut lines: Vec<String> = contents.lines().map(|line| line.to_string()).collect();
+    for stage in Stage::ALL {
+        let [first, second] = _template_items(stage);
+
+        if let Some(idx) = lines
+            .windows(2)
+            .position(|pair| pair[0].trim() == first && pair[1].trim() == second)
+        {
+            lines.splice(idx..idx + 2, [start_marker(stage), end_marker(stage)]);
+        }
+    }
+
+    let mut updated = lines.join("\n");
+    if contents.ends_with('\n') {
+        updated.push('\n');
+    }
+    let (synced, _) = sync_command::sync(&updated);
+
+    if changes.write(readme.clone(), synced) {
+        vec![readme]
+    } else {
+        Vec::new()
+    }
+}
+
+#[derive(Args)]
+pub struct Migrate {
+    /// Shows what would change without changing anything
+    #[clap(short, long)]
+    pub dry_run: bool,
+}
+
+impl Migrate {
+    /// The format of the current project
+    ///
+    /// # Errors
+    ///
+    /// * The project was created by a newer version of geoff
+    pub fn current_format(&self) -> u32 {
+        let format = Marker::load().format;
+
+        if format > FORMAT_VERSION {
+            eprintln!(
+                "{} {}",
+                Style::new()
+                    .color256(9)
+                    .apply_to("This project was created by a newer version of geoff, format"),
+                format
+            );
+            panic!("Format {} isn't supported", format);
+        }
+
+        format
+    }
+
+    /// Plans every migration the project needs, in order
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The project's current format
+    ///
+    /// # Return value
+    ///
+    /// The changes to make and the files each migration changes
+    pub fn plan(&self, from: u32) -> (Changes, Vec<(&'static Migration, Vec<path::PathBuf>)>) {
+        let mut changes = Changes::default();
+
+        let steps = MIGRATIONS
+            .iter()
+            .filter(|migration| migration.to > from)
+            .map(|migration| (migration, (migration.plan)(&mut changes)))
+            .collect();
+
+        changes.write(
+            path::PathBuf::from(MARKER_FILE),
+            Marker {
+                format: FORMAT_VERSION,
+            }
+            .render(),
+        );
+
+        (changes, steps)
+    }
+
+    /// Copies the files that are about to change into a backup folder
+    ///
+    /// # Arguments
+    ///
+    /// * `changes` - The changes to make
+    ///
+    /// # Return value
+    ///
+    /// The backup folder
+    pub fn backup(&self, changes: &Changes) -> path::PathBuf {
+        let backup = path::Path::new(BACKUP_FOLDER)
+            .join(chrono::Local::now().format("%Y-%m-%dT%H%M%S").to_string());
+
+        for file in changes.files.keys().filter(|file| file.exists()) {
+            let destination = backup.join(file);
+            fs::create_dir_all(destination.parent().expect("Backups are in a folder"))
+                .unwrap_or_else(|_| panic!("Unable to create {}", backup.display()));
+            fs::copy(file, &destination)
+                .unwrap_or_else(|_| panic!("Unable to back up {}", file.display()));
+        }
+
+        backup
+    }
+
+    /// Writes the changes to the project
+    ///
+    /// # Arguments
+    ///
+    /// * `changes` - The changes to make
+    pub fn apply(&self, changes: &Changes) {
+        for (file, contents) in changes.files.iter() {
+            fs::write(file, contents)
+                .unwrap_or_else(|_| panic!("Unable to update {}", file.display()));
+        }
+    }
+
+    /// Creates a tree of the migrations and the files each one changes
+    ///
+    /// # Arguments
+    ///
+    /// * `steps` - The migrations with the files they change
+    pub fn create_tree(
+        &self,
+        steps: &[(&'static Migration, Vec<path::PathBuf>)],
+    ) -> item::StringItem {
+        let hd = Style::new().color256(194);
+
+        let mut tree = TreeBuilder::new(format!(
+            "{}",
+            Style::new()
+                .bold()
+                .apply_to(format!("Migrations to format {}", FORMAT_VERSION))
+        ));
+        for (migration, files) in steps.iter() {
+            tree.begin_child(format!("{}. {}", migration.to, migration.description));
+            for file in files.iter() {
+                tree.add_empty_child(format!(
+                    "{} {}",
+                    hd.apply_to("\u{1F5CE}"),
+                    file.to_string_lossy().replace('\\', "/")
+                ));
+            }
+            tree.end_child();
+        }
+
+        tree.build()
+    }
+}
+
+#[cfg(test)]
+mod tests;
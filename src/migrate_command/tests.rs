@@ -0,0 +1,169 @@
+use std::fs;
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+
+const OLD_README: &str = "# iris\n\
+    ## Introduction\n\
+    *brief intro to project*\n\
+    \n\
+    Data sources \n\
+    * data source 1\n\
+    * data source 2\n\
+    \n\
+    Models\n\
+    * classifier\n";
+
+fn setup_old_project() {
+    fs::write(MARKER_FILE, "").unwrap();
+    fs::write(README, OLD_README).unwrap();
+    fs::create_dir_all("data_sources/iris").unwrap();
+    fs::write("data_sources/iris/metadata.md", "# iris\n").unwrap();
+    fs::create_dir_all("models/classifier").unwrap();
+    fs::write(
+        "models/classifier/README.md",
+        "---\nname: classifier\nstage: model\ncreated: 2022-04-01\n---\n\n# classifier\n",
+    )
+    .unwrap();
+}
+
+// ++++++ //
+// Marker //
+// ++++++ //
+#[test]
+fn empty_marker_is_format_zero() {
+    test_in_tmp_dir(
+        || {
+            fs::write(MARKER_FILE, "").unwrap();
+
+            assert_eq!(Marker::load(), Marker { format: 0 });
+        },
+        false,
+    )
+}
+
+#[test]
+fn template_marker_is_current_format() {
+    let marker: Marker = toml::from_str(include_str!("../templates/root/.geoff")).unwrap();
+
+    assert_eq!(marker.format, FORMAT_VERSION);
+}
+
+#[test]
+fn migrations_end_at_current_format() {
+    let formats: Vec<u32> = MIGRATIONS.iter().map(|migration| migration.to).collect();
+
+    assert_eq!(formats, (1..=FORMAT_VERSION).collect::<Vec<u32>>());
+}
+
+// ++++++++++++++++ //
+// add_front_matter //
+// ++++++++++++++++ //
+#[test]
+fn adds_front_matter_where_missing() {
+    test_in_tmp_dir(
+        || {
+            setup_old_project();
+
+            let mut changes = Changes::default();
+            let changed = add_front_matter(&mut changes);
+
+            assert_eq!(
+                changed,
+                vec![
+                    path::PathBuf::from(README),
+                    path::PathBuf::from("data_sources/iris/metadata.md"),
+                ]
+            );
+            assert_eq!(
+                changes.read(path::Path::new("data_sources/iris/metadata.md")),
+                Some(format!(
+                    "---\nname: iris\ncreated: {}\n---\n\n# iris\n",
+                    front_matter::today()
+                ))
+            );
+            assert_eq!(
+                fs::read_to_string("data_sources/iris/metadata.md").unwrap(),
+                "# iris\n"
+            );
+        },
+        false,
+    )
+}
+
+// +++++++++++++++++ //
+// add_managed_lists //
+// +++++++++++++++++ //
+#[test]
+fn replaces_untouched_example_lists() {
+    test_in_tmp_dir(
+        || {
+            setup_old_project();
+
+            let mut changes = Changes::default();
+            add_managed_lists(&mut changes);
+
+            let readme = changes.read(path::Path::new(README)).unwrap();
+            assert!(readme.contains(
+                "Data sources \n\
+                <!-- geoff:begin data_sources -->\n\
+                * [iris](data_sources/iris/metadata.md)\n\
+                <!-- geoff:end data_sources -->\n"
+            ));
+            assert!(readme.contains("Models\n* classifier\n"));
+        },
+        false,
+    )
+}
+
+// ++++ //
+// plan //
+// ++++ //
+#[test]
+fn plans_each_migration_after_current_format() {
+    test_in_tmp_dir(
+        || {
+            setup_old_project();
+
+            let (changes, steps) = Migrate { dry_run: true }.plan(1);
+
+            assert_eq!(steps.len(), 1);
+            assert_eq!(steps[0].0.to, 2);
+            assert_eq!(
+                changes.read(path::Path::new(MARKER_FILE)),
+                Some(String::from("format = 2\n"))
+            );
+            assert!(!changes
+                .read(path::Path::new(README))
+                .unwrap()
+                .starts_with("---"));
+        },
+        false,
+    )
+}
+
+// ++++++ //
+// backup //
+// ++++++ //
+#[test]
+fn backs_up_existing_files_before_applying() {
+    test_in_tmp_dir(
+        || {
+            setup_old_project();
+
+            let migrate = Migrate { dry_run: false };
+            let (changes, _) = migrate.plan(0);
+            let backup = migrate.backup(&changes);
+            migrate.apply(&changes);
+
+            assert_eq!(fs::read_to_string(backup.join(README)).unwrap(), OLD_README);
+            assert_eq!(
+                fs::read_to_string(backup.join("data_sources/iris/metadata.md")).unwrap(),
+                "# iris\n"
+            );
+            assert!(!backup.join("models/classifier/README.md").exists());
+            assert_eq!(Marker::load().format, FORMAT_VERSION);
+        },
+        false,
+    )
+}
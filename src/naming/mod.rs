@@ -0,0 +1,482 @@
+use chrono::NaiveDate;
+use console::Style;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt};
+
+use crate::component::{Stage, ARCHIVE_FOLDER};
+
+/// The longest a name can be unless the project sets its own limit
+pub const MAX_LENGTH: usize = 64;
+
+/// Names that can't be used as folders on Windows, or that geoff uses itself
+const RESERVED: &[&str] = &[
+    "con",
+    "prn",
+    "aux",
+    "nul",
+    "com1",
+    "com2",
+    "com3",
+    "com4",
+    "com5",
+    "com6",
+    "com7",
+    "com8",
+    "com9",
+    "lpt1",
+    "lpt2",
+    "lpt3",
+    "lpt4",
+    "lpt5",
+    "lpt6",
+    "lpt7",
+    "lpt8",
+    "lpt9",
+    ARCHIVE_FOLDER,
+];
+
+/// The conventions a project can require names to follow
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Convention {
+    /// Lowercase words joined by underscores, e.g sales_data_2022
+    #[serde(rename = "snake_case")]
+    SnakeCase,
+    /// Lowercase words joined by hyphens, e.g sales-data-2022
+    #[serde(rename = "kebab-case")]
+    KebabCase,
+}
+
+impl Convention {
+    /// The character that joins the words of a name
+    pub fn separator(&self) -> char {
+        match self {
+            Convention::SnakeCase => '_',
+            Convention::KebabCase => '-',
+        }
+    }
+
+    /// Whether a name follows the convention
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to check
+    pub fn matches(&self, name: &str) -> bool {
+        let separator = self.separator();
+
+        !name.is_empty()
+            && name.split(separator).all(|word| {
+                !word.is_empty()
+                    && word
+                        .chars()
+                        .all(|c| c.is_alphanumeric() && !c.is_uppercase())
+            })
+    }
+}
+
+impl fmt::Display for Convention {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Convention::SnakeCase => write!(f, "snake_case"),
+            Convention::KebabCase => write!(f, "kebab-case"),
+        }
+    }
+}
+
+/// The prefixes that keep the components of a stage in the order they were
+/// added
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Prefix {
+    /// A number one higher than any in the stage, e.g 003_churn_drivers
+    #[serde(rename = "number")]
+    Number,
+    /// The date the component was added, e.g 2022-04-01_churn_drivers
+    #[serde(rename = "date")]
+    Date,
+}
+
+impl fmt::Display for Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Prefix::Number => write!(f, "number"),
+            Prefix::Date => write!(f, "date"),
+        }
+    }
+}
+
+/// How names in the project must be written
+///
+/// The rules live in the `[naming]` section of the manifest, e.g
+///
+/// ```toml
+/// [naming]
+/// max_length = 40
+///
+/// [naming.conventions]
+/// data_sources = "snake_case"
+/// models = "kebab-case"
+///
+/// [naming.prefixes]
+/// explorations = "number"
+/// ```
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
+pub struct NamingConfig {
+    /// The convention the names in each stage must follow, keyed by the
+    /// stage's folder
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub conventions: BTreeMap<String, Convention>,
+    /// The longest a name can be, [MAX_LENGTH] if not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    /// The prefix added to new names in each stage, keyed by the stage's
+    /// folder
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub prefixes: BTreeMap<String, Prefix>,
+}
+
+impl NamingConfig {
+    /// Whether every rule is left at its default
+    pub fn is_default(&self) -> bool {
+        *self == NamingConfig::default()
+    }
+
+    /// The convention the names in a stage must follow
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The stage, or None for the project itself
+    pub fn convention(&self, stage: Option<Stage>) -> Option<Convention> {
+        stage.and_then(|stage| self.conventions.get(stage.folder()).copied())
+    }
+
+    /// The prefix added to new names in a stage
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The stage
+    pub fn prefix(&self, stage: Stage) -> Option<Prefix> {
+        self.prefixes.get(stage.folder()).copied()
+    }
+}
+
+/// The reasons a name can't be used
+#[derive(Debug, PartialEq)]
+pub enum NameError {
+    Empty,
+    TooLong(usize),
+    Separator,
+    Relative,
+    Hidden,
+    Reserved,
+    Character(char),
+    Convention(Convention),
+}
+
+impl fmt::Display for NameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NameError::Empty => write!(f, "is empty"),
+            NameError::TooLong(max_length) => {
+                write!(f, "is longer than {} characters", max_length)
+            }
+            NameError::Separator => write!(f, "contains a path separator"),
+            NameError::Relative => write!(f, "refers to a folder rather than naming one"),
+            NameError::Hidden => write!(f, "starts with a ."),
+            NameError::Reserved => write!(f, "is reserved"),
+            NameError::Character(' ') => write!(f, "contains a space"),
+            NameError::Character(c) => write!(f, "contains the character {:?}", c),
+            NameError::Convention(convention) => write!(f, "isn't {}", convention),
+        }
+    }
+}
+
+/// Checks a name can be used as a folder
+///
+/// Names are limited to letters, digits, `_`, `-` and `.`, can't start with
+/// a `.` and can't be one of the names Windows reserves for devices.
+///
+/// # Arguments
+///
+/// * `name` - The name to check
+/// * `max_length` - The longest the name can be, in characters
+pub fn validate(name: &str, max_length: usize) -> Result<(), NameError> {
+    if name.is_empty() {
+        return Err(NameError::Empty);
+    }
+    if name == "." || name == ".." {
+        return Err(NameError::Relative);
+    }
+    if name.contains(['/', '\\']) {
+        return Err(NameError::Separator);
+    }
+    if name.starts_with('.') {
+        return Err(NameError::Hidden);
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.')))
+    {
+        return Err(NameError::Character(c));
+    }
+    if name.chars().count() > max_length {
+        return Err(NameError::TooLong(max_length));
+    }
+
+    let stem = name.split('.').next().unwrap_or(name).to_lowercase();
+    if RESERVED.contains(&stem.as_str()) {
+        return Err(NameError::Reserved);
+    }
+
+    Ok(())
+}
+
+/// Turns a title into a name
+///
+/// Letters are lowercased and runs of anything other than letters and
+/// digits become a single separator, e.g `Sales Data 2022` becomes
+/// `sales_data_2022`.
+///
+/// # Arguments
+///
+/// * `title` - The title to turn into a name
+/// * `separator` - The character that joins the words
+pub fn slugify(title: &str, separator: char) -> String {
+    let mut slug = String::new();
+
+    for c in title.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with(separator) {
+            slug.push(separator);
+        }
+    }
+
+    slug.trim_end_matches(separator).to_string()
+}
+
+/// A name that can be used for a project or component
+#[derive(Clone, Debug, PartialEq)]
+pub struct Name {
+    /// The name, which is also the folder
+    pub name: String,
+    /// What the user wrote if the name was made from it
+    pub title: Option<String>,
+}
+
+/// Checks a name, or makes one from it, against the project's rules
+///
+/// A number or date prefix isn't held to the stage's convention. When the
+/// stage is prefixed and the name doesn't have one yet, room is left for the
+/// prefix geoff will add within the length limit.
+///
+/// # Arguments
+///
+/// * `input` - The name as written by the user
+/// * `stage` - The stage of the component, or None for a project
+/// * `config` - The project's naming rules
+/// * `slugify` - Whether to make a name from the input rather than use it
+///   as it is
+///
+/// # Errors
+///
+/// * The name, or the one made from it, breaks one of the rules
+pub fn name(
+    input: &str,
+    stage: Option<Stage>,
+    config: &NamingConfig,
+    slugify: bool,
+) -> Result<Name, NameError> {
+    let convention = config.convention(stage);
+    let name = if slugify {
+        self::slugify(input, _separator(convention))
+    } else {
+        input.to_string()
+    };
+
+    let max_length = config.max_length.unwrap_or(MAX_LENGTH);
+    let reserved = match stage.and_then(|stage| config.prefix(stage)) {
+        Some(_) if unprefixed(&name) != name => 0,
+        Some(Prefix::Number) => NUMBER_WIDTH + 1,
+        Some(Prefix::Date) => DATE_WIDTH + 1,
+        None => 0,
+    };
+    validate(&name, max_length.saturating_sub(reserved))?;
+
+    if let Some(convention) = convention {
+        if !convention.matches(unprefixed(&name)) {
+            return Err(NameError::Convention(convention));
+        }
+    }
+
+    Ok(Name {
+        title: if name != input {
+            Some(input.to_string())
+        } else {
+            None
+        },
+        name,
+    })
+}
+
+/// The digits a number prefix is padded to
+pub const NUMBER_WIDTH: usize = 3;
+
+/// Splits the number prefix from a name
+///
+/// # Arguments
+///
+/// * `name` - The name to split
+///
+/// # Return value
+///
+/// The number, the separator after it and the rest of the name, e.g
+/// `003_churn` is split into 3, `_` and `churn`. None if the name doesn't
+/// start with a number followed by a separator.
+pub fn split_number(name: &str) -> Option<(u32, char, &str)> {
+    let digits = name.len() - name.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let separator = name[digits..].chars().next()?;
+    if digits == 0 || !matches!(separator, '_' | '-') {
+        return None;
+    }
+
+    let rest = &name[digits + 1..];
+    if rest.is_empty() {
+        return None;
+    }
+
+    name[..digits]
+        .parse()
+        .ok()
+        .map(|number| (number, separator, rest))
+}
+
+/// Puts a number prefix in front of a name
+///
+/// # Arguments
+///
+/// * `number` - The number
+/// * `separator` - The character between the number and the name
+/// * `name` - The name without a prefix
+pub fn numbered(number: u32, separator: char, name: &str) -> String {
+    format!(
+        "{:0width$}{}{}",
+        number,
+        separator,
+        name,
+        width = NUMBER_WIDTH
+    )
+}
+
+/// The characters in a date prefix, formatted as YYYY-MM-DD
+const DATE_WIDTH: usize = 10;
+
+/// Whether a name starts with an ISO date followed by a separator
+fn _has_date(name: &str) -> bool {
+    match (name.get(..DATE_WIDTH), name.get(DATE_WIDTH..DATE_WIDTH + 1)) {
+        (Some(date), Some("_" | "-")) => NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok(),
+        _ => false,
+    }
+}
+
+/// The name without a number or date prefix
+///
+/// # Arguments
+///
+/// * `name` - The name of a component
+pub fn unprefixed(name: &str) -> &str {
+    if _has_date(name) {
+        return &name[DATE_WIDTH + 1..];
+    }
+
+    split_number(name).map(|(_, _, rest)| rest).unwrap_or(name)
+}
+
+/// Adds the stage's prefix to a new name
+///
+/// Names that already start with the prefix are left as they are, so a
+/// number or date can be given by hand.
+///
+/// # Arguments
+///
+/// * `named` - The checked name
+/// * `stage` - The stage the component is being added to
+/// * `config` - The project's naming rules
+/// * `existing` - The names already in the stage, including archived ones
+/// * `today` - Today's date formatted as YYYY-MM-DD
+pub fn prefix(
+    named: Name,
+    stage: Stage,
+    config: &NamingConfig,
+    existing: &[String],
+    today: &str,
+) -> Name {
+    let separator = _separator(config.convention(Some(stage)));
+
+    let name = match config.prefix(stage) {
+        Some(Prefix::Number) if split_number(&named.name).is_none() => {
+            let next = existing
+                .iter()
+                .filter_map(|name| split_number(name))
+                .map(|(number, _, _)| number)
+                .max()
+                .unwrap_or(0)
+                + 1;
+            numbered(next, separator, &named.name)
+        }
+        Some(Prefix::Date) if !_has_date(&named.name) => {
+            format!("{}{}{}", today, separator, named.name)
+        }
+        _ => return named,
+    };
+
+    Name { name, ..named }
+}
+
+/// The separator used when suggesting or making a name
+fn _separator(convention: Option<Convention>) -> char {
+    convention
+        .map(|convention| convention.separator())
+        .unwrap_or('_')
+}
+
+/// Describes why a name can't be used, with a name that could be
+///
+/// # Arguments
+///
+/// * `input` - The name as written by the user
+/// * `stage` - The stage of the component, or None for a project
+/// * `config` - The project's naming rules
+/// * `error` - Why the name can't be used
+pub fn explain(
+    input: &str,
+    stage: Option<Stage>,
+    config: &NamingConfig,
+    error: &NameError,
+) -> String {
+    match name(input, stage, config, true) {
+        Ok(suggestion) if suggestion.name != input => {
+            format!("{}, try {}", error, suggestion.name)
+        }
+        _ => error.to_string(),
+    }
+}
+
+/// Checks a name against the project's rules, see [name]
+///
+/// # Errors
+///
+/// * The name, or the one made from it, breaks one of the rules
+pub fn check(input: &str, stage: Option<Stage>, config: &NamingConfig, slugify: bool) -> Name {
+    name(input, stage, config, slugify).unwrap_or_else(|err| {
+        eprintln!(
+            "{} {}",
+            input,
+            Style::new()
+                .color256(9)
+                .apply_to(explain(input, stage, config, &err))
+        );
+        panic!("{} isn't a valid name", input)
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,300 @@
+use rstest::rstest;
+
+use super::*;
+
+fn kebab_models() -> NamingConfig {
+    NamingConfig {
+        conventions: [(String::from("models"), Convention::KebabCase)]
+            .into_iter()
+            .collect(),
+        max_length: None,
+        prefixes: BTreeMap::new(),
+    }
+}
+
+// ++++++++ //
+// validate //
+// ++++++++ //
+#[rstest]
+#[case("iris")]
+#[case("sales_data_2022")]
+#[case("iris-v2.1")]
+#[case("Iris")]
+fn accepts_valid_names(#[case] name: &str) {
+    assert_eq!(validate(name, MAX_LENGTH), Ok(()));
+}
+
+#[rstest]
+#[case("", NameError::Empty)]
+#[case("..", NameError::Relative)]
+#[case("raw/iris", NameError::Separator)]
+#[case("raw\\iris", NameError::Separator)]
+#[case(".iris", NameError::Hidden)]
+#[case("CON", NameError::Reserved)]
+#[case("nul.txt", NameError::Reserved)]
+#[case("_archive", NameError::Reserved)]
+#[case("sales data", NameError::Character(' '))]
+#[case("iris?", NameError::Character('?'))]
+fn rejects_invalid_names(#[case] name: &str, #[case] error: NameError) {
+    assert_eq!(validate(name, MAX_LENGTH), Err(error));
+}
+
+#[test]
+fn rejects_long_names() {
+    assert_eq!(validate("abcdef", 5), Err(NameError::TooLong(5)));
+    assert_eq!(validate("abcde", 5), Ok(()));
+}
+
+// +++++++ //
+// slugify //
+// +++++++ //
+#[rstest]
+#[case("Sales Data 2022", '_', "sales_data_2022")]
+#[case("  Sales -- Data!  ", '_', "sales_data")]
+#[case("Sales Data 2022", '-', "sales-data-2022")]
+#[case("Données météo", '_', "données_météo")]
+fn slugifies_titles(#[case] title: &str, #[case] separator: char, #[case] slug: &str) {
+    assert_eq!(slugify(title, separator), slug);
+}
+
+// ++++ //
+// name //
+// ++++ //
+#[test]
+fn name_keeps_title_when_slugified() {
+    assert_eq!(
+        name(
+            "Sales Data 2022",
+            Some(Stage::DataSource),
+            &NamingConfig::default(),
+            true
+        ),
+        Ok(Name {
+            name: String::from("sales_data_2022"),
+            title: Some(String::from("Sales Data 2022")),
+        })
+    );
+    assert_eq!(
+        name(
+            "iris",
+            Some(Stage::DataSource),
+            &NamingConfig::default(),
+            true
+        ),
+        Ok(Name {
+            name: String::from("iris"),
+            title: None,
+        })
+    );
+}
+
+#[test]
+fn name_follows_stage_convention() {
+    assert_eq!(
+        name("Iris Classifier", Some(Stage::Model), &kebab_models(), true)
+            .unwrap()
+            .name,
+        "iris-classifier"
+    );
+    assert_eq!(
+        name(
+            "iris_classifier",
+            Some(Stage::Model),
+            &kebab_models(),
+            false
+        ),
+        Err(NameError::Convention(Convention::KebabCase))
+    );
+    assert!(name(
+        "iris_classifier",
+        Some(Stage::Exploration),
+        &kebab_models(),
+        false
+    )
+    .is_ok());
+}
+
+#[test]
+fn name_uses_configured_length() {
+    let config = NamingConfig {
+        max_length: Some(4),
+        ..NamingConfig::default()
+    };
+
+    assert_eq!(
+        name("iris_classifier", None, &config, false),
+        Err(NameError::TooLong(4))
+    );
+}
+
+#[test]
+fn name_ignores_prefix_for_convention() {
+    let config = NamingConfig {
+        conventions: [(String::from("explorations"), Convention::SnakeCase)]
+            .into_iter()
+            .collect(),
+        prefixes: [(String::from("explorations"), Prefix::Date)]
+            .into_iter()
+            .collect(),
+        ..NamingConfig::default()
+    };
+
+    assert!(name(
+        "2022-04-01_churn_v2",
+        Some(Stage::Exploration),
+        &config,
+        false
+    )
+    .is_ok());
+    assert_eq!(
+        name(
+            "2022-04-01_churn-v2",
+            Some(Stage::Exploration),
+            &config,
+            false
+        ),
+        Err(NameError::Convention(Convention::SnakeCase))
+    );
+}
+
+#[test]
+fn name_leaves_room_for_prefix() {
+    let config = NamingConfig {
+        max_length: Some(10),
+        prefixes: [(String::from("explorations"), Prefix::Number)]
+            .into_iter()
+            .collect(),
+        ..NamingConfig::default()
+    };
+
+    assert_eq!(
+        name("churn_v2", Some(Stage::Exploration), &config, false),
+        Err(NameError::TooLong(6))
+    );
+    assert!(name("churn", Some(Stage::Exploration), &config, false).is_ok());
+    assert!(name("001_churn", Some(Stage::Exploration), &config, false).is_ok());
+    assert!(name("churn_v2", Some(Stage::Model), &config, false).is_ok());
+}
+
+// +++++++ //
+// explain //
+// +++++++ //
+#[test]
+fn explains_with_suggestion() {
+    assert_eq!(
+        explain(
+            "Sales Data",
+            None,
+            &NamingConfig::default(),
+            &NameError::Character(' ')
+        ),
+        "contains a space, try sales_data"
+    );
+    assert_eq!(
+        explain("..", None, &NamingConfig::default(), &NameError::Relative),
+        "refers to a folder rather than naming one"
+    );
+}
+
+// +++++ //
+// check //
+// +++++ //
+#[test]
+#[should_panic(expected = "../oops isn't a valid name")]
+fn check_panics_on_invalid_name() {
+    check(
+        "../oops",
+        Some(Stage::DataSource),
+        &NamingConfig::default(),
+        false,
+    );
+}
+
+// ++++++++++++ //
+// split_number //
+// ++++++++++++ //
+#[rstest]
+#[case("003_churn", Some((3, '_', "churn")))]
+#[case("12-eda", Some((12, '-', "eda")))]
+#[case("2022-04-01_eda", Some((2022, '-', "04-01_eda")))]
+#[case("eda", None)]
+#[case("003", None)]
+#[case("003_", None)]
+#[case("003churn", None)]
+fn splits_number_prefix(#[case] name: &str, #[case] split: Option<(u32, char, &str)>) {
+    assert_eq!(split_number(name), split);
+}
+
+// ++++++ //
+// prefix //
+// ++++++ //
+fn prefixed(name: &str, prefix: Prefix, existing: &[&str]) -> String {
+    let config = NamingConfig {
+        prefixes: [(String::from("explorations"), prefix)]
+            .into_iter()
+            .collect(),
+        ..NamingConfig::default()
+    };
+    let existing: Vec<String> = existing.iter().map(|name| name.to_string()).collect();
+
+    self::prefix(
+        Name {
+            name: String::from(name),
+            title: None,
+        },
+        Stage::Exploration,
+        &config,
+        &existing,
+        "2022-04-01",
+    )
+    .name
+}
+
+#[rstest]
+#[case(&[], "001_churn")]
+#[case(&["001_eda", "007_late", "notes"], "008_churn")]
+fn numbers_after_highest(#[case] existing: &[&str], #[case] expected: &str) {
+    assert_eq!(prefixed("churn", Prefix::Number, existing), expected);
+}
+
+#[test]
+fn dates_new_names() {
+    assert_eq!(prefixed("churn", Prefix::Date, &[]), "2022-04-01_churn");
+}
+
+#[rstest]
+#[case("010_churn", Prefix::Number)]
+#[case("2021-12-31_churn", Prefix::Date)]
+fn keeps_prefix_given(#[case] name: &str, #[case] prefix: Prefix) {
+    assert_eq!(prefixed(name, prefix, &["001_eda"]), name);
+}
+
+#[test]
+fn other_stages_arent_prefixed() {
+    let config = NamingConfig {
+        prefixes: [(String::from("explorations"), Prefix::Number)]
+            .into_iter()
+            .collect(),
+        ..NamingConfig::default()
+    };
+    let named = Name {
+        name: String::from("classifier"),
+        title: None,
+    };
+
+    assert_eq!(
+        prefix(named.clone(), Stage::Model, &config, &[], "2022-04-01"),
+        named
+    );
+}
+
+// ++++++++++ //
+// unprefixed //
+// ++++++++++ //
+#[rstest]
+#[case("003_churn", "churn")]
+#[case("2022-04-01_churn", "churn")]
+#[case("churn", "churn")]
+fn strips_prefix(#[case] name: &str, #[case] expected: &str) {
+    assert_eq!(unprefixed(name), expected);
+}
@@ -5,7 +5,7 @@ use super::*;
 
 #[test]
 #[should_panic(expected = "test_data exists")]
-fn pleasant_error_name_already_exists_error_raised() -> () {
+fn pleasant_error_name_already_exists_error_raised() {
     struct TestData {
         name: path::PathBuf,
     }
@@ -23,7 +23,7 @@ fn pleasant_error_name_already_exists_error_raised() -> () {
 
 #[test]
 #[should_panic(expected = "Parents don't exist")]
-fn pleasant_error_parents_dont_exist_error_raised() -> () {
+fn pleasant_error_parents_dont_exist_error_raised() {
     struct TestData {
         name: path::PathBuf,
     }
@@ -41,7 +41,7 @@ fn pleasant_error_parents_dont_exist_error_raised() -> () {
 
 #[test]
 #[should_panic(expected = "Invalid permissions")]
-fn pleasant_error_invalid_permissions_error_raised() -> () {
+fn pleasant_error_invalid_permissions_error_raised() {
     struct TestData {
         name: path::PathBuf,
     }
@@ -59,7 +59,7 @@ fn pleasant_error_invalid_permissions_error_raised() -> () {
 
 #[test]
 #[should_panic(expected = "Unknown error")]
-fn pleasant_error_generic_error_raised() -> () {
+fn pleasant_error_generic_error_raised() {
     struct TestData {}
 
     impl PleasantErrorHandler for TestData {}
@@ -82,7 +82,7 @@ fn pleasant_error_generic_error_raised() -> () {
     #[should_panic(expected = "Unknown error")]
     case::panic_with_message(io::ErrorKind::InvalidData),
 )]
-fn pleasant_error_validate_error_kinds_raised(kind: io::ErrorKind) -> () {
+fn pleasant_error_validate_error_kinds_raised(kind: io::ErrorKind) {
     struct TestData {
         name: path::PathBuf,
     }
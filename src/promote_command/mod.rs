@@ -0,0 +1,355 @@
+use clap::Args;
+use console::Style;
+use ptree::{item, TreeBuilder};
+use std::{fs, path};
+
+use crate::add_command::{self, Component};
+use crate::component::{ComponentId, Stage};
+use crate::front_matter::{self, ComponentFrontMatter};
+use crate::interview::Interview;
+use crate::manifest::Manifest;
+use crate::naming::{self, NamingConfig};
+use crate::template_command;
+
+/// The heading of the section that records where a component came from or went
+pub const PROVENANCE_HEADING: &str = "## Provenance";
+
+/// Adds a line to a document's provenance section
+///
+/// The section is added to the end of the document if it doesn't have one
+/// yet, otherwise the line goes at the end of the existing section.
+///
+/// # Arguments
+///
+/// * `contents` - The contents of a markdown document
+/// * `line` - The line to add to the section
+pub fn add_provenance(contents: &str, line: &str) -> String {
+    let lines: Vec<&str> = contents.lines().collect();
+
+    let start = match lines
+        .iter()
+        .position(|l| l.trim_end() == PROVENANCE_HEADING)
+    {
+        Some(start) => start,
+        None => {
+            return format!(
+                "{}\n\n{}\n\n{}\n",
+                contents.trim_end(),
+                PROVENANCE_HEADING,
+                line
+            )
+        }
+    };
+
+    let end = lines[start + 1..]
+        .iter()
+        .position(|l| l.starts_with("# ") || l.starts_with("## "))
+        .map(|offset| start + 1 + offset)
+        .unwrap_or(lines.len());
+    let last = lines[..end]
+        .iter()
+        .rposition(|l| !l.trim().is_empty())
+        .unwrap_or(start);
+
+    let mut promoted: Vec<&str> = lines[..=last].to_vec();
+    if last == start {
+        promoted.push("");
+    }
+    promoted.push(line);
+    if end < lines.len() {
+        promoted.push("");
+        promoted.extend(&lines[end..]);
+    }
+
+    format!("{}\n", promoted.join("\n"))
+}
+
+/// Copies a file or a folder and everything in it
+fn _copy_recursive(from: &path::Path, to: &path::Path) {
+    if from.is_dir() {
+        fs::create_dir_all(to).unwrap_or_else(|_| panic!("Unable to create {}", to.display()));
+
+        for entry in fs::read_dir(from)
+            .unwrap_or_else(|_| panic!("Unable to read {}", from.display()))
+            .filter_map(|entry| entry.ok())
+        {
+            _copy_recursive(&entry.path(), &to.join(entry.file_name()));
+        }
+    } else {
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)
+                .unwrap_or_else(|_| panic!("Unable to create {}", parent.display()));
+        }
+        fs::copy(from, to).unwrap_or_else(|_| panic!("Unable to copy {}", from.display()));
+    }
+}
+
+#[derive(Args, Clone)]
+pub struct Promote {
+    /// The stage of the component being promoted
+    #[clap(value_enum)]
+    pub stage: Stage,
+    /// The name of the component being promoted
+    #[clap(value_parser)]
+    pub name: String,
+    /// The stage to promote the component into
+    #[clap(short, long, value_enum)]
+    pub to: Stage,
+    /// The name of the new component
+    #[clap(value_parser)]
+    pub new_name: String,
+    /// A notebook, script or folder to copy into the new component, can be
+    /// repeated
+    #[clap(short, long, value_parser)]
+    pub copy: Vec<path::PathBuf>,
+    /// The person responsible for the new component, defaults to the owner
+    /// of the one being promoted
+    #[clap(short, long, value_parser)]
+    pub owner: Option<String>,
+}
+
+impl Promote {
+    /// The id of the component being promoted
+    pub fn id(&self) -> ComponentId {
+        ComponentId::new(self.stage, &self.name)
+    }
+
+    /// The id of the component being created
+    pub fn new_id(&self) -> ComponentId {
+        ComponentId::new(self.to, &self.new_name)
+    }
+
+    /// The promotion with the new stage's prefix added to the new name, see
+    /// [add_command::prefixed]
+    ///
+    /// # Arguments
+    ///
+    /// * `naming` - The project's naming rules
+    pub fn named(&self, naming: &NamingConfig) -> Promote {
+        let named = add_command::prefixed(
+            naming::Name {
+                name: self.new_name.clone(),
+                title: None,
+            },
+            self.to,
+            naming,
+        );
+
+        Promote {
+            new_name: named.name,
+            ..self.clone()
+        }
+    }
+
+    /// Checks the promotion can go ahead before anything is created
+    ///
+    /// # Arguments
+    ///
+    /// * `naming` - The project's naming rules the new name must follow
+    ///
+    /// # Errors
+    ///
+    /// * The component is a data source or the stage isn't a later one
+    /// * The new name breaks one of the rules
+    /// * The component doesn't exist
+    /// * A file to copy is outside the component, doesn't exist within it or
+    ///   is the component's main document
+    pub fn validate(&self, naming: &NamingConfig) {
+        let red = Style::new().color256(9);
+
+        if self.stage == Stage::DataSource || self.to <= self.stage {
+            eprintln!(
+                "{} {} {}",
+                red.apply_to("Components can only be promoted to a later stage, e.g"),
+                Stage::Exploration,
+                Stage::Model
+            );
+            panic!("Can't promote {} to the {} stage", self.id(), self.to);
+        }
+
+        naming::check(&self.new_name, Some(self.to), naming, false);
+
+        let component = self.id();
+        if !component.exists() {
+            eprintln!(
+                "{} {}",
+                component,
+                red.apply_to("isn't a component in this project")
+            );
+            panic!("{} doesn't exist", component);
+        }
+
+        for file in self.copy.iter() {
+            if !file
+                .components()
+                .all(|part| matches!(part, path::Component::Normal(_)))
+            {
+                eprintln!(
+                    "{} {} {}",
+                    file.display(),
+                    red.apply_to("isn't a file within"),
+                    component
+                );
+                panic!("{} is outside {}", file.display(), component);
+            }
+            if component.path().join(file) == component.document() {
+                eprintln!(
+                    "{} {}",
+                    file.display(),
+                    red.apply_to("is written for the new component, it can't be copied")
+                );
+                panic!(
+                    "{} would replace the new component's document",
+                    file.display()
+                );
+            }
+            if !component.path().join(file).exists() {
+                eprintln!(
+                    "{} {} {}",
+                    file.display(),
+                    red.apply_to("isn't in"),
+                    component
+                );
+                panic!("{} doesn't exist", component.path().join(file).display());
+            }
+        }
+    }
+
+    /// The data sources the promoted component uses
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest holding the dependencies
+    pub fn data_sources(&self, manifest: &Manifest) -> Vec<ComponentId> {
+        manifest
+            .dependencies
+            .get(&self.id())
+            .map(|uses| {
+                uses.iter()
+                    .filter(|component| component.stage == Stage::DataSource)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The new component, owned by whoever owns the promoted one unless
+    /// another owner was given
+    pub fn component(&self) -> Component {
+        let owner = self.owner.clone().or_else(|| {
+            fs::read_to_string(self.id().document())
+                .ok()
+                .and_then(|contents| front_matter::parse::<ComponentFrontMatter>(&contents).ok())
+                .and_then(|front_matter| front_matter.owner)
+        });
+
+        Component {
+            name: path::PathBuf::from(&self.new_name),
+            owner,
+            uses: Vec::new(),
+            slugify: false,
+            title: None,
+            interview: Interview::default(),
+        }
+    }
+
+    /// Creates the new component from its template and records the template
+    /// and the data sources it uses
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest
+    pub fn create(&self, manifest: &mut Manifest) {
+        let component = self.component();
+
+        component.create_component(self.to);
+        component.create_readme(self.to, &component.readme_contents(self.to));
+        template_command::remember(
+            manifest,
+            path::Path::new("."),
+            &self.new_id().document().to_string_lossy(),
+            &add_command::readme_template_name(self.to),
+            &self.new_name,
+        );
+
+        let data_sources = self.data_sources(manifest);
+        if !data_sources.is_empty() {
+            manifest.add_dependencies(&self.new_id(), &data_sources);
+        }
+    }
+
+    /// Copies the selected files into the new component
+    pub fn copy_files(&self) {
+        for file in self.copy.iter() {
+            _copy_recursive(
+                &self.id().path().join(file),
+                &self.new_id().path().join(file),
+            );
+        }
+    }
+
+    /// Writes a reference to the other component in both components' docs
+    pub fn write_provenance(&self) {
+        let (from, to) = (self.id(), self.new_id());
+        let today = front_matter::today();
+
+        let references = [
+            (
+                to.document(),
+                format!(
+                    "Promoted from [{}](../../{}) on {}",
+                    from,
+                    from.document().to_string_lossy().replace('\\', "/"),
+                    today
+                ),
+            ),
+            (
+                from.document(),
+                format!(
+                    "Promoted to [{}](../../{}) on {}",
+                    to,
+                    to.document().to_string_lossy().replace('\\', "/"),
+                    today
+                ),
+            ),
+        ];
+
+        for (document, line) in references.iter() {
+            let contents = fs::read_to_string(document).unwrap_or_default();
+            fs::write(document, add_provenance(&contents, line))
+                .unwrap_or_else(|_| panic!("Unable to update {}", document.display()));
+        }
+    }
+
+    /// Creates a tree showing the new component and the files copied into it
+    pub fn create_tree(&self) -> item::StringItem {
+        let gold = Style::new().color256(220);
+        let hd = Style::new().color256(194);
+
+        let mut tree = TreeBuilder::new(format!(
+            "{} {}",
+            gold.apply_to("\u{1F5BF}"),
+            self.to.folder()
+        ));
+        tree.begin_child(format!("{} {}", gold.apply_to("\u{1F5BF}"), self.new_name));
+        tree.add_empty_child(format!("{} README.md", hd.apply_to("\u{1F5CE}")));
+        for file in self.copy.iter() {
+            let icon = if self.new_id().path().join(file).is_dir() {
+                gold.apply_to("\u{1F5BF}")
+            } else {
+                hd.apply_to("\u{1F5CE}")
+            };
+            tree.add_empty_child(format!(
+                "{} {}",
+                icon,
+                file.to_string_lossy().replace('\\', "/")
+            ));
+        }
+        tree.end_child();
+
+        tree.build()
+    }
+}
+
+#[cfg(test)]
+mod tests;
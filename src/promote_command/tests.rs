@@ -0,0 +1,224 @@
+use std::fs;
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+use crate::naming::Prefix;
+
+fn id(value: &str) -> ComponentId {
+    value.parse().unwrap()
+}
+
+fn promote(copy: &[&str]) -> Promote {
+    Promote {
+        stage: Stage::Exploration,
+        name: String::from("eda"),
+        to: Stage::Model,
+        new_name: String::from("classifier"),
+        copy: copy.iter().map(path::PathBuf::from).collect(),
+        owner: None,
+    }
+}
+
+fn setup_project() -> Manifest {
+    fs::create_dir_all("explorations/eda/notebooks").unwrap();
+    fs::create_dir_all("models").unwrap();
+    fs::write(
+        "explorations/eda/README.md",
+        "---\nname: eda\nstage: exploration\nowner: Ronald Fisher\ncreated: 2022-04-01\n---\n\n# eda\n",
+    )
+    .unwrap();
+    fs::write("explorations/eda/notebooks/eda.ipynb", "{}").unwrap();
+    fs::write("explorations/eda/train.py", "print('hi')").unwrap();
+
+    let mut manifest = Manifest::default();
+    manifest.add_dependencies(
+        &id("explorations/eda"),
+        &[id("data_sources/iris"), id("explorations/cleaning")],
+    );
+
+    manifest
+}
+
+// ++++++++++++++ //
+// add_provenance //
+// ++++++++++++++ //
+#[test]
+fn adds_provenance_section() {
+    assert_eq!(
+        add_provenance("# eda\n\n## Findings\nIt works\n", "Promoted to models"),
+        "# eda\n\n## Findings\nIt works\n\n## Provenance\n\nPromoted to models\n"
+    );
+}
+
+#[test]
+fn adds_to_existing_provenance_section() {
+    assert_eq!(
+        add_provenance(
+            "# eda\n\n## Provenance\n\nPromoted to models\n\n## Findings\nIt works\n",
+            "Promoted to products"
+        ),
+        "# eda\n\n## Provenance\n\nPromoted to models\nPromoted to products\n\n## Findings\nIt works\n"
+    );
+}
+
+// ++++++++ //
+// validate //
+// ++++++++ //
+#[test]
+#[should_panic(expected = "Can't promote explorations/eda to the data-source stage")]
+fn earlier_stage_panics() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut promote = promote(&[]);
+            promote.to = Stage::DataSource;
+            promote.validate(&NamingConfig::default());
+        },
+        true,
+    )
+}
+
+#[test]
+#[should_panic(expected = "explorations/eda/model.pkl doesn't exist")]
+fn missing_file_to_copy_panics() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            promote(&["model.pkl"]).validate(&NamingConfig::default());
+        },
+        true,
+    )
+}
+
+#[test]
+#[should_panic(expected = "/etc/passwd is outside explorations/eda")]
+fn absolute_file_to_copy_panics() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            promote(&["/etc/passwd"]).validate(&NamingConfig::default());
+        },
+        true,
+    )
+}
+
+#[test]
+#[should_panic(expected = "../eda/README.md is outside explorations/eda")]
+fn parent_file_to_copy_panics() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            promote(&["../eda/README.md"]).validate(&NamingConfig::default());
+        },
+        true,
+    )
+}
+
+#[test]
+#[should_panic(expected = "README.md would replace the new component's document")]
+fn main_document_to_copy_panics() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            promote(&["README.md"]).validate(&NamingConfig::default());
+        },
+        true,
+    )
+}
+
+// +++++ //
+// named //
+// +++++ //
+#[test]
+fn named_adds_stage_prefix() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+            fs::create_dir_all("models/001_baseline").unwrap();
+            let naming = NamingConfig {
+                prefixes: [(String::from("models"), Prefix::Number)]
+                    .into_iter()
+                    .collect(),
+                ..NamingConfig::default()
+            };
+
+            assert_eq!(promote(&[]).named(&naming).new_name, "002_classifier");
+            assert_eq!(
+                promote(&[]).named(&NamingConfig::default()).new_name,
+                "classifier"
+            );
+        },
+        false,
+    )
+}
+
+// ++++++ //
+// create //
+// ++++++ //
+#[test]
+fn carries_over_data_sources_and_owner() {
+    test_in_tmp_dir(
+        || {
+            let mut manifest = setup_project();
+
+            promote(&[]).create(&mut manifest);
+
+            assert_eq!(
+                manifest.dependencies[&id("models/classifier")],
+                [id("data_sources/iris")].into()
+            );
+            let readme = fs::read_to_string("models/classifier/README.md").unwrap();
+            assert!(readme.contains("name: classifier\nstage: model\nowner: Ronald Fisher\n"));
+        },
+        false,
+    )
+}
+
+// ++++++++++ //
+// copy_files //
+// ++++++++++ //
+#[test]
+fn copies_files_and_folders() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+            fs::create_dir_all("models/classifier").unwrap();
+
+            promote(&["notebooks", "train.py"]).copy_files();
+
+            assert!(path::Path::new("models/classifier/notebooks/eda.ipynb").exists());
+            assert!(path::Path::new("models/classifier/train.py").exists());
+            assert!(path::Path::new("explorations/eda/train.py").exists());
+        },
+        false,
+    )
+}
+
+// ++++++++++++++++ //
+// write_provenance //
+// ++++++++++++++++ //
+#[test]
+fn references_written_both_ways() {
+    test_in_tmp_dir(
+        || {
+            let mut manifest = setup_project();
+            let promote = promote(&[]);
+
+            promote.create(&mut manifest);
+            promote.write_provenance();
+
+            assert!(fs::read_to_string("models/classifier/README.md")
+                .unwrap()
+                .contains("Promoted from [explorations/eda](../../explorations/eda/README.md)"));
+            assert!(fs::read_to_string("explorations/eda/README.md")
+                .unwrap()
+                .contains("Promoted to [models/classifier](../../models/classifier/README.md)"));
+        },
+        false,
+    )
+}
@@ -0,0 +1,56 @@
+use std::io;
+
+/// Asks a yes or no question
+///
+/// Anything other than `y` or `yes`, in any case, is treated as no so
+/// nothing happens by accident.
+///
+/// # Arguments
+///
+/// * `question` - The question to print
+/// * `input` - Where the answer is read from
+///
+/// # Return value
+///
+/// Whether the user answered yes
+pub fn confirm<R: io::BufRead>(question: &str, mut input: R) -> bool {
+    println!("{} [y/N]", question);
+
+    let mut answer = String::new();
+    input
+        .read_line(&mut answer)
+        .expect("Unable to read the answer");
+
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Asks a question with a free text answer
+///
+/// # Arguments
+///
+/// * `question` - The question to print
+/// * `default` - The answer used when nothing is entered
+/// * `input` - Where the answer is read from
+///
+/// # Return value
+///
+/// The trimmed answer, or the default if the answer was blank
+pub fn ask<R: io::BufRead>(question: &str, default: Option<&str>, mut input: R) -> Option<String> {
+    match default {
+        Some(default) => println!("{} [{}]", question, default),
+        None => println!("{}", question),
+    }
+
+    let mut answer = String::new();
+    input
+        .read_line(&mut answer)
+        .expect("Unable to read the answer");
+
+    match answer.trim() {
+        "" => default.map(|default| default.to_string()),
+        answer => Some(answer.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,36 @@
+use rstest::rstest;
+
+use super::*;
+
+// +++++++ //
+// confirm //
+// +++++++ //
+#[rstest]
+#[case("y\n", true)]
+#[case("Yes\n", true)]
+#[case("  y  \n", true)]
+#[case("n\n", false)]
+#[case("\n", false)]
+#[case("", false)]
+fn confirm_reads_answer(#[case] answer: &str, #[case] expected: bool) {
+    assert_eq!(confirm("Continue?", answer.as_bytes()), expected);
+}
+
+// +++ //
+// ask //
+// +++ //
+#[rstest]
+#[case("model\n", Some("data-source"), Some("model"))]
+#[case("  model  \n", None, Some("model"))]
+#[case("\n", Some("data-source"), Some("data-source"))]
+#[case("", None, None)]
+fn ask_reads_answer(
+    #[case] answer: &str,
+    #[case] default: Option<&str>,
+    #[case] expected: Option<&str>,
+) {
+    assert_eq!(
+        ask("Which stage?", default, answer.as_bytes()),
+        expected.map(String::from)
+    );
+}
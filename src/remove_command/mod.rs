@@ -0,0 +1,464 @@
+use clap::{AppSettings, Args, Subcommand};
+use console::Style;
+use ptree::{item, TreeBuilder};
+use std::{fs, io, path};
+
+use crate::component::{ComponentId, Stage};
+use crate::front_matter;
+use crate::lineage_command::used_by;
+use crate::manifest::{Manifest, TrashEntry};
+use crate::prompt;
+
+/// The folder in the project root that removed components are moved into
+pub const TRASH_FOLDER: &str = ".trash";
+
+/// Lists every file within a folder
+///
+/// # Return value
+///
+/// The paths of the files relative to the folder, joined with `/` and sorted
+fn _list_files(folder: &path::Path) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut to_visit = vec![(folder.to_path_buf(), String::new())];
+
+    while let Some((current, prefix)) = to_visit.pop() {
+        let entries = match fs::read_dir(&current) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+
+            if entry.path().is_dir() {
+                to_visit.push((entry.path(), format!("{}/", name)));
+            } else {
+                files.push(name);
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
+
+#[derive(Args, Clone)]
+pub struct Remove {
+    /// The stage the component belongs to
+    #[clap(value_enum)]
+    pub stage: Stage,
+    /// The name of the component
+    #[clap(value_parser)]
+    pub name: String,
+    /// Remove the component without asking for confirmation, unless only the
+    /// start of its name was given
+    #[clap(short, long)]
+    pub yes: bool,
+    /// Remove a component from the stage's archive
+    #[clap(short, long)]
+    pub archived: bool,
+}
+
+impl Remove {
+    /// Finds the component the name refers to, see [ComponentId::find] and
+    /// [ComponentId::find_archived]
+    ///
+    /// When only the start of the name was given the component that was
+    /// found is printed and `--yes` is ignored, so nothing is removed without
+    /// the user seeing which component it is.
+    ///
+    /// # Return value
+    ///
+    /// The removal with the name of the component that was found
+    ///
+    /// # Errors
+    ///
+    /// * The component is archived but `--archived` wasn't passed
+    /// * The component doesn't exist or the name is ambiguous
+    pub fn resolved(&self) -> Remove {
+        let component = self.id();
+        if !self.archived && !component.exists() && component.archive_path().is_dir() {
+            eprintln!(
+                "{} {}",
+                component,
+                Style::new()
+                    .color256(9)
+                    .apply_to("is archived, pass --archived to remove it")
+            );
+            panic!("{} is archived", component);
+        }
+
+        let found = if self.archived {
+            ComponentId::find_archived(self.stage, &self.name)
+        } else {
+            ComponentId::find(self.stage, &self.name)
+        };
+        if found.name == self.name {
+            return self.clone();
+        }
+
+        println!(
+            "{} {} {}",
+            self.name,
+            Style::new().color256(220).apply_to("matches"),
+            found
+        );
+        Remove {
+            name: found.name,
+            yes: false,
+            ..self.clone()
+        }
+    }
+
+    /// The id of the component being removed
+    pub fn id(&self) -> ComponentId {
+        ComponentId::new(self.stage, &self.name)
+    }
+
+    /// The component's folder, within the stage's archive when `--archived`
+    /// was passed
+    fn _folder(&self) -> path::PathBuf {
+        if self.archived {
+            self.id().archive_path()
+        } else {
+            self.id().path()
+        }
+    }
+
+    /// Lists the files that will be moved to the trash
+    ///
+    /// # Errors
+    ///
+    /// * The component doesn't exist
+    pub fn files(&self) -> Vec<String> {
+        let component = self.id();
+
+        if !self._folder().is_dir() {
+            eprintln!(
+                "{} {}",
+                component,
+                Style::new().color256(9).apply_to(if self.archived {
+                    "isn't an archived component in this project"
+                } else {
+                    "isn't a component in this project"
+                })
+            );
+            panic!("{} doesn't exist", component);
+        }
+
+        _list_files(&self._folder())
+    }
+
+    /// Creates a tree showing what will be removed
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - The files within the component's folder
+    pub fn create_tree(&self, files: &[String]) -> item::StringItem {
+        let gold = Style::new().color256(220);
+        let red = Style::new().color256(9);
+
+        let mut tree = TreeBuilder::new(format!("{} {}", gold.apply_to("\u{1F5BF}"), self.id()));
+        for file in files.iter() {
+            tree.add_empty_child(format!("{} {}", red.apply_to("\u{1F5CE}"), file));
+        }
+
+        tree.build()
+    }
+
+    /// Asks the user to confirm the component should be removed
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest holding the dependencies
+    /// * `input` - Where the answer is read from
+    ///
+    /// # Return value
+    ///
+    /// Whether the user answered yes, always true when `--yes` was passed
+    pub fn confirm<R: io::BufRead>(&self, manifest: &Manifest, input: R) -> bool {
+        if self.yes {
+            return true;
+        }
+
+        let users = used_by(manifest, &self.id());
+        let warning = if users.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "{} {}\n",
+                Style::new().color256(220).apply_to("It is still used by"),
+                users
+                    .iter()
+                    .map(|component| component.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        };
+
+        prompt::confirm(
+            &format!(
+                "{}{} will be moved to the trash.\nContinue?",
+                warning,
+                self.id()
+            ),
+            input,
+        )
+    }
+
+    /// Finds a free folder in the trash for the component
+    ///
+    /// Components removed more than once get a number added to their name.
+    fn _trash_path(&self) -> path::PathBuf {
+        let stage_trash = path::Path::new(TRASH_FOLDER).join(self.stage.folder());
+
+        let mut trash_path = stage_trash.join(&self.name);
+        let mut count = 1;
+        while trash_path.exists() {
+            count += 1;
+            trash_path = stage_trash.join(format!("{}-{}", self.name, count));
+        }
+
+        trash_path
+    }
+
+    /// Moves the component to the trash and takes it out of the manifest
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest
+    ///
+    /// # Return value
+    ///
+    /// Where the component was moved to
+    pub fn remove(&self, manifest: &mut Manifest) -> path::PathBuf {
+        let component = self.id();
+        let trash_path = self._trash_path();
+
+        let stage_trash = trash_path.parent().expect("The trash path has a parent");
+        fs::create_dir_all(stage_trash)
+            .unwrap_or_else(|_| panic!("Unable to create {}", stage_trash.display()));
+        fs::rename(self._folder(), &trash_path)
+            .unwrap_or_else(|_| panic!("Unable to move {}", self._folder().display()));
+
+        let users = used_by(manifest, &component);
+        for user in users.iter() {
+            if let Some(uses) = manifest.dependencies.get_mut(user) {
+                uses.remove(&component);
+                if uses.is_empty() {
+                    manifest.dependencies.remove(user);
+                }
+            }
+        }
+
+        let data_source = match self.stage {
+            Stage::DataSource => manifest.data_sources.remove(&self.name),
+            _ => None,
+        };
+
+        let prefix = format!("{}/", self._folder().to_string_lossy().replace('\\', "/"));
+        let documents: Vec<String> = manifest
+            .templates
+            .keys()
+            .filter(|document| document.starts_with(&prefix))
+            .cloned()
+            .collect();
+        let templates = documents
+            .into_iter()
+            .filter_map(|document| {
+                let record = manifest.templates.remove(&document)?;
+                Some((document, record))
+            })
+            .collect();
+
+        manifest.trash.push(TrashEntry {
+            component: component.clone(),
+            path: trash_path.to_string_lossy().replace('\\', "/"),
+            removed: front_matter::today(),
+            uses: manifest.dependencies.remove(&component).unwrap_or_default(),
+            used_by: users,
+            data_source,
+            archived: if self.archived {
+                manifest.archived.remove(&component)
+            } else {
+                None
+            },
+            templates,
+        });
+
+        trash_path
+    }
+}
+
+#[derive(Args)]
+pub struct Trash {
+    #[clap(subcommand)]
+    pub command: Option<TrashCommands>,
+}
+
+#[derive(Subcommand)]
+#[clap(setting = AppSettings::SubcommandRequiredElseHelp)]
+pub enum TrashCommands {
+    /// Lists the components in the trash
+    List(TrashList),
+    /// Puts a removed component back
+    Restore(TrashRestore),
+    /// Permanently deletes everything in the trash
+    Empty(TrashEmpty),
+}
+
+#[derive(Args)]
+pub struct TrashList {}
+
+impl TrashList {
+    /// Formats the components in the trash with a line for each
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest holding the trash
+    pub fn summary(&self, manifest: &Manifest) -> String {
+        if manifest.trash.is_empty() {
+            return String::from("The trash is empty");
+        }
+
+        manifest
+            .trash
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{} removed on {} ({})",
+                    entry.component, entry.removed, entry.path
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[derive(Args)]
+pub struct TrashRestore {
+    /// The stage the component belonged to
+    #[clap(value_enum)]
+    pub stage: Stage,
+    /// The name of the removed component
+    #[clap(value_parser)]
+    pub name: String,
+}
+
+impl TrashRestore {
+    /// The id of the component being restored
+    pub fn id(&self) -> ComponentId {
+        ComponentId::new(self.stage, &self.name)
+    }
+
+    /// Moves the most recently removed copy of the component back and puts
+    /// its details back in the manifest
+    ///
+    /// Components that were removed from the archive go back into it.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest
+    ///
+    /// # Errors
+    ///
+    /// * The component isn't in the trash
+    /// * A component with the same name has been added since
+    pub fn restore(&self, manifest: &mut Manifest) {
+        let component = self.id();
+        let red = Style::new().color256(9);
+
+        let idx = match manifest
+            .trash
+            .iter()
+            .rposition(|entry| entry.component == component)
+        {
+            Some(idx) => idx,
+            None => {
+                eprintln!("{} {}", component, red.apply_to("isn't in the trash"));
+                panic!("{} doesn't exist", component);
+            }
+        };
+
+        let destination = match manifest.trash[idx].archived {
+            Some(_) => component.archive_path(),
+            None => component.path(),
+        };
+        if destination.exists() {
+            eprintln!(
+                "{} {}",
+                component,
+                red.apply_to("already exists, please rename it first")
+            );
+            panic!("{} exists", component);
+        }
+
+        let entry = manifest.trash.remove(idx);
+        if let Some(stage_folder) = destination.parent() {
+            fs::create_dir_all(stage_folder)
+                .unwrap_or_else(|_| panic!("Unable to create {}", stage_folder.display()));
+        }
+        fs::rename(&entry.path, &destination)
+            .unwrap_or_else(|_| panic!("Unable to move {}", entry.path));
+
+        manifest.add_dependencies(&component, &entry.uses.into_iter().collect::<Vec<_>>());
+        for user in entry.used_by.iter() {
+            manifest.add_dependencies(user, std::slice::from_ref(&component));
+        }
+        if let Some(data_source) = entry.data_source {
+            manifest.data_sources.insert(self.name.clone(), data_source);
+        }
+        if let Some(archived) = entry.archived {
+            manifest.archived.insert(component.clone(), archived);
+        }
+        manifest.templates.extend(entry.templates);
+    }
+}
+
+#[derive(Args)]
+pub struct TrashEmpty {
+    /// Empty the trash without asking for confirmation
+    #[clap(short, long)]
+    pub yes: bool,
+}
+
+impl TrashEmpty {
+    /// Asks the user to confirm the trash should be emptied
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest holding the trash
+    /// * `input` - Where the answer is read from
+    pub fn confirm<R: io::BufRead>(&self, manifest: &Manifest, input: R) -> bool {
+        self.yes
+            || prompt::confirm(
+                &format!(
+                    "{} components will be permanently deleted.\nContinue?",
+                    manifest.trash.len()
+                ),
+                input,
+            )
+    }
+
+    /// Deletes the trash folder and forgets the removed components
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest
+    ///
+    /// # Return value
+    ///
+    /// The number of components that were deleted
+    pub fn empty(&self, manifest: &mut Manifest) -> usize {
+        let trash = path::Path::new(TRASH_FOLDER);
+        if trash.exists() {
+            fs::remove_dir_all(trash)
+                .unwrap_or_else(|_| panic!("Unable to delete {}", trash.display()));
+        }
+
+        manifest.trash.drain(..).count()
+    }
+}
+
+#[cfg(test)]
+mod tests;
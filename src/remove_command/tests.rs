@@ -0,0 +1,362 @@
+use std::{fs, path};
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+use crate::manifest::{ArchiveEntry, DataSourceEntry, TemplateRecord};
+
+fn id(value: &str) -> ComponentId {
+    value.parse().unwrap()
+}
+
+fn remove(stage: Stage, name: &str) -> Remove {
+    Remove {
+        stage,
+        name: String::from(name),
+        yes: false,
+        archived: false,
+    }
+}
+
+fn setup_project() -> Manifest {
+    fs::create_dir_all("data_sources/iris/versions").unwrap();
+    fs::write("data_sources/iris/metadata.md", "# iris\n").unwrap();
+    fs::write("data_sources/iris/versions/iris.csv", "id\n").unwrap();
+    fs::create_dir_all("explorations/eda").unwrap();
+
+    let mut manifest = Manifest::default();
+    manifest.add_dependencies(&id("explorations/eda"), &[id("data_sources/iris")]);
+    manifest.data_sources.insert(
+        String::from("iris"),
+        DataSourceEntry {
+            checksums: [(String::from("versions/iris.csv"), String::from("abc"))].into(),
+            ..DataSourceEntry::default()
+        },
+    );
+
+    manifest
+}
+
+fn classifier_manifest(archived: bool) -> Manifest {
+    let mut manifest = Manifest::default();
+    if archived {
+        manifest.archived.insert(
+            id("models/classifier"),
+            ArchiveEntry {
+                archived: String::from("2022-04-01"),
+                reason: None,
+            },
+        );
+    } else {
+        manifest.templates.insert(
+            String::from("models/classifier/README.md"),
+            TemplateRecord {
+                template: String::from("models/README.md"),
+                version: String::from("abc123"),
+                name: String::from("classifier"),
+            },
+        );
+    }
+
+    manifest
+}
+
+// +++++ //
+// files //
+// +++++ //
+#[test]
+fn lists_files_to_remove() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            assert_eq!(
+                remove(Stage::DataSource, "iris").files(),
+                vec!["metadata.md", "versions/iris.csv"]
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "models/classifier doesn't exist")]
+fn missing_component_panics() {
+    test_in_tmp_dir(
+        || {
+            remove(Stage::Model, "classifier").files();
+        },
+        true,
+    )
+}
+
+// ++++++++ //
+// resolved //
+// ++++++++ //
+#[test]
+fn resolved_prefix_needs_confirmation() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let exact = Remove {
+                yes: true,
+                ..remove(Stage::DataSource, "iris")
+            }
+            .resolved();
+            assert!(exact.yes);
+
+            let prefix = Remove {
+                yes: true,
+                ..remove(Stage::DataSource, "ir")
+            }
+            .resolved();
+            assert_eq!(prefix.name, "iris");
+            assert!(!prefix.yes);
+        },
+        false,
+    )
+}
+
+// +++++++ //
+// confirm //
+// +++++++ //
+#[test]
+fn confirm_reads_answer() {
+    let manifest = Manifest::default();
+
+    assert!(remove(Stage::Model, "classifier").confirm(&manifest, "y\n".as_bytes()));
+    assert!(!remove(Stage::Model, "classifier").confirm(&manifest, "\n".as_bytes()));
+}
+
+#[test]
+fn confirm_skipped_with_yes() {
+    let mut model = remove(Stage::Model, "classifier");
+    model.yes = true;
+
+    assert!(model.confirm(&Manifest::default(), "n\n".as_bytes()));
+}
+
+// ++++++ //
+// remove //
+// ++++++ //
+#[test]
+fn remove_moves_to_trash_and_updates_manifest() {
+    test_in_tmp_dir(
+        || {
+            let mut manifest = setup_project();
+
+            let trash_path = remove(Stage::DataSource, "iris").remove(&mut manifest);
+
+            assert_eq!(trash_path, path::PathBuf::from(".trash/data_sources/iris"));
+            assert!(!path::Path::new("data_sources/iris").exists());
+            assert!(path::Path::new(".trash/data_sources/iris/versions/iris.csv").exists());
+
+            assert!(manifest.dependencies.is_empty());
+            assert!(manifest.data_sources.is_empty());
+            assert_eq!(manifest.trash.len(), 1);
+            assert_eq!(manifest.trash[0].component, id("data_sources/iris"));
+            assert_eq!(manifest.trash[0].path, ".trash/data_sources/iris");
+            assert_eq!(manifest.trash[0].used_by, [id("explorations/eda")].into());
+            assert!(manifest.trash[0].data_source.is_some());
+        },
+        false,
+    )
+}
+
+#[test]
+fn removing_twice_keeps_both_copies() {
+    test_in_tmp_dir(
+        || {
+            let mut manifest = Manifest::default();
+
+            fs::create_dir_all("models/classifier").unwrap();
+            remove(Stage::Model, "classifier").remove(&mut manifest);
+            fs::create_dir_all("models/classifier").unwrap();
+            let trash_path = remove(Stage::Model, "classifier").remove(&mut manifest);
+
+            assert_eq!(
+                trash_path,
+                path::PathBuf::from(".trash/models/classifier-2")
+            );
+            assert!(path::Path::new(".trash/models/classifier").exists());
+        },
+        false,
+    )
+}
+
+// +++++++++++++ //
+// trash restore //
+// +++++++++++++ //
+#[test]
+fn restore_puts_component_and_manifest_back() {
+    test_in_tmp_dir(
+        || {
+            let original = setup_project();
+            let mut manifest = setup_project();
+
+            remove(Stage::DataSource, "iris").remove(&mut manifest);
+            TrashRestore {
+                stage: Stage::DataSource,
+                name: String::from("iris"),
+            }
+            .restore(&mut manifest);
+
+            assert!(path::Path::new("data_sources/iris/versions/iris.csv").exists());
+            assert_eq!(manifest, original);
+        },
+        false,
+    )
+}
+
+#[test]
+fn restore_puts_template_records_back() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("models/classifier").unwrap();
+            let original = classifier_manifest(false);
+            let mut manifest = classifier_manifest(false);
+
+            remove(Stage::Model, "classifier").remove(&mut manifest);
+
+            assert!(manifest.templates.is_empty());
+            assert_eq!(manifest.trash[0].templates.len(), 1);
+
+            TrashRestore {
+                stage: Stage::Model,
+                name: String::from("classifier"),
+            }
+            .restore(&mut manifest);
+
+            assert_eq!(manifest, original);
+        },
+        false,
+    )
+}
+
+#[test]
+fn restore_puts_archived_component_back_in_archive() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("models/_archive/classifier").unwrap();
+            let original = classifier_manifest(true);
+            let mut manifest = classifier_manifest(true);
+
+            let removal = Remove {
+                archived: true,
+                ..remove(Stage::Model, "classifier")
+            };
+            assert!(removal.files().is_empty());
+            removal.remove(&mut manifest);
+
+            assert!(!path::Path::new("models/_archive/classifier").exists());
+            assert!(manifest.archived.is_empty());
+            assert!(manifest.trash[0].archived.is_some());
+
+            TrashRestore {
+                stage: Stage::Model,
+                name: String::from("classifier"),
+            }
+            .restore(&mut manifest);
+
+            assert!(path::Path::new("models/_archive/classifier").is_dir());
+            assert!(!path::Path::new("models/classifier").exists());
+            assert_eq!(manifest, original);
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "models/classifier doesn't exist")]
+fn restore_missing_from_trash_panics() {
+    test_in_tmp_dir(
+        || {
+            TrashRestore {
+                stage: Stage::Model,
+                name: String::from("classifier"),
+            }
+            .restore(&mut Manifest::default());
+        },
+        true,
+    )
+}
+
+#[test]
+#[should_panic(expected = "models/classifier exists")]
+fn restore_panics_when_name_taken() {
+    test_in_tmp_dir(
+        || {
+            let mut manifest = Manifest::default();
+
+            fs::create_dir_all("models/classifier").unwrap();
+            remove(Stage::Model, "classifier").remove(&mut manifest);
+            fs::create_dir_all("models/classifier").unwrap();
+
+            TrashRestore {
+                stage: Stage::Model,
+                name: String::from("classifier"),
+            }
+            .restore(&mut manifest);
+        },
+        true,
+    )
+}
+
+// +++++++++++ //
+// trash empty //
+// +++++++++++ //
+#[test]
+fn empty_deletes_trash() {
+    test_in_tmp_dir(
+        || {
+            let mut manifest = setup_project();
+            remove(Stage::DataSource, "iris").remove(&mut manifest);
+
+            let deleted = TrashEmpty { yes: true }.empty(&mut manifest);
+
+            assert_eq!(deleted, 1);
+            assert!(!path::Path::new(TRASH_FOLDER).exists());
+            assert!(manifest.trash.is_empty());
+        },
+        false,
+    )
+}
+
+// ++++++++++ //
+// trash list //
+// ++++++++++ //
+#[test]
+fn list_summarises_trash() {
+    test_in_tmp_dir(
+        || {
+            let mut manifest = setup_project();
+            assert_eq!(TrashList {}.summary(&manifest), "The trash is empty");
+
+            remove(Stage::DataSource, "iris").remove(&mut manifest);
+
+            assert_eq!(
+                TrashList {}.summary(&manifest),
+                format!(
+                    "data_sources/iris removed on {} (.trash/data_sources/iris)",
+                    front_matter::today()
+                )
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn trash_round_trips_through_manifest_file() {
+    test_in_tmp_dir(
+        || {
+            let mut manifest = setup_project();
+            remove(Stage::DataSource, "iris").remove(&mut manifest);
+
+            manifest.save();
+
+            assert_eq!(Manifest::load(), manifest);
+        },
+        false,
+    )
+}
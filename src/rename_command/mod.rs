@@ -0,0 +1,520 @@
+use clap::Args;
+use console::Style;
+use ptree::{item, TreeBuilder};
+use std::{env, fs, path};
+
+use crate::component::{list_components, ComponentId, Stage};
+use crate::front_matter;
+use crate::manifest::Manifest;
+use crate::naming::{self, NamingConfig, Prefix};
+
+/// Replaces a heading that is exactly the old name
+///
+/// Only level one headings are changed as that's where geoff inserts the
+/// name, so sections that happen to mention the name are left alone.
+///
+/// # Arguments
+///
+/// * `contents` - The contents of a markdown document
+/// * `old` - The name geoff inserted
+/// * `new` - The name to replace it with
+pub fn rewrite_heading(contents: &str, old: &str, new: &str) -> String {
+    contents
+        .split_inclusive('\n')
+        .map(|line| {
+            let text = line.trim_end_matches(['\r', '\n']);
+
+            if text.trim_end() == format!("# {}", old) {
+                format!("# {}{}", new, &line[text.len()..])
+            } else {
+                line.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Replaces references to a path, e.g in links and lists
+///
+/// A reference only matches when it isn't followed by more of a name, so
+/// renaming `data_sources/iris` leaves `data_sources/iris_2022` alone.
+///
+/// # Arguments
+///
+/// * `contents` - The contents of a document
+/// * `old` - The path as it's currently written
+/// * `new` - The path to replace it with
+pub fn rewrite_references(contents: &str, old: &str, new: &str) -> String {
+    let mut rewritten = String::with_capacity(contents.len());
+    let mut rest = contents;
+
+    while let Some(idx) = rest.find(old) {
+        let after = &rest[idx + old.len()..];
+        let is_whole = !after
+            .chars()
+            .next()
+            .map(|c| c.is_alphanumeric() || c == '_' || c == '-')
+            .unwrap_or(false);
+        let is_start = !rest[..idx]
+            .chars()
+            .last()
+            .map(|c| c.is_alphanumeric() || c == '_' || c == '-')
+            .unwrap_or(false);
+
+        rewritten.push_str(&rest[..idx]);
+        rewritten.push_str(if is_whole && is_start { new } else { old });
+        rest = after;
+    }
+
+    rewritten.push_str(rest);
+    rewritten
+}
+
+/// Replaces a field in a document's front matter if it has the old value
+///
+/// # Arguments
+///
+/// * `contents` - The contents of a markdown document
+/// * `field` - The name of the field, e.g `name`
+/// * `old` - The value geoff inserted
+/// * `new` - The value to replace it with
+pub fn rewrite_front_matter(contents: &str, field: &str, old: &str, new: &str) -> String {
+    let mut fields = match front_matter::parse::<serde_yaml::Mapping>(contents) {
+        Ok(fields) => fields,
+        Err(_) => return contents.to_string(),
+    };
+
+    match fields.get_mut(field) {
+        Some(value) if value.as_str() == Some(old) => {
+            *value = serde_yaml::Value::String(new.to_string());
+            front_matter::replace(contents, &fields)
+        }
+        _ => contents.to_string(),
+    }
+}
+
+/// Lists the markdown documents in the project
+///
+/// The root documents and every markdown file within the stage folders are
+/// included. The trash is left out.
+pub fn list_documents() -> Vec<path::PathBuf> {
+    let mut documents: Vec<path::PathBuf> = ["README.md", "project_scoping.md"]
+        .iter()
+        .map(path::PathBuf::from)
+        .filter(|document| document.is_file())
+        .collect();
+
+    let mut to_visit: Vec<path::PathBuf> = Stage::ALL
+        .iter()
+        .map(|stage| path::PathBuf::from(stage.folder()))
+        .collect();
+
+    while let Some(folder) = to_visit.pop() {
+        for entry in fs::read_dir(&folder)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok())
+        {
+            let entry_path = entry.path();
+
+            if entry_path.is_dir() {
+                to_visit.push(entry_path);
+            } else if entry_path
+                .extension()
+                .map(|ext| ext == "md")
+                .unwrap_or(false)
+            {
+                documents.push(entry_path);
+            }
+        }
+    }
+
+    documents.sort();
+    documents
+}
+
+/// Writes a friendly error and panics when the new name is already taken
+fn _name_taken(folder: &path::Path) -> ! {
+    eprintln!(
+        "{} {}",
+        folder.display(),
+        Style::new()
+            .color256(9)
+            .apply_to("already exists, please pick a different name")
+    );
+    panic!("{} exists", folder.display());
+}
+
+/// Creates a tree of the documents that were rewritten
+fn _rewritten_tree(root: String, documents: &[path::PathBuf]) -> item::StringItem {
+    let hd = Style::new().color256(194);
+
+    let mut tree = TreeBuilder::new(root);
+    for document in documents.iter() {
+        tree.add_empty_child(format!(
+            "{} {}",
+            hd.apply_to("\u{1F5CE}"),
+            document.to_string_lossy().replace('\\', "/")
+        ));
+    }
+
+    tree.build()
+}
+
+#[derive(Args)]
+pub struct Rename {
+    /// The stage the component belongs to
+    #[clap(value_enum)]
+    pub stage: Stage,
+    /// The current name of the component
+    #[clap(value_parser)]
+    pub old: String,
+    /// The new name for the component
+    #[clap(value_parser)]
+    pub new: String,
+}
+
+impl Rename {
+    /// The component's current id
+    pub fn old_id(&self) -> ComponentId {
+        ComponentId::new(self.stage, &self.old)
+    }
+
+    /// The component's new id
+    pub fn new_id(&self) -> ComponentId {
+        ComponentId::new(self.stage, &self.new)
+    }
+
+    /// Moves the component's folder to its new name
+    ///
+    /// # Arguments
+    ///
+    /// * `naming` - The project's naming rules the new name must follow
+    ///
+    /// # Errors
+    ///
+    /// * The new name breaks one of the rules
+    /// * The component doesn't exist
+    /// * A component already has the new name
+    pub fn move_folder(&self, naming: &NamingConfig) {
+        naming::check(&self.new, Some(self.stage), naming, false);
+        let (old, new) = (self.old_id(), self.new_id());
+
+        if !old.exists() {
+            eprintln!(
+                "{} {}",
+                old,
+                Style::new()
+                    .color256(9)
+                    .apply_to("isn't a component in this project")
+            );
+            panic!("{} doesn't exist", old);
+        }
+
+        if new.path().exists() {
+            _name_taken(&new.path());
+        }
+
+        fs::rename(old.path(), new.path())
+            .unwrap_or_else(|_| panic!("Unable to move {}", old.path().display()));
+    }
+
+    /// Rewrites the name where geoff inserted it
+    ///
+    /// The component's own document gets a new heading and front matter name,
+    /// and references to the component's folder are updated in every
+    /// document in the project.
+    ///
+    /// # Return value
+    ///
+    /// The documents that were changed
+    pub fn rewrite_documents(&self) -> Vec<path::PathBuf> {
+        let (old, new) = (self.old_id(), self.new_id());
+        let own_document = new.document();
+
+        list_documents()
+            .into_iter()
+            .filter(|document| {
+                let contents = match fs::read_to_string(document) {
+                    Ok(contents) => contents,
+                    Err(_) => return false,
+                };
+
+                let mut rewritten =
+                    rewrite_references(&contents, &old.to_string(), &new.to_string());
+                if document == &own_document {
+                    rewritten = rewrite_heading(&rewritten, &self.old, &self.new);
+                    rewritten = rewrite_front_matter(&rewritten, "name", &self.old, &self.new);
+                }
+
+                if rewritten == contents {
+                    return false;
+                }
+
+                fs::write(document, rewritten)
+                    .unwrap_or_else(|_| panic!("Unable to update {}", document.display()));
+                true
+            })
+            .collect()
+    }
+
+    /// Updates the manifest and any recorded dependencies
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest
+    pub fn update_manifest(&self, manifest: &mut Manifest) {
+        manifest.rename_component(&self.old_id(), &self.new_id());
+    }
+
+    /// Creates a tree of the documents that were rewritten
+    ///
+    /// # Arguments
+    ///
+    /// * `documents` - The documents that were changed
+    pub fn create_tree(&self, documents: &[path::PathBuf]) -> item::StringItem {
+        _rewritten_tree(
+            format!(
+                "{} {}",
+                Style::new().color256(220).apply_to("\u{1F5BF}"),
+                self.new_id()
+            ),
+            documents,
+        )
+    }
+}
+
+#[derive(Args)]
+pub struct RenameProject {
+    /// The new name for the project
+    #[clap(value_parser)]
+    pub new: String,
+}
+
+impl RenameProject {
+    /// The current name of the project
+    ///
+    /// The name is read from the README's front matter, falling back to the
+    /// name of the project folder.
+    pub fn old_name(&self) -> String {
+        fs::read_to_string("README.md")
+            .ok()
+            .and_then(|contents| {
+                front_matter::parse::<front_matter::ProjectFrontMatter>(&contents).ok()
+            })
+            .map(|front_matter| front_matter.project)
+            .unwrap_or_else(|| {
+                env::current_dir()
+                    .expect("Unable to get current directory")
+                    .file_name()
+                    .expect("The project folder has a name")
+                    .to_string_lossy()
+                    .to_string()
+            })
+    }
+
+    /// Checks the new name can be used before anything is changed
+    ///
+    /// # Errors
+    ///
+    /// * The new name breaks one of the rules
+    pub fn validate(&self) {
+        naming::check(&self.new, None, &NamingConfig::default(), false);
+    }
+
+    /// Rewrites the project name in the root documents
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - The current name of the project
+    ///
+    /// # Return value
+    ///
+    /// The documents that were changed
+    pub fn rewrite_documents(&self, old: &str) -> Vec<path::PathBuf> {
+        ["README.md", "project_scoping.md"]
+            .iter()
+            .map(path::PathBuf::from)
+            .filter(|document| {
+                let contents = match fs::read_to_string(document) {
+                    Ok(contents) => contents,
+                    Err(_) => return false,
+                };
+
+                let rewritten = rewrite_front_matter(
+                    &rewrite_heading(&contents, old, &self.new),
+                    "project",
+                    old,
+                    &self.new,
+                );
+
+                if rewritten == contents {
+                    return false;
+                }
+
+                fs::write(document, rewritten)
+                    .unwrap_or_else(|_| panic!("Unable to update {}", document.display()));
+                true
+            })
+            .collect()
+    }
+
+    /// Moves the project folder to its new name
+    ///
+    /// The working directory is changed to the new folder.
+    ///
+    /// # Errors
+    ///
+    /// * A folder next to the project already has the new name
+    ///
+    /// # Return value
+    ///
+    /// The new location of the project
+    pub fn move_folder(&self) -> path::PathBuf {
+        let current = env::current_dir().expect("Unable to get current directory");
+        let renamed = current
+            .parent()
+            .expect("The project folder has a parent")
+            .join(&self.new);
+
+        if renamed == current {
+            return current;
+        }
+
+        if renamed.exists() {
+            _name_taken(&renamed);
+        }
+
+        fs::rename(&current, &renamed)
+            .unwrap_or_else(|_| panic!("Unable to move {}", current.display()));
+        env::set_current_dir(&renamed).expect("Unable to change to the renamed project");
+
+        renamed
+    }
+
+    /// Creates a tree of the documents that were rewritten
+    ///
+    /// # Arguments
+    ///
+    /// * `documents` - The documents that were changed
+    pub fn create_tree(&self, documents: &[path::PathBuf]) -> item::StringItem {
+        _rewritten_tree(
+            format!("{}", Style::new().bold().apply_to(&self.new)),
+            documents,
+        )
+    }
+}
+
+#[derive(Args)]
+pub struct Renumber {
+    /// The stage to renumber
+    #[clap(value_enum)]
+    pub stage: Stage,
+    /// Shows what would be renamed without renaming anything
+    #[clap(short, long)]
+    pub dry_run: bool,
+}
+
+impl Renumber {
+    /// Works out the renames that number the stage's components from 1
+    /// without gaps or repeats
+    ///
+    /// Components keep their order, with ones that share a number ordered by
+    /// name. Components without a number and archived components are left
+    /// alone. The renames are ordered so none of them moves a folder onto
+    /// one that hasn't been moved yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `naming` - The project's naming rules
+    ///
+    /// # Errors
+    ///
+    /// * The stage isn't numbered in the project's naming rules
+    /// * A new name breaks one of the rules
+    pub fn plan(&self, naming: &NamingConfig) -> Vec<Rename> {
+        if naming.prefix(self.stage) != Some(Prefix::Number) {
+            eprintln!(
+                "{} {}",
+                self.stage.folder(),
+                Style::new()
+                    .color256(9)
+                    .apply_to("aren't numbered, see the [naming.prefixes] table of geoff.toml")
+            );
+            panic!("{} aren't numbered", self.stage.folder());
+        }
+
+        let mut numbered: Vec<(u32, String, char, String)> = list_components(self.stage)
+            .into_iter()
+            .filter_map(|component| {
+                naming::split_number(&component.name).map(|(number, separator, rest)| {
+                    (number, component.name.clone(), separator, rest.to_string())
+                })
+            })
+            .collect();
+        numbered.sort();
+
+        let mut moves: Vec<(u32, u32, Rename)> = numbered
+            .into_iter()
+            .zip(1..)
+            .filter_map(|((number, old, separator, rest), position)| {
+                let new = naming::numbered(position, separator, &rest);
+                (new != old).then(|| {
+                    naming::check(&new, Some(self.stage), naming, false);
+                    (
+                        number,
+                        position,
+                        Rename {
+                            stage: self.stage,
+                            old,
+                            new,
+                        },
+                    )
+                })
+            })
+            .collect();
+
+        // Moves to a lower number go first, lowest first, so each one's
+        // folder has already been freed. Moves to a higher number go after,
+        // highest first, for the same reason.
+        moves.sort_by_key(|(number, position, _)| {
+            if position < number {
+                (0, i64::from(*position))
+            } else {
+                (1, -i64::from(*position))
+            }
+        });
+
+        moves.into_iter().map(|(_, _, rename)| rename).collect()
+    }
+
+    /// Creates a tree of the components that were renumbered
+    ///
+    /// # Arguments
+    ///
+    /// * `renames` - The renames that were made
+    pub fn create_tree(&self, renames: &[Rename]) -> item::StringItem {
+        let gold = Style::new().color256(220);
+
+        let mut sorted: Vec<&Rename> = renames.iter().collect();
+        sorted.sort_by(|a, b| a.new.cmp(&b.new));
+
+        let mut tree = TreeBuilder::new(format!(
+            "{} {}",
+            gold.apply_to("\u{1F5BF}"),
+            self.stage.folder()
+        ));
+        for rename in sorted {
+            tree.add_empty_child(format!(
+                "{} {} (was {})",
+                gold.apply_to("\u{1F5BF}"),
+                rename.new,
+                rename.old
+            ));
+        }
+
+        tree.build()
+    }
+}
+
+#[cfg(test)]
+mod tests;
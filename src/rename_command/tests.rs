@@ -0,0 +1,290 @@
+use std::fs;
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+
+fn rename(stage: Stage, old: &str, new: &str) -> Rename {
+    Rename {
+        stage,
+        old: String::from(old),
+        new: String::from(new),
+    }
+}
+
+// +++++++++++++++ //
+// rewrite_heading //
+// +++++++++++++++ //
+#[test]
+fn rewrites_matching_heading() {
+    assert_eq!(
+        rewrite_heading("# iris\n\n## iris details\n", "iris", "flowers"),
+        "# flowers\n\n## iris details\n"
+    );
+}
+
+#[test]
+fn leaves_other_headings_alone() {
+    assert_eq!(
+        rewrite_heading("# iris dataset\n", "iris", "flowers"),
+        "# iris dataset\n"
+    );
+}
+
+// ++++++++++++++++++ //
+// rewrite_references //
+// ++++++++++++++++++ //
+#[test]
+fn rewrites_links_and_lists() {
+    assert_eq!(
+        rewrite_references(
+            "* [iris](data_sources/iris/metadata.md)\n* data_sources/iris\n",
+            "data_sources/iris",
+            "data_sources/flowers"
+        ),
+        "* [iris](data_sources/flowers/metadata.md)\n* data_sources/flowers\n"
+    );
+}
+
+#[test]
+fn leaves_longer_names_alone() {
+    assert_eq!(
+        rewrite_references(
+            "data_sources/iris_2022 and old_data_sources/iris",
+            "data_sources/iris",
+            "data_sources/flowers"
+        ),
+        "data_sources/iris_2022 and old_data_sources/iris"
+    );
+}
+
+// ++++++++++++++++++++ //
+// rewrite_front_matter //
+// ++++++++++++++++++++ //
+#[test]
+fn rewrites_front_matter_field_in_place() {
+    assert_eq!(
+        rewrite_front_matter(
+            "---\nname: iris\nowner: Ronald Fisher\n---\n\n# iris\n",
+            "name",
+            "iris",
+            "flowers"
+        ),
+        "---\nname: flowers\nowner: Ronald Fisher\n---\n\n# iris\n"
+    );
+}
+
+#[test]
+fn leaves_documents_without_front_matter_alone() {
+    assert_eq!(
+        rewrite_front_matter("# iris\n", "name", "iris", "flowers"),
+        "# iris\n"
+    );
+}
+
+// ++++++ //
+// Rename //
+// ++++++ //
+#[test]
+fn renames_component_and_references() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+            fs::write(
+                "data_sources/iris/metadata.md",
+                "---\nname: iris\n---\n\n# iris\n",
+            )
+            .unwrap();
+            fs::create_dir_all("explorations/eda").unwrap();
+            fs::write(
+                "explorations/eda/README.md",
+                "# eda\n\nUses [iris](../../data_sources/iris/metadata.md)\n",
+            )
+            .unwrap();
+
+            let rename = rename(Stage::DataSource, "iris", "flowers");
+            rename.move_folder(&NamingConfig::default());
+            let documents = rename.rewrite_documents();
+
+            assert_eq!(
+                documents,
+                vec![
+                    path::PathBuf::from("data_sources/flowers/metadata.md"),
+                    path::PathBuf::from("explorations/eda/README.md"),
+                ]
+            );
+            assert_eq!(
+                fs::read_to_string("data_sources/flowers/metadata.md").unwrap(),
+                "---\nname: flowers\n---\n\n# flowers\n"
+            );
+            assert_eq!(
+                fs::read_to_string("explorations/eda/README.md").unwrap(),
+                "# eda\n\nUses [iris](../../data_sources/flowers/metadata.md)\n"
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "models/classifier doesn't exist")]
+fn missing_component_panics() {
+    test_in_tmp_dir(
+        || rename(Stage::Model, "classifier", "predictor").move_folder(&NamingConfig::default()),
+        true,
+    )
+}
+
+#[test]
+#[should_panic(expected = "models/predictor exists")]
+fn taken_name_panics() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("models/classifier").unwrap();
+            fs::create_dir_all("models/predictor").unwrap();
+
+            rename(Stage::Model, "classifier", "predictor").move_folder(&NamingConfig::default())
+        },
+        true,
+    )
+}
+
+// +++++++++++++ //
+// RenameProject //
+// +++++++++++++ //
+#[test]
+fn reads_project_name_from_front_matter() {
+    test_in_tmp_dir(
+        || {
+            fs::write(
+                "README.md",
+                "---\nproject: iris\ncreated: 2022-04-01\n---\n\n# iris\n",
+            )
+            .unwrap();
+
+            let rename = RenameProject {
+                new: String::from("flowers"),
+            };
+
+            assert_eq!(rename.old_name(), "iris");
+        },
+        false,
+    )
+}
+
+#[test]
+fn rewrites_root_documents() {
+    test_in_tmp_dir(
+        || {
+            fs::write(
+                "README.md",
+                "---\nproject: iris\ncreated: 2022-04-01\n---\n\n# iris\n",
+            )
+            .unwrap();
+            fs::write("project_scoping.md", "# iris\n\n## Scope\n").unwrap();
+
+            let rename = RenameProject {
+                new: String::from("flowers"),
+            };
+
+            assert_eq!(rename.rewrite_documents("iris").len(), 2);
+            assert_eq!(
+                fs::read_to_string("README.md").unwrap(),
+                "---\nproject: flowers\ncreated: 2022-04-01\n---\n\n# flowers\n"
+            );
+            assert_eq!(
+                fs::read_to_string("project_scoping.md").unwrap(),
+                "# flowers\n\n## Scope\n"
+            );
+        },
+        false,
+    )
+}
+
+// ++++++++ //
+// Renumber //
+// ++++++++ //
+fn numbered_explorations() -> NamingConfig {
+    NamingConfig {
+        prefixes: [(String::from("explorations"), Prefix::Number)]
+            .into_iter()
+            .collect(),
+        ..NamingConfig::default()
+    }
+}
+
+fn planned(folders: &[&str]) -> Vec<(String, String)> {
+    for folder in folders {
+        fs::create_dir_all(path::Path::new("explorations").join(folder)).unwrap();
+    }
+
+    Renumber {
+        stage: Stage::Exploration,
+        dry_run: false,
+    }
+    .plan(&numbered_explorations())
+    .into_iter()
+    .map(|rename| (rename.old, rename.new))
+    .collect()
+}
+
+#[test]
+fn renumbers_to_close_gaps() {
+    test_in_tmp_dir(
+        || {
+            assert_eq!(
+                planned(&["002_eda", "005_churn", "notes", "_archive/001_old"]),
+                vec![
+                    (String::from("002_eda"), String::from("001_eda")),
+                    (String::from("005_churn"), String::from("002_churn")),
+                ]
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn renumbers_repeats_in_name_order() {
+    test_in_tmp_dir(
+        || {
+            assert_eq!(
+                planned(&["001_eda", "002_churn", "002_age"]),
+                vec![(String::from("002_churn"), String::from("003_churn"))]
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn renames_never_overwrite_unmoved_folders() {
+    test_in_tmp_dir(
+        || {
+            let renames = planned(&["001_eda", "001_churn", "002_eda", "004_eda"]);
+
+            let mut folders: Vec<String> = list_components(Stage::Exploration)
+                .into_iter()
+                .map(|component| component.name)
+                .collect();
+            for (old, new) in renames {
+                assert!(!folders.contains(&new), "{} is still in use", new);
+                folders.retain(|folder| folder != &old);
+                folders.push(new);
+            }
+            folders.sort();
+
+            assert_eq!(folders, vec!["001_churn", "002_eda", "003_eda", "004_eda"]);
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "models aren't numbered")]
+fn unnumbered_stage_panics() {
+    Renumber {
+        stage: Stage::Model,
+        dry_run: false,
+    }
+    .plan(&numbered_explorations());
+}
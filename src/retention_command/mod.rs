@@ -0,0 +1,324 @@
+use chrono::NaiveDate;
+use clap::{AppSettings, Args, Subcommand};
+use console::Style;
+use ptree::{item, TreeBuilder};
+use std::{fs, io, path};
+
+use crate::data_source_command::{
+    data_source_path, find_data_source, list_data_files, list_data_sources,
+};
+use crate::front_matter;
+use crate::manifest::{Manifest, Purge};
+use crate::prompt;
+
+#[derive(Args)]
+pub struct Retention {
+    #[clap(subcommand)]
+    pub command: Option<RetentionCommands>,
+}
+
+#[derive(Subcommand)]
+#[clap(setting = AppSettings::SubcommandRequiredElseHelp)]
+pub enum RetentionCommands {
+    /// Lists the data sources that have expired or will expire soon
+    Check(RetentionCheck),
+    /// Deletes the data files of a data source, keeping its metadata
+    Purge(RetentionPurge),
+}
+
+/// A data source that has expired or will expire soon
+#[derive(Debug, PartialEq)]
+pub struct Expiry {
+    /// The name of the data source
+    pub name: String,
+    /// The date the data files must be deleted by
+    pub expires: NaiveDate,
+    /// The number of days until the data source expires, negative once it
+    /// has expired
+    pub days_left: i64,
+    /// The number of data files still in the data source
+    pub data_files: usize,
+}
+
+impl Expiry {
+    /// Whether the data source is past its expiry date
+    pub fn is_expired(&self) -> bool {
+        self.days_left < 0
+    }
+}
+
+/// Reads the expiry date from a data source's front matter
+///
+/// # Arguments
+///
+/// * `data_source` - The folder of the data source
+///
+/// # Return value
+///
+/// The expiry date, or None if the data source doesn't have one or it
+/// isn't formatted as YYYY-MM-DD
+pub fn expiry_date(data_source: &path::Path) -> Option<NaiveDate> {
+    let expires = front_matter::read_data_source(data_source).ok()?.expires?;
+
+    NaiveDate::parse_from_str(&expires, "%Y-%m-%d").ok()
+}
+
+#[derive(Args)]
+pub struct RetentionCheck {
+    /// Also list data sources that expire within this many days
+    #[clap(short, long, value_parser, default_value_t = 30)]
+    pub within: i64,
+}
+
+impl RetentionCheck {
+    /// Finds the data sources that have expired or expire within the window
+    ///
+    /// # Arguments
+    ///
+    /// * `today` - The date to check the expiry dates against
+    ///
+    /// # Return value
+    ///
+    /// The data sources ordered by expiry date
+    pub fn check(&self, today: NaiveDate) -> Vec<Expiry> {
+        let mut expiries: Vec<Expiry> = list_data_sources()
+            .into_iter()
+            .filter_map(|name| {
+                let data_source = path::Path::new("data_sources").join(&name);
+                let expires = expiry_date(&data_source)?;
+                let days_left = (expires - today).num_days();
+
+                if days_left > self.within {
+                    return None;
+                }
+
+                Some(Expiry {
+                    name,
+                    expires,
+                    days_left,
+                    data_files: list_data_files(&data_source).len(),
+                })
+            })
+            .collect();
+
+        expiries.sort_by(|a, b| a.expires.cmp(&b.expires).then(a.name.cmp(&b.name)));
+        expiries
+    }
+
+    /// Formats the expiring data sources with a line for each
+    ///
+    /// # Arguments
+    ///
+    /// * `expiries` - The data sources found by the check
+    pub fn summary(&self, expiries: &[Expiry]) -> String {
+        if expiries.is_empty() {
+            return format!(
+                "{}",
+                Style::new().color256(10).apply_to(format!(
+                    "No data sources expire within {} days",
+                    self.within
+                ))
+            );
+        }
+
+        expiries
+            .iter()
+            .map(|expiry| {
+                let (label, col_256) = if expiry.is_expired() {
+                    ("expired", 9)
+                } else {
+                    ("expiring", 220)
+                };
+
+                let when = match expiry.days_left {
+                    0 => "today".to_string(),
+                    days if days < 0 => format!("{} days ago", -days),
+                    days => format!("in {} days", days),
+                };
+
+                format!(
+                    "{} {} on {} ({}), {} data files",
+                    Style::new()
+                        .color256(col_256)
+                        .apply_to(format!("{:>10}:", label)),
+                    expiry.name,
+                    expiry.expires.format("%Y-%m-%d"),
+                    when,
+                    expiry.data_files
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[derive(Args, Clone)]
+pub struct RetentionPurge {
+    /// The name of the data source to purge
+    #[clap(value_parser)]
+    pub name: path::PathBuf,
+    /// Delete the data files without asking for confirmation
+    #[clap(short, long)]
+    pub yes: bool,
+}
+
+impl RetentionPurge {
+    /// The name of the data source as a str
+    fn _name_str(&self) -> &str {
+        self.name
+            .to_str()
+            .expect("Unable to convert data source name to str")
+    }
+
+    /// Finds the data source the name refers to, see [find_data_source]
+    ///
+    /// When only the start of the name was given the data source that was
+    /// found is printed and `--yes` is ignored, just as it is by
+    /// `geoff remove`.
+    ///
+    /// # Return value
+    ///
+    /// The purge with the name of the data source that was found
+    ///
+    /// # Errors
+    ///
+    /// * The data source doesn't exist or the name is ambiguous
+    pub fn resolved(&self) -> RetentionPurge {
+        let found = find_data_source(&self.name);
+        if found == self.name {
+            return self.clone();
+        }
+
+        println!(
+            "{} {} data_sources/{}",
+            self.name.display(),
+            Style::new().color256(220).apply_to("matches"),
+            found.display()
+        );
+        RetentionPurge {
+            name: found,
+            yes: false,
+        }
+    }
+
+    /// Lists the data files that will be deleted
+    ///
+    /// # Errors
+    ///
+    /// * The name isn't a single folder within data_sources, e.g `..`
+    /// * The data source doesn't exist
+    pub fn data_files(&self) -> Vec<String> {
+        list_data_files(&data_source_path(&self.name))
+    }
+
+    /// Asks the user to confirm the data files should be deleted
+    ///
+    /// # Arguments
+    ///
+    /// * `data_files` - The data files that will be deleted
+    /// * `input` - Where the answer is read from
+    ///
+    /// # Return value
+    ///
+    /// Whether the user answered yes, always true when `--yes` was passed
+    pub fn confirm<R: io::BufRead>(&self, data_files: &[String], input: R) -> bool {
+        if self.yes {
+            return true;
+        }
+
+        prompt::confirm(
+            &format!(
+                "{} data files will be deleted from {}. metadata.md will be kept.\nContinue?",
+                data_files.len(),
+                self.name.display()
+            ),
+            input,
+        )
+    }
+
+    /// Deletes the data files, keeping metadata.md
+    ///
+    /// Folders left empty once their files are deleted are removed too.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_files` - The data files to delete, relative to the data source
+    pub fn purge(&self, data_files: &[String]) {
+        let data_source = data_source_path(&self.name);
+
+        for data_file in data_files.iter() {
+            let file_path = data_source.join(data_file);
+            fs::remove_file(&file_path)
+                .unwrap_or_else(|_| panic!("Unable to delete {}", file_path.display()));
+
+            let mut parent = file_path.parent();
+            while let Some(folder) = parent {
+                if folder == data_source || fs::remove_dir(folder).is_err() {
+                    break;
+                }
+                parent = folder.parent();
+            }
+        }
+    }
+
+    /// Logs the deletion in the manifest
+    ///
+    /// The checksums of the deleted files are dropped so `geoff verify`
+    /// doesn't report them as missing.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest
+    /// * `data_files` - The data files that were deleted
+    ///
+    /// # Return value
+    ///
+    /// The purge that was logged, or None when no files were deleted
+    pub fn record_purge(&self, manifest: &mut Manifest, data_files: &[String]) -> Option<Purge> {
+        if data_files.is_empty() {
+            return None;
+        }
+
+        let purge = Purge {
+            purged: front_matter::today(),
+            expired: expiry_date(&data_source_path(&self.name))
+                .map(|expires| expires.format("%Y-%m-%d").to_string()),
+            files: data_files.to_vec(),
+        };
+
+        let entry = manifest.data_source_mut(self._name_str());
+        entry
+            .checksums
+            .retain(|data_file, _| !data_files.contains(data_file));
+        entry.purges.push(purge.clone());
+
+        Some(purge)
+    }
+
+    /// Creates a tree showing the files that were deleted
+    pub fn create_tree(&self, purge: &Purge) -> item::StringItem {
+        let gold = Style::new().color256(220);
+        let hd = Style::new().color256(194);
+        let red = Style::new().color256(9);
+
+        let mut tree = TreeBuilder::new(format!(
+            "{} {}",
+            gold.apply_to("\u{1F5BF}"),
+            self.name.display()
+        ));
+
+        tree.add_empty_child(format!("{} metadata.md", hd.apply_to("\u{1F5CE}")));
+        for data_file in purge.files.iter() {
+            tree.add_empty_child(format!(
+                "{} {} (deleted)",
+                red.apply_to("\u{1F5CE}"),
+                data_file
+            ));
+        }
+
+        tree.build()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,263 @@
+use rstest::rstest;
+use std::{fs, path};
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+
+fn setup_data_source(name: &str, expires: Option<&str>) {
+    let data_source = path::Path::new("data_sources").join(name);
+    fs::create_dir_all(data_source.join("versions/2022-04-01")).unwrap();
+
+    let expires_line = expires
+        .map(|expires| format!("expires: {}\n", expires))
+        .unwrap_or_default();
+    fs::write(
+        data_source.join("metadata.md"),
+        format!(
+            "---\nname: {}\ncreated: 2022-04-01\n{}---\n\n# {}\n",
+            name, expires_line, name
+        ),
+    )
+    .unwrap();
+    fs::write(
+        data_source.join("versions/2022-04-01/extract.csv"),
+        "id\n1\n",
+    )
+    .unwrap();
+}
+
+fn date(value: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+}
+
+fn purge(yes: bool) -> RetentionPurge {
+    RetentionPurge {
+        name: path::PathBuf::from("iris"),
+        yes,
+    }
+}
+
+// +++++++++++ //
+// expiry_date //
+// +++++++++++ //
+#[test]
+fn reads_expiry_date_from_front_matter() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source("iris", Some("2023-04-01"));
+
+            assert_eq!(
+                expiry_date(path::Path::new("data_sources/iris")),
+                Some(date("2023-04-01"))
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn no_expiry_date_is_none() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source("iris", None);
+
+            assert_eq!(expiry_date(path::Path::new("data_sources/iris")), None);
+        },
+        false,
+    )
+}
+
+// +++++ //
+// check //
+// +++++ //
+#[test]
+fn finds_expired_and_expiring_data_sources() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source("iris", Some("2022-05-10"));
+            setup_data_source("penguins", Some("2022-03-01"));
+            setup_data_source("titanic", Some("2023-01-01"));
+            setup_data_source("mtcars", None);
+
+            let expiries = RetentionCheck { within: 30 }.check(date("2022-05-01"));
+
+            assert_eq!(
+                expiries,
+                vec![
+                    Expiry {
+                        name: String::from("penguins"),
+                        expires: date("2022-03-01"),
+                        days_left: -61,
+                        data_files: 1,
+                    },
+                    Expiry {
+                        name: String::from("iris"),
+                        expires: date("2022-05-10"),
+                        days_left: 9,
+                        data_files: 1,
+                    },
+                ]
+            );
+            assert!(expiries[0].is_expired());
+            assert!(!expiries[1].is_expired());
+        },
+        false,
+    )
+}
+
+#[test]
+fn summary_labels_expired_and_expiring() {
+    let expiries = vec![
+        Expiry {
+            name: String::from("penguins"),
+            expires: date("2022-03-01"),
+            days_left: -61,
+            data_files: 1,
+        },
+        Expiry {
+            name: String::from("iris"),
+            expires: date("2022-05-01"),
+            days_left: 0,
+            data_files: 2,
+        },
+    ];
+
+    let summary =
+        console::strip_ansi_codes(&RetentionCheck { within: 30 }.summary(&expiries)).to_string();
+
+    assert_eq!(
+        summary,
+        "   expired: penguins on 2022-03-01 (61 days ago), 1 data files\n  \
+        expiring: iris on 2022-05-01 (today), 2 data files"
+    );
+}
+
+#[test]
+fn summary_with_nothing_expiring() {
+    let summary = console::strip_ansi_codes(&RetentionCheck { within: 7 }.summary(&[])).to_string();
+
+    assert_eq!(summary, "No data sources expire within 7 days");
+}
+
+// +++++++ //
+// confirm //
+// +++++++ //
+#[test]
+fn confirm_reads_answer() {
+    assert!(purge(false).confirm(&[String::from("iris.csv")], "y\n".as_bytes()));
+    assert!(!purge(false).confirm(&[String::from("iris.csv")], "n\n".as_bytes()));
+}
+
+#[test]
+fn confirm_skipped_with_yes() {
+    assert!(purge(true).confirm(&[String::from("iris.csv")], "n\n".as_bytes()));
+}
+
+// +++++ //
+// purge //
+// +++++ //
+#[test]
+fn purge_keeps_metadata() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source("iris", Some("2022-03-01"));
+            fs::write("data_sources/iris/iris.csv", "id\n").unwrap();
+
+            let retention_purge = purge(true);
+            let data_files = retention_purge.data_files();
+            retention_purge.purge(&data_files);
+
+            assert!(path::Path::new("data_sources/iris/metadata.md").exists());
+            assert!(!path::Path::new("data_sources/iris/iris.csv").exists());
+            assert!(!path::Path::new("data_sources/iris/versions").exists());
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "data_sources/iris doesn't exist")]
+fn purge_unknown_data_source_panics() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources").unwrap();
+
+            purge(true).data_files();
+        },
+        true,
+    )
+}
+
+#[rstest]
+#[case("..")]
+#[case("")]
+#[case("iris/versions")]
+#[should_panic(expected = "isn't a data source")]
+fn purge_outside_data_source_panics(#[case] name: &str) {
+    test_in_tmp_dir(
+        || {
+            setup_data_source("iris", Some("2022-03-01"));
+
+            RetentionPurge {
+                name: path::PathBuf::from(name),
+                yes: true,
+            }
+            .data_files();
+        },
+        true,
+    )
+}
+
+// ++++++++++++ //
+// record_purge //
+// ++++++++++++ //
+#[test]
+fn record_purge_skips_when_nothing_deleted() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source("iris", Some("2022-03-01"));
+
+            let mut manifest = Manifest::default();
+
+            assert_eq!(purge(true).record_purge(&mut manifest, &[]), None);
+            assert!(manifest.data_sources.is_empty());
+        },
+        false,
+    )
+}
+
+#[test]
+fn record_purge_logs_deletion_and_drops_checksums() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source("iris", Some("2022-03-01"));
+
+            let mut manifest = Manifest::default();
+            let entry = manifest.data_source_mut("iris");
+            entry.checksums.insert(
+                String::from("versions/2022-04-01/extract.csv"),
+                String::from("abc"),
+            );
+            entry
+                .checksums
+                .insert(String::from("kept.csv"), String::from("def"));
+
+            let data_files = vec![String::from("versions/2022-04-01/extract.csv")];
+            let logged = purge(true)
+                .record_purge(&mut manifest, &data_files)
+                .unwrap();
+
+            assert_eq!(logged.expired, Some(String::from("2022-03-01")));
+            assert_eq!(logged.files, data_files);
+            assert_eq!(manifest.data_sources["iris"].purges, vec![logged]);
+            assert_eq!(
+                manifest.data_sources["iris"]
+                    .checksums
+                    .keys()
+                    .collect::<Vec<_>>(),
+                vec!["kept.csv"]
+            );
+        },
+        false,
+    )
+}
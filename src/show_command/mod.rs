@@ -0,0 +1,116 @@
+use clap::{AppSettings, Args, Subcommand, ValueEnum};
+use console::Style;
+use std::{fs, path};
+
+use crate::component::{ComponentId, Stage};
+use crate::metadata::{self, DataSourceMetadata};
+
+#[derive(Args)]
+pub struct Show {
+    #[clap(subcommand)]
+    pub command: Option<ShowCommands>,
+}
+
+#[derive(Subcommand)]
+#[clap(setting = AppSettings::SubcommandRequiredElseHelp)]
+pub enum ShowCommands {
+    /// Shows the details recorded in a data source's metadata
+    DataSource(ShowDataSource),
+}
+
+/// The formats the details can be shown in
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    /// A summary for reading in the terminal
+    Text,
+    /// JSON for other tools to read
+    Json,
+}
+
+#[derive(Args)]
+pub struct ShowDataSource {
+    /// The name of the data source
+    #[clap(value_parser)]
+    pub name: path::PathBuf,
+    /// The format to show the details in
+    #[clap(short, long, value_enum, default_value = "text")]
+    pub format: OutputFormat,
+}
+
+impl ShowDataSource {
+    /// Reads the metadata of the data source
+    ///
+    /// The name can be the start of a data source's name if only one data
+    /// source starts with it.
+    ///
+    /// # Errors
+    ///
+    /// * The data source doesn't exist or the name is ambiguous
+    pub fn read_metadata(&self) -> DataSourceMetadata {
+        let metadata_path =
+            ComponentId::find(Stage::DataSource, &self.name.to_string_lossy()).document();
+        let contents = fs::read_to_string(&metadata_path)
+            .unwrap_or_else(|_| panic!("Unable to read {}", metadata_path.display()));
+
+        metadata::parse_data_source(&contents)
+    }
+
+    /// Formats the metadata in the requested format
+    ///
+    /// # Arguments
+    ///
+    /// * `data_source_metadata` - The metadata read from the data source
+    pub fn render(&self, data_source_metadata: &DataSourceMetadata) -> String {
+        match self.format {
+            OutputFormat::Json => serde_json::to_string_pretty(data_source_metadata)
+                .expect("Unable to serialise the metadata"),
+            OutputFormat::Text => self._render_text(data_source_metadata),
+        }
+    }
+
+    /// Formats the metadata as a summary for the terminal
+    fn _render_text(&self, data_source_metadata: &DataSourceMetadata) -> String {
+        let bold = Style::new().bold();
+        let mut lines = vec![format!("{}", bold.apply_to(&data_source_metadata.title))];
+
+        if let Some(front_matter) = &data_source_metadata.front_matter {
+            if let Some(kind) = &front_matter.kind {
+                lines.push(format!("kind: {}", kind));
+            }
+            if let Some(owner) = &front_matter.owner {
+                lines.push(format!("owner: {}", owner));
+            }
+            if let Some(sensitivity) = &front_matter.sensitivity {
+                lines.push(format!("sensitivity: {}", sensitivity));
+            }
+            lines.push(format!("created: {}", front_matter.created));
+            if let Some(refreshed) = &front_matter.refreshed {
+                lines.push(format!("refreshed: {}", refreshed));
+            }
+            if let Some(expires) = &front_matter.expires {
+                lines.push(format!("expires: {}", expires));
+            }
+            if let Some(needs_review) = &front_matter.needs_review {
+                lines.push(format!("needs review: {}", needs_review));
+            }
+        }
+
+        lines.push(format!("\n{}", bold.apply_to("Data dictionary")));
+        for entry in data_source_metadata.data_dictionary.iter() {
+            lines.push(format!(
+                "  {} ({}) {}",
+                entry.field, entry.data_type, entry.description
+            ));
+        }
+
+        lines.push(format!("\n{}", bold.apply_to("Key people")));
+        for person in data_source_metadata.key_people.iter() {
+            lines.push(format!("  {} {}", person.name, person.details));
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests;
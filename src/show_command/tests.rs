@@ -0,0 +1,105 @@
+use std::{fs, path};
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+
+fn setup_data_source() {
+    fs::create_dir_all("data_sources/iris").unwrap();
+    fs::write(
+        "data_sources/iris/metadata.md",
+        "---\n\
+        name: iris\n\
+        kind: extract\n\
+        owner: Ronald Fisher\n\
+        created: 2022-04-01\n\
+        ---\n\
+        \n\
+        # iris\n\
+        \n\
+        ## Data dictionary\n\
+        \n\
+        | data field | data type | description |\n\
+        | :--------- | :-------- | :---------- |\n\
+        | id | INT | A unique identifier |\n",
+    )
+    .unwrap();
+}
+
+fn show(format: OutputFormat) -> ShowDataSource {
+    ShowDataSource {
+        name: path::PathBuf::from("iris"),
+        format,
+    }
+}
+
+// +++++++++++++ //
+// read_metadata //
+// +++++++++++++ //
+#[test]
+fn reads_data_source_metadata() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source();
+
+            let data_source_metadata = show(OutputFormat::Text).read_metadata();
+
+            assert_eq!(data_source_metadata.title, "iris");
+            assert_eq!(data_source_metadata.data_dictionary.len(), 1);
+        },
+        false,
+    )
+}
+
+#[test]
+#[should_panic(expected = "data_sources/penguins doesn't exist")]
+fn errors_on_missing_data_source() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source();
+
+            ShowDataSource {
+                name: path::PathBuf::from("penguins"),
+                format: OutputFormat::Json,
+            }
+            .read_metadata();
+        },
+        true,
+    )
+}
+
+// ++++++ //
+// render //
+// ++++++ //
+#[test]
+fn renders_json() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source();
+
+            let show = show(OutputFormat::Json);
+            let rendered = show.render(&show.read_metadata());
+            let json: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+
+            assert_eq!(json["front_matter"]["kind"], "extract");
+            assert_eq!(json["data_dictionary"][0]["field"], "id");
+            assert_eq!(json["key_people"], serde_json::json!([]));
+        },
+        false,
+    )
+}
+
+#[test]
+fn renders_text() {
+    test_in_tmp_dir(
+        || {
+            setup_data_source();
+
+            let show = show(OutputFormat::Text);
+            let rendered = show.render(&show.read_metadata());
+
+            assert!(rendered.contains("kind: extract\nowner: Ronald Fisher\ncreated: 2022-04-01"));
+            assert!(rendered.contains("  id (INT) A unique identifier"));
+        },
+        false,
+    )
+}
@@ -0,0 +1,195 @@
+use clap::{AppSettings, Args, Subcommand};
+use std::{fs, path};
+
+use crate::component::{list_components, ComponentId, Stage};
+use crate::front_matter;
+use crate::promote_command::PROVENANCE_HEADING;
+
+/// The README that holds the managed component lists
+pub const README: &str = "README.md";
+
+#[derive(Args)]
+pub struct Sync {
+    #[clap(subcommand)]
+    pub command: Option<SyncCommands>,
+}
+
+#[derive(Subcommand)]
+#[clap(setting = AppSettings::SubcommandRequiredElseHelp)]
+pub enum SyncCommands {
+    /// Regenerates the component lists in the project README
+    Readme(SyncReadme),
+}
+
+#[derive(Args)]
+pub struct SyncReadme {}
+
+/// The marker that starts a stage's managed section
+///
+/// # Arguments
+///
+/// * `stage` - The stage listed in the section
+pub fn start_marker(stage: Stage) -> String {
+    format!("<!-- geoff:begin {} -->", stage.folder())
+}
+
+/// The marker that ends a stage's managed section
+///
+/// # Arguments
+///
+/// * `stage` - The stage listed in the section
+pub fn end_marker(stage: Stage) -> String {
+    format!("<!-- geoff:end {} -->", stage.folder())
+}
+
+/// Finds a one line description of a component
+///
+/// A `description` field in the front matter is used if there is one,
+/// otherwise the first line of text after the heading. Headings, tables and
+/// the italic instructions and examples from the templates are skipped.
+///
+/// # Arguments
+///
+/// * `contents` - The contents of the component's main document
+pub fn describe(contents: &str) -> Option<String> {
+    let description = front_matter::parse::<serde_yaml::Mapping>(contents)
+        .ok()
+        .and_then(|fields| {
+            fields
+                .get("description")
+                .and_then(|value| value.as_str())
+                .map(|value| value.trim().to_string())
+        });
+    if description.is_some() {
+        return description;
+    }
+
+    let body = front_matter::split(contents)
+        .map(|(_, body)| body)
+        .unwrap_or(contents);
+
+    body.lines()
+        .map(|line| line.trim())
+        .take_while(|line| *line != PROVENANCE_HEADING)
+        .find(|line| {
+            !(line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with('|')
+                || line.starts_with('*'))
+        })
+        .map(|line| line.to_string())
+}
+
+/// Creates the list item for a component
+///
+/// The component's title is shown if it has one, otherwise its name.
+///
+/// # Arguments
+///
+/// * `component` - The component to list
+pub fn list_item(component: &ComponentId) -> String {
+    let contents = fs::read_to_string(component.document()).ok();
+    let title = contents
+        .as_deref()
+        .and_then(|contents| front_matter::parse::<serde_yaml::Mapping>(contents).ok())
+        .and_then(|fields| {
+            fields
+                .get("title")
+                .and_then(|value| value.as_str())
+                .map(|value| value.to_string())
+        })
+        .unwrap_or_else(|| component.name.clone());
+    let link = format!(
+        "* [{}]({})",
+        title,
+        component.document().to_string_lossy().replace('\\', "/")
+    );
+
+    match contents.as_deref().and_then(describe) {
+        Some(description) => format!("{} - {}", link, description),
+        None => link,
+    }
+}
+
+/// Replaces the lines between a stage's markers
+///
+/// # Arguments
+///
+/// * `contents` - The contents of the README
+/// * `stage` - The stage of the section to replace
+/// * `items` - The lines to put between the markers
+///
+/// # Return value
+///
+/// The updated README, or None if it doesn't have the stage's markers
+pub fn replace_section(contents: &str, stage: Stage, items: &[String]) -> Option<String> {
+    let (start, end) = (start_marker(stage), end_marker(stage));
+
+    let lines: Vec<&str> = contents.lines().collect();
+    let first = lines.iter().position(|line| line.trim() == start)?;
+    let last = first + lines[first..].iter().position(|line| line.trim() == end)?;
+
+    let mut replaced: Vec<&str> = lines[..=first].to_vec();
+    replaced.extend(items.iter().map(|item| item.as_str()));
+    replaced.extend(&lines[last..]);
+
+    let mut updated = replaced.join("\n");
+    if contents.ends_with('\n') {
+        updated.push('\n');
+    }
+
+    Some(updated)
+}
+
+/// Regenerates every managed section in the README
+///
+/// Text outside the markers is left as it is, as are the sections of any
+/// stage whose markers have been taken out.
+///
+/// # Arguments
+///
+/// * `contents` - The contents of the README
+///
+/// # Return value
+///
+/// The updated README and the number of sections that were regenerated
+pub fn sync(contents: &str) -> (String, usize) {
+    let mut synced = contents.to_string();
+    let mut sections = 0;
+
+    for stage in Stage::ALL {
+        let items: Vec<String> = list_components(stage).iter().map(list_item).collect();
+
+        if let Some(updated) = replace_section(&synced, stage, &items) {
+            synced = updated;
+            sections += 1;
+        }
+    }
+
+    (synced, sections)
+}
+
+/// Regenerates the managed sections in the project README
+///
+/// Projects whose README doesn't exist or has no markers are left alone.
+///
+/// # Return value
+///
+/// The number of sections that were regenerated
+pub fn sync_readme() -> usize {
+    let readme = path::Path::new(README);
+    let contents = match fs::read_to_string(readme) {
+        Ok(contents) => contents,
+        Err(_) => return 0,
+    };
+
+    let (synced, sections) = sync(&contents);
+    if synced != contents {
+        fs::write(readme, synced).unwrap_or_else(|_| panic!("Unable to update {}", README));
+    }
+
+    sections
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,122 @@
+use std::fs;
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+
+const README_WITH_MARKERS: &str = "# iris\n\
+    Some notes\n\
+    \n\
+    Data sources\n\
+    <!-- geoff:begin data_sources -->\n\
+    * [old](data_sources/old/metadata.md)\n\
+    <!-- geoff:end data_sources -->\n\
+    \n\
+    Models\n\
+    <!-- geoff:begin models -->\n\
+    <!-- geoff:end models -->\n";
+
+// ++++++++ //
+// describe //
+// ++++++++ //
+#[test]
+fn describes_from_front_matter() {
+    assert_eq!(
+        describe("---\nname: eda\ndescription: Looks at petals\n---\n\n# eda\nSomething else\n"),
+        Some(String::from("Looks at petals"))
+    );
+}
+
+#[test]
+fn describes_from_first_line_of_text() {
+    assert_eq!(
+        describe("---\nname: eda\n---\n\n# eda\n\n## Purpose\n*What question*\nFind the species\n"),
+        Some(String::from("Find the species"))
+    );
+}
+
+#[test]
+fn untouched_template_has_no_description() {
+    assert_eq!(
+        describe("# eda\n\n## Purpose\n*What question*\n\n## Provenance\n\nPromoted to models\n"),
+        None
+    );
+}
+
+// +++++++++++++++ //
+// replace_section //
+// +++++++++++++++ //
+#[test]
+fn replaces_only_between_markers() {
+    let replaced = replace_section(
+        README_WITH_MARKERS,
+        Stage::DataSource,
+        &[String::from("* [iris](data_sources/iris/metadata.md)")],
+    )
+    .unwrap();
+
+    assert_eq!(
+        replaced,
+        README_WITH_MARKERS.replace(
+            "* [old](data_sources/old/metadata.md)",
+            "* [iris](data_sources/iris/metadata.md)"
+        )
+    );
+}
+
+#[test]
+fn missing_markers_not_replaced() {
+    assert_eq!(
+        replace_section(README_WITH_MARKERS, Stage::Product, &[]),
+        None
+    );
+}
+
+// ++++ //
+// sync //
+// ++++ //
+#[test]
+fn sync_lists_components_with_descriptions() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+            fs::write(
+                "data_sources/iris/metadata.md",
+                "# iris\nFisher's iris data\n",
+            )
+            .unwrap();
+            fs::create_dir_all("data_sources/_archive/old").unwrap();
+            fs::create_dir_all("models/classifier").unwrap();
+            fs::write("models/classifier/README.md", "# classifier\n").unwrap();
+
+            let (synced, sections) = sync(README_WITH_MARKERS);
+
+            assert_eq!(sections, 2);
+            assert!(synced.contains(
+                "<!-- geoff:begin data_sources -->\n\
+                * [iris](data_sources/iris/metadata.md) - Fisher's iris data\n\
+                <!-- geoff:end data_sources -->\n"
+            ));
+            assert!(synced.contains(
+                "<!-- geoff:begin models -->\n\
+                * [classifier](models/classifier/README.md)\n\
+                <!-- geoff:end models -->\n"
+            ));
+            assert!(synced.starts_with("# iris\nSome notes\n"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn readme_without_markers_left_alone() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("models/classifier").unwrap();
+            fs::write(README, "# iris\n").unwrap();
+
+            assert_eq!(sync_readme(), 0);
+            assert_eq!(fs::read_to_string(README).unwrap(), "# iris\n");
+        },
+        false,
+    )
+}
@@ -0,0 +1,287 @@
+use clap::{AppSettings, Args, Subcommand, ValueEnum};
+use console::Style;
+use std::{fmt, fs, path};
+
+use crate::add_command::{self, DataSourceKind};
+use crate::checksum::sha256_hex;
+use crate::component::Stage;
+use crate::front_matter;
+use crate::manifest::{Manifest, TemplateRecord};
+
+/// The folder in the project root that keeps the text of each template
+/// version documents were generated from
+pub const TEMPLATE_STORE: &str = ".geoff_templates";
+
+#[derive(Args)]
+pub struct Template {
+    #[clap(subcommand)]
+    pub command: Option<TemplateCommands>,
+}
+
+#[derive(Subcommand)]
+#[clap(setting = AppSettings::SubcommandRequiredElseHelp)]
+pub enum TemplateCommands {
+    /// Merges changes to the templates into the documents generated from them
+    Update(TemplateUpdate),
+}
+
+/// Gets the current text of one of geoff's templates
+///
+/// # Arguments
+///
+/// * `name` - The template's path within geoff's templates
+///
+/// # Return value
+///
+/// The template's text, or None if there isn't a template with the name
+pub fn template_text(name: &str) -> Option<&'static str> {
+    match name {
+        "root/README.md" => return Some(include_str!("../templates/root/README.md")),
+        "root/project_scoping.md" => {
+            return Some(include_str!("../templates/root/project_scoping.md"))
+        }
+        _ => {}
+    }
+
+    let kinds = DataSourceKind::value_variants()
+        .iter()
+        .map(|kind| Some(*kind))
+        .chain([None]);
+    for kind in kinds {
+        if add_command::metadata_template_name(kind) == name {
+            return Some(add_command::metadata_template(kind));
+        }
+    }
+
+    [Stage::Exploration, Stage::Model, Stage::Product]
+        .into_iter()
+        .find(|stage| add_command::readme_template_name(*stage) == name)
+        .map(add_command::readme_template)
+}
+
+/// Replaces the name placeholder in a template
+///
+/// # Arguments
+///
+/// * `text` - The template's text
+/// * `name` - The name of the project or component
+pub fn render_template(text: &str, name: &str) -> String {
+    [
+        "<<<project_name>>>",
+        "<<<data_source_name>>>",
+        "<<<component_name>>>",
+    ]
+    .iter()
+    .fold(text.to_string(), |rendered, tag| {
+        rendered.replace(tag, name)
+    })
+}
+
+/// Records the template a document was generated from
+///
+/// The template's text is kept in the template store so later versions of
+/// the template can be merged into the document.
+///
+/// # Arguments
+///
+/// * `manifest` - The manifest of the project the document is in
+/// * `root` - The root of the project
+/// * `document` - The path of the document relative to the project root
+/// * `template` - The template's path within geoff's templates
+/// * `name` - The name that replaced the template's placeholder
+pub fn remember(
+    manifest: &mut Manifest,
+    root: &path::Path,
+    document: &str,
+    template: &str,
+    name: &str,
+) {
+    let text = template_text(template)
+        .unwrap_or_else(|| panic!("{} isn't one of geoff's templates", template));
+    let version = sha256_hex(text.as_bytes());
+
+    _store(root, &version, text);
+    manifest.templates.insert(
+        document.to_string(),
+        TemplateRecord {
+            template: template.to_string(),
+            version,
+            name: name.to_string(),
+        },
+    );
+}
+
+/// The path of a template version within the template store
+fn _stored_path(root: &path::Path, version: &str) -> path::PathBuf {
+    root.join(TEMPLATE_STORE).join(format!("{}.md", version))
+}
+
+/// Writes a template version to the template store if it isn't there
+fn _store(root: &path::Path, version: &str, text: &str) {
+    let stored = _stored_path(root, version);
+    if stored.exists() {
+        return;
+    }
+
+    fs::create_dir_all(root.join(TEMPLATE_STORE))
+        .unwrap_or_else(|_| panic!("Unable to create {}", TEMPLATE_STORE));
+    fs::write(&stored, text).unwrap_or_else(|_| panic!("Unable to write {}", stored.display()));
+}
+
+/// Merges the changes between two versions of a template into a document
+///
+/// The document's front matter is kept as it is and the merge is done on the
+/// rest of the document.
+///
+/// # Arguments
+///
+/// * `contents` - The contents of the document
+/// * `original` - The template the document was generated from, rendered
+/// * `updated` - The new version of the template, rendered
+///
+/// # Return value
+///
+/// The merged document, or the document with conflict markers around the
+/// lines that changed in both the document and the template
+pub fn merge(contents: &str, original: &str, updated: &str) -> Result<String, String> {
+    let (front_matter, body, original, updated) = match front_matter::split(contents) {
+        Some((_, body)) => (
+            &contents[..contents.len() - body.len()],
+            body,
+            format!("\n{}", original),
+            format!("\n{}", updated),
+        ),
+        None => ("", contents, original.to_string(), updated.to_string()),
+    };
+
+    match diffy::merge(&original, body, &updated) {
+        Ok(merged) => Ok(format!("{}{}", front_matter, merged)),
+        Err(conflicted) => Err(format!("{}{}", front_matter, conflicted)),
+    }
+}
+
+/// What happened when a document was updated
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    /// The template hasn't changed since the document was generated
+    UpToDate,
+    /// The template's changes were merged in
+    Merged,
+    /// Conflict markers were written where the document and template disagree
+    Conflicted,
+    /// The document has been moved or removed
+    Missing,
+    /// The text of the template it was generated from isn't in the store
+    Unknown,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (colour, text) = match self {
+            Outcome::UpToDate => (10, "up to date"),
+            Outcome::Merged => (10, "merged"),
+            Outcome::Conflicted => (9, "conflicts to resolve"),
+            Outcome::Missing => (220, "missing, skipped"),
+            Outcome::Unknown => (220, "original template unknown, skipped"),
+        };
+
+        write!(f, "{}", Style::new().color256(colour).apply_to(text))
+    }
+}
+
+#[derive(Args)]
+pub struct TemplateUpdate {
+    /// Shows which documents would change without changing them
+    #[clap(short, long)]
+    pub dry_run: bool,
+}
+
+impl TemplateUpdate {
+    /// Updates a document to the current version of its template
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - The path of the document relative to the project root
+    /// * `record` - The template the document was generated from, which is
+    ///   changed to the current version once the document is updated
+    pub fn update(&self, document: &str, record: &mut TemplateRecord) -> Outcome {
+        let root = path::Path::new(".");
+        let document_path = root.join(document);
+
+        let contents = match fs::read_to_string(&document_path) {
+            Ok(contents) => contents,
+            Err(_) => return Outcome::Missing,
+        };
+        let current = match template_text(&record.template) {
+            Some(current) => current,
+            None => return Outcome::Unknown,
+        };
+        let version = sha256_hex(current.as_bytes());
+        if version == record.version {
+            return Outcome::UpToDate;
+        }
+        let original = match fs::read_to_string(_stored_path(root, &record.version)) {
+            Ok(original) => original,
+            Err(_) => return Outcome::Unknown,
+        };
+
+        let (merged, outcome) = match merge(
+            &contents,
+            &render_template(&original, &record.name),
+            &render_template(current, &record.name),
+        ) {
+            Ok(merged) => (merged, Outcome::Merged),
+            Err(conflicted) => (conflicted, Outcome::Conflicted),
+        };
+
+        if !self.dry_run {
+            fs::write(&document_path, merged)
+                .unwrap_or_else(|_| panic!("Unable to update {}", document));
+            _store(root, &version, current);
+            record.version = version;
+        }
+
+        outcome
+    }
+
+    /// Updates every document geoff remembers the template for
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest
+    ///
+    /// # Return value
+    ///
+    /// Each document with what happened to it
+    pub fn update_all(&self, manifest: &mut Manifest) -> Vec<(String, Outcome)> {
+        manifest
+            .templates
+            .iter_mut()
+            .map(|(document, record)| (document.clone(), self.update(document, record)))
+            .collect()
+    }
+
+    /// Formats the outcomes with a line for each document
+    ///
+    /// # Arguments
+    ///
+    /// * `outcomes` - Each document with what happened to it
+    pub fn summary(&self, outcomes: &[(String, Outcome)]) -> String {
+        if outcomes.is_empty() {
+            return String::from("No documents were generated from a recorded template");
+        }
+
+        let hd = Style::new().color256(194);
+
+        outcomes
+            .iter()
+            .map(|(document, outcome)| {
+                format!("{} {} {}", hd.apply_to("\u{1F5CE}"), document, outcome)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests;
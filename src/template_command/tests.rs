@@ -0,0 +1,211 @@
+use rstest::rstest;
+use std::fs;
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+
+const ORIGINAL: &str = "# <<<component_name>>>\n\n## Purpose\n*What*\n\n## Findings\n*Learnt*\n";
+const UPDATED: &str =
+    "# <<<component_name>>>\n\n## Purpose\n*What*\n\n## Data used\n*Which data*\n\n## Findings\n*Learnt*\n";
+
+fn record(version: &str) -> TemplateRecord {
+    TemplateRecord {
+        template: String::from("explorations/README.md"),
+        version: String::from(version),
+        name: String::from("eda"),
+    }
+}
+
+// +++++++++++++ //
+// template_text //
+// +++++++++++++ //
+#[rstest]
+#[case("root/README.md")]
+#[case("data_sources/default_metadata.md")]
+#[case("data_sources/web_metadata.md")]
+#[case("models/README.md")]
+fn finds_every_template(#[case] name: &str) {
+    assert!(template_text(name).is_some());
+}
+
+#[test]
+fn unknown_template_isnt_found() {
+    assert_eq!(template_text("data_sources/README.md"), None);
+}
+
+// +++++++++++++++ //
+// render_template //
+// +++++++++++++++ //
+#[test]
+fn renders_name_placeholders() {
+    assert_eq!(
+        render_template(ORIGINAL, "eda"),
+        ORIGINAL.replace("<<<component_name>>>", "eda")
+    );
+}
+
+// ++++++++ //
+// remember //
+// ++++++++ //
+#[test]
+fn remembers_template_and_stores_its_text() {
+    test_in_tmp_dir(
+        || {
+            let mut manifest = Manifest::default();
+
+            remember(
+                &mut manifest,
+                path::Path::new("."),
+                "models/classifier/README.md",
+                "models/README.md",
+                "classifier",
+            );
+
+            let record = &manifest.templates["models/classifier/README.md"];
+            assert_eq!(record.template, "models/README.md");
+            assert_eq!(record.name, "classifier");
+            assert_eq!(
+                fs::read_to_string(_stored_path(path::Path::new("."), &record.version)).unwrap(),
+                template_text("models/README.md").unwrap()
+            );
+        },
+        false,
+    )
+}
+
+// +++++ //
+// merge //
+// +++++ //
+#[test]
+fn merges_new_section_and_keeps_edits() {
+    let original = render_template(ORIGINAL, "eda");
+    let contents = format!(
+        "---\nname: eda\n---\n\n{}",
+        original.replace("*Learnt*", "Petal length separates the species")
+    );
+
+    assert_eq!(
+        merge(&contents, &original, &render_template(UPDATED, "eda")),
+        Ok(String::from(
+            "---\nname: eda\n---\n\n# eda\n\n## Purpose\n*What*\n\n## Data used\n*Which data*\n\n## Findings\nPetal length separates the species\n"
+        ))
+    );
+}
+
+#[test]
+fn conflicting_changes_get_markers() {
+    let original = "# eda\n\n## Purpose\n*What*\n";
+    let contents = "# eda\n\n## Purpose\nFind the species\n";
+    let updated = "# eda\n\n## Purpose\n*What question is being answered*\n";
+
+    let conflicted = merge(contents, original, updated).unwrap_err();
+
+    assert!(conflicted.contains("<<<<<<<"));
+    assert!(conflicted.contains("Find the species"));
+    assert!(conflicted.contains("*What question is being answered*"));
+}
+
+// ++++++ //
+// update //
+// ++++++ //
+#[test]
+fn updates_document_to_current_template() {
+    test_in_tmp_dir(
+        || {
+            let root = path::Path::new(".");
+            let current = template_text("explorations/README.md").unwrap();
+            let approach = current.find("## Approach").unwrap();
+            let findings = current.find("## Findings").unwrap();
+            let original = format!("{}{}", &current[..approach], &current[findings..]);
+
+            let old_version = sha256_hex(original.as_bytes());
+            _store(root, &old_version, &original);
+            fs::create_dir_all("explorations/eda").unwrap();
+            fs::write(
+                "explorations/eda/README.md",
+                render_template(&original, "eda").replace(
+                    "*What was learnt and what should happen next*",
+                    "Petal length separates the species",
+                ),
+            )
+            .unwrap();
+
+            let mut record = record(&old_version);
+            let outcome =
+                TemplateUpdate { dry_run: false }.update("explorations/eda/README.md", &mut record);
+
+            assert_eq!(outcome, Outcome::Merged);
+            assert_eq!(
+                fs::read_to_string("explorations/eda/README.md").unwrap(),
+                render_template(current, "eda").replace(
+                    "*What was learnt and what should happen next*",
+                    "Petal length separates the species",
+                )
+            );
+            assert_eq!(record.version, sha256_hex(current.as_bytes()));
+            assert!(_stored_path(root, &record.version).exists());
+        },
+        false,
+    )
+}
+
+#[test]
+fn dry_run_changes_nothing() {
+    test_in_tmp_dir(
+        || {
+            let old_version = sha256_hex(ORIGINAL.as_bytes());
+            _store(path::Path::new("."), &old_version, ORIGINAL);
+            fs::create_dir_all("explorations/eda").unwrap();
+            fs::write("explorations/eda/README.md", "# eda\n").unwrap();
+
+            let mut record = record(&old_version);
+            TemplateUpdate { dry_run: true }.update("explorations/eda/README.md", &mut record);
+
+            assert_eq!(record.version, old_version);
+            assert_eq!(
+                fs::read_to_string("explorations/eda/README.md").unwrap(),
+                "# eda\n"
+            );
+        },
+        false,
+    )
+}
+
+#[rstest]
+#[case(false, Outcome::Missing)]
+#[case(true, Outcome::Unknown)]
+fn skips_documents_it_cant_update(#[case] document_exists: bool, #[case] expected: Outcome) {
+    test_in_tmp_dir(
+        || {
+            if document_exists {
+                fs::create_dir_all("explorations/eda").unwrap();
+                fs::write("explorations/eda/README.md", "# eda\n").unwrap();
+            }
+
+            let outcome = TemplateUpdate { dry_run: false }
+                .update("explorations/eda/README.md", &mut record("abc123"));
+
+            assert_eq!(outcome, expected);
+        },
+        false,
+    )
+}
+
+#[test]
+fn current_template_is_up_to_date() {
+    test_in_tmp_dir(
+        || {
+            let current = template_text("explorations/README.md").unwrap();
+            fs::create_dir_all("explorations/eda").unwrap();
+            fs::write("explorations/eda/README.md", "# eda\n").unwrap();
+
+            let outcome = TemplateUpdate { dry_run: false }.update(
+                "explorations/eda/README.md",
+                &mut record(&sha256_hex(current.as_bytes())),
+            );
+
+            assert_eq!(outcome, Outcome::UpToDate);
+        },
+        false,
+    )
+}
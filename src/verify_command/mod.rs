@@ -0,0 +1,129 @@
+use clap::Args;
+use console::Style;
+use std::{collections::BTreeSet, path};
+
+use crate::checksum;
+use crate::component::{ComponentId, Stage};
+use crate::data_source_command::{list_data_files, list_data_sources};
+use crate::manifest::Manifest;
+
+#[derive(Args)]
+pub struct Verify {}
+
+/// The data files that don't match the checksums in the manifest
+///
+/// Each path is relative to the project root.
+#[derive(Debug, Default, PartialEq)]
+pub struct VerifyReport {
+    /// Files whose contents have changed since they were locked
+    pub modified: Vec<String>,
+    /// Files that were locked but no longer exist
+    pub missing: Vec<String>,
+    /// Files that exist but haven't been locked
+    pub untracked: Vec<String>,
+}
+
+impl VerifyReport {
+    /// Whether every data file matches the manifest
+    pub fn is_clean(&self) -> bool {
+        self.modified.is_empty() && self.missing.is_empty() && self.untracked.is_empty()
+    }
+
+    /// Formats the report with a line for each file that doesn't match
+    pub fn summary(&self) -> String {
+        if self.is_clean() {
+            return format!(
+                "{}",
+                Style::new()
+                    .color256(10)
+                    .apply_to("All data files match their checksums")
+            );
+        }
+
+        let sections = [
+            ("modified", &self.modified, 9),
+            ("missing", &self.missing, 9),
+            ("untracked", &self.untracked, 220),
+        ];
+
+        sections
+            .iter()
+            .flat_map(|(label, files, col_256)| {
+                files.iter().map(move |file| {
+                    format!(
+                        "{} {}",
+                        Style::new()
+                            .color256(*col_256)
+                            .apply_to(format!("{:>10}:", label)),
+                        file
+                    )
+                })
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Verify {
+    /// Compares the data files in every data source with the manifest
+    ///
+    /// Data sources that are recorded in the manifest but have been deleted
+    /// are also checked so their files are reported as missing. Archived data
+    /// sources are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The project manifest holding the locked checksums
+    pub fn verify(&self, manifest: &Manifest) -> VerifyReport {
+        let mut report = VerifyReport::default();
+
+        let mut names: BTreeSet<String> = list_data_sources().into_iter().collect();
+        names.extend(
+            manifest
+                .data_sources
+                .keys()
+                .filter(|name| {
+                    !manifest
+                        .archived
+                        .contains_key(&ComponentId::new(Stage::DataSource, name))
+                })
+                .cloned(),
+        );
+
+        for name in names.iter() {
+            let data_source = path::Path::new("data_sources").join(name);
+            let locked = manifest
+                .data_sources
+                .get(name)
+                .map(|entry| entry.checksums.clone())
+                .unwrap_or_default();
+
+            let display_path = |data_file: &str| format!("data_sources/{}/{}", name, data_file);
+
+            for data_file in list_data_files(&data_source) {
+                match locked.get(&data_file) {
+                    Some(hash) => {
+                        let current = checksum::file_sha256(&data_source.join(&data_file))
+                            .unwrap_or_default();
+
+                        if &current != hash {
+                            report.modified.push(display_path(&data_file));
+                        }
+                    }
+                    None => report.untracked.push(display_path(&data_file)),
+                }
+            }
+
+            for data_file in locked.keys() {
+                if !data_source.join(data_file).is_file() {
+                    report.missing.push(display_path(data_file));
+                }
+            }
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,117 @@
+use std::{collections::BTreeMap, fs};
+use test_fixtures::test_in_tmp_dir;
+
+use super::*;
+
+fn locked_manifest(files: &[(&str, &[u8])]) -> Manifest {
+    let mut manifest = Manifest::default();
+    manifest.data_source_mut("iris").checksums = files
+        .iter()
+        .map(|(file, contents)| (file.to_string(), checksum::sha256_hex(contents)))
+        .collect::<BTreeMap<_, _>>();
+
+    manifest
+}
+
+// ++++++ //
+// verify //
+// ++++++ //
+#[test]
+fn unchanged_files_are_clean() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+            fs::write("data_sources/iris/metadata.md", "# iris\n").unwrap();
+            fs::write("data_sources/iris/iris.csv", "id\n").unwrap();
+
+            let report = Verify {}.verify(&locked_manifest(&[("iris.csv", b"id\n")]));
+
+            assert!(report.is_clean());
+        },
+        false,
+    )
+}
+
+#[test]
+fn reports_modified_missing_and_untracked_files() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/iris").unwrap();
+            fs::create_dir_all("data_sources/penguins").unwrap();
+            fs::write("data_sources/iris/iris.csv", "id,edited\n").unwrap();
+            fs::write("data_sources/iris/extra.csv", "id\n").unwrap();
+            fs::write("data_sources/penguins/penguins.csv", "id\n").unwrap();
+
+            let report = Verify {}.verify(&locked_manifest(&[
+                ("iris.csv", b"id\n"),
+                ("old.csv", b"id\n"),
+            ]));
+
+            assert_eq!(
+                report,
+                VerifyReport {
+                    modified: vec![String::from("data_sources/iris/iris.csv")],
+                    missing: vec![String::from("data_sources/iris/old.csv")],
+                    untracked: vec![
+                        String::from("data_sources/iris/extra.csv"),
+                        String::from("data_sources/penguins/penguins.csv"),
+                    ],
+                }
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn deleted_data_source_files_are_missing() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources").unwrap();
+
+            let report = Verify {}.verify(&locked_manifest(&[("iris.csv", b"id\n")]));
+
+            assert_eq!(report.missing, vec!["data_sources/iris/iris.csv"]);
+        },
+        false,
+    )
+}
+
+#[test]
+fn archived_data_sources_are_skipped() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("data_sources/_archive/iris").unwrap();
+            fs::write("data_sources/_archive/iris/iris.csv", "id\n").unwrap();
+
+            let mut manifest = locked_manifest(&[("iris.csv", b"id\n")]);
+            manifest.archived.insert(
+                ComponentId::new(Stage::DataSource, "iris"),
+                crate::manifest::ArchiveEntry {
+                    archived: String::from("2022-04-01"),
+                    reason: None,
+                },
+            );
+
+            assert!(Verify {}.verify(&manifest).is_clean());
+        },
+        false,
+    )
+}
+
+// +++++++ //
+// summary //
+// +++++++ //
+#[test]
+fn summary_lists_each_file() {
+    let report = VerifyReport {
+        modified: vec![String::from("data_sources/iris/iris.csv")],
+        missing: vec![],
+        untracked: vec![String::from("data_sources/iris/extra.csv")],
+    };
+
+    let summary = report.summary();
+
+    assert!(summary.contains("modified: data_sources/iris/iris.csv"));
+    assert!(summary.contains("untracked: data_sources/iris/extra.csv"));
+}
@@ -1,5 +1,8 @@
 use rand::Rng;
-use std::{env, fs, panic, path};
+use std::{env, fs, panic, path, sync};
+
+/// Guards the working directory so only one test changes it at a time
+static CWD_LOCK: sync::Mutex<()> = sync::Mutex::new(());
 
 /// Runs a test within a temporary directory
 ///
@@ -8,6 +11,9 @@ use std::{env, fs, panic, path};
 /// Following the execution of the test the temporary directory and all files
 /// within it are deleted.
 ///
+/// The working directory is shared by every test in the process so tests run
+/// through this function are serialised.
+///
 /// # Arguments:
 ///
 /// * `test` - any type that implements the FnOnce trait and returns either
@@ -17,6 +23,7 @@ where
     T: FnOnce() -> () + panic::UnwindSafe,
 {
     // Setup
+    let _guard = CWD_LOCK.lock().unwrap_or_else(|err| err.into_inner());
     let cwd: path::PathBuf = env::current_dir().expect("Unable to get current directory");
     let tmp_dir: path::PathBuf = create_random_tmp_dir().unwrap();
 
@@ -1,2 +1,20 @@
+mod test_add_component;
 mod test_add_data_source;
+mod test_adopt_command;
+mod test_apply_command;
+mod test_archive_command;
+mod test_audit_command;
+mod test_check_command;
 mod test_create_command;
+mod test_data_source_fetch;
+mod test_data_source_receive;
+mod test_lineage;
+mod test_migrate_command;
+mod test_promote_command;
+mod test_remove_command;
+mod test_rename_command;
+mod test_retention_command;
+mod test_show_command;
+mod test_sync_command;
+mod test_template_command;
+mod test_verify_command;
@@ -0,0 +1,115 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use rstest::rstest;
+use std::{env, fs, path, process};
+use test_fixtures::test_in_tmp_dir;
+
+#[rstest]
+#[case("exploration", "explorations")]
+#[case("model", "models")]
+#[case("product", "products")]
+fn creates_component_with_readme(#[case] stage: &str, #[case] folder: &str) {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all(format!("./test_project/{}", folder)).unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("add")
+                .arg(stage)
+                .arg("--owner")
+                .arg("Ronald Fisher")
+                .arg("iris_work");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains(format!(
+                    "🖿 {}\n└─ 🖿 iris_work\n   └─ 🗎 README.md",
+                    folder
+                )));
+
+            let readme =
+                fs::read_to_string(path::Path::new(folder).join("iris_work/README.md")).unwrap();
+            assert!(readme.starts_with(&format!(
+                "---\nname: iris_work\nstage: {}\nowner: Ronald Fisher\ncreated: ",
+                stage
+            )));
+            assert!(readme.contains("# iris_work\n"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn records_components_used() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources/iris").unwrap();
+            fs::create_dir_all("./test_project/explorations").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("add")
+                .arg("exploration")
+                .arg("--uses")
+                .arg("iris")
+                .arg("eda");
+            cmd.assert().success();
+
+            let manifest = fs::read_to_string("geoff.toml").unwrap();
+            assert!(manifest.contains("\"explorations/eda\" = ['data_sources/iris']"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn unknown_component_used_errors_before_creating() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/explorations").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("add")
+                .arg("exploration")
+                .arg("--uses")
+                .arg("penguins")
+                .arg("eda");
+
+            cmd.assert().failure().stderr(predicate::str::contains(
+                "penguins isn't a component in this project",
+            ));
+            assert!(!path::Path::new("explorations/eda").exists());
+        },
+        false,
+    )
+}
+
+#[test]
+fn name_follows_stage_convention() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/models").unwrap();
+            env::set_current_dir("test_project").unwrap();
+            fs::write(
+                "geoff.toml",
+                "[naming.conventions]\nmodels = \"kebab-case\"\n",
+            )
+            .unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["add", "model", "iris_classifier"]);
+            cmd.assert().failure().stderr(predicate::str::contains(
+                "isn't kebab-case, try iris-classifier",
+            ));
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["add", "model", "Iris Classifier", "--slugify"]);
+            cmd.assert().success();
+
+            assert!(path::Path::new("models/iris-classifier/README.md").exists());
+        },
+        false,
+    )
+}
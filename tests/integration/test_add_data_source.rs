@@ -6,13 +6,16 @@ use test_fixtures::test_in_tmp_dir;
 
 #[rstest]
 #[case("")]
-#[case("--database")]
-#[case("-d")]
-#[case("--extract")]
-#[case("-e")]
-#[case("--web")]
-#[case("-w")]
-fn creates_new_default_data_source(#[case] option: &str) -> () {
+#[case("--kind=database")]
+#[case("-kdatabase")]
+#[case("--kind=extract")]
+#[case("-kextract")]
+#[case("--kind=web")]
+#[case("-kweb")]
+#[case("--kind=api")]
+#[case("--kind=survey")]
+#[case("--kind=synthetic")]
+fn creates_new_default_data_source(#[case] option: &str) {
     test_in_tmp_dir(
         || {
             fs::create_dir_all("./test_project/data_sources").unwrap();
@@ -36,13 +39,16 @@ fn creates_new_default_data_source(#[case] option: &str) -> () {
 
 #[rstest]
 #[case("")]
-#[case("--database")]
-#[case("-d")]
-#[case("--extract")]
-#[case("-e")]
-#[case("--web")]
-#[case("-w")]
-fn creates_files_default_data_source(#[case] option: &str) -> () {
+#[case("--kind=database")]
+#[case("-kdatabase")]
+#[case("--kind=extract")]
+#[case("-kextract")]
+#[case("--kind=web")]
+#[case("-kweb")]
+#[case("--kind=api")]
+#[case("--kind=survey")]
+#[case("--kind=synthetic")]
+fn creates_files_default_data_source(#[case] option: &str) {
     test_in_tmp_dir(
         || {
             fs::create_dir_all("./test_project/data_sources").unwrap();
@@ -65,13 +71,16 @@ fn creates_files_default_data_source(#[case] option: &str) -> () {
 
 #[rstest]
 #[case("")]
-#[case("--database")]
-#[case("-d")]
-#[case("--extract")]
-#[case("-e")]
-#[case("--web")]
-#[case("-w")]
-fn prints_default_data_source_tree(#[case] option: &str) -> () {
+#[case("--kind=database")]
+#[case("-kdatabase")]
+#[case("--kind=extract")]
+#[case("-kextract")]
+#[case("--kind=web")]
+#[case("-kweb")]
+#[case("--kind=api")]
+#[case("--kind=survey")]
+#[case("--kind=synthetic")]
+fn prints_default_data_source_tree(#[case] option: &str) {
     test_in_tmp_dir(
         || {
             fs::create_dir_all("./test_project/data_sources").unwrap();
@@ -97,7 +106,7 @@ fn prints_default_data_source_tree(#[case] option: &str) -> () {
 // No options //
 // ++++++++++ //
 #[test]
-fn default_metadata_has_only_title() -> () {
+fn default_metadata_has_only_front_matter_and_title() {
     test_in_tmp_dir(
         || {
             fs::create_dir_all("./test_project/data_sources").unwrap();
@@ -112,9 +121,15 @@ fn default_metadata_has_only_title() -> () {
                 fs::read_to_string("./data_sources/test_data_source/metadata.md").unwrap();
 
             let file_contains = if env::consts::OS == "windows" {
-                predicates::str::is_match("^# test_data_source\r\n$").unwrap()
+                predicates::str::is_match(
+                    "^---\nname: test_data_source\ncreated: \\d{4}-\\d{2}-\\d{2}\n---\n\n# test_data_source\r\n$",
+                )
+                .unwrap()
             } else {
-                predicates::str::is_match("^# test_data_source\n$").unwrap()
+                predicates::str::is_match(
+                    "^---\nname: test_data_source\ncreated: \\d{4}-\\d{2}-\\d{2}\n---\n\n# test_data_source\n$",
+                )
+                .unwrap()
             };
 
             assert!(file_contains.eval(&contents))
@@ -123,13 +138,134 @@ fn default_metadata_has_only_title() -> () {
     )
 }
 
+#[test]
+fn front_matter_populated_from_arguments() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+
+            cmd.arg("add")
+                .arg("data-source")
+                .arg("--kind=extract")
+                .arg("--owner")
+                .arg("Ronald Fisher")
+                .arg("test_data_source");
+            cmd.assert().success();
+
+            let contents =
+                fs::read_to_string("./data_sources/test_data_source/metadata.md").unwrap();
+
+            assert!(contents.starts_with(
+                "---\nname: test_data_source\nkind: extract\nowner: Ronald Fisher\ncreated: "
+            ));
+        },
+        false,
+    )
+}
+
+#[rstest]
+#[case("--sensitivity=public", "public")]
+#[case("-sinternal", "internal")]
+#[case("--sensitivity=confidential", "confidential")]
+#[case("--sensitivity=restricted", "restricted")]
+fn sensitivity_recorded_in_front_matter(#[case] option: &str, #[case] sensitivity: &str) {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+
+            cmd.arg("add")
+                .arg("data-source")
+                .arg(option)
+                .arg("test_data_source");
+            cmd.assert().success();
+
+            let contents =
+                fs::read_to_string("./data_sources/test_data_source/metadata.md").unwrap();
+
+            assert!(contents.contains(&format!("\nsensitivity: {}\n", sensitivity)));
+        },
+        false,
+    )
+}
+
+#[test]
+fn expiry_date_recorded_in_front_matter() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+
+            cmd.arg("add")
+                .arg("data-source")
+                .arg("--expires=2025-03-31")
+                .arg("test_data_source");
+            cmd.assert().success();
+
+            let contents =
+                fs::read_to_string("./data_sources/test_data_source/metadata.md").unwrap();
+
+            assert!(contents.contains("\nexpires: 2025-03-31\n---\n"));
+        },
+        false,
+    )
+}
+
+#[rstest]
+#[case("--expires=31/03/2025")]
+#[case("--expires=soon")]
+#[case("-e2025-03-31")]
+fn invalid_expiry_date_should_error(#[case] option: &str) {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+
+            cmd.arg("add")
+                .arg("data-source")
+                .arg(option)
+                .arg("test_data_source");
+            cmd.assert().failure();
+        },
+        false,
+    )
+}
+
+#[test]
+fn invalid_sensitivity_should_error() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+
+            cmd.arg("add")
+                .arg("data-source")
+                .arg("--sensitivity=secret")
+                .arg("test_data_source");
+            cmd.assert().failure();
+        },
+        false,
+    )
+}
+
 // ++++++++ //
 // Database //
 // ++++++++ //
 #[rstest]
-#[case("--database")]
-#[case("-d")]
-fn database_metadata_contents_correct(#[case] option: &str) -> () {
+#[case("--kind=database")]
+#[case("-kdatabase")]
+fn database_metadata_contents_correct(#[case] option: &str) {
     test_in_tmp_dir(
         || {
             fs::create_dir_all("./test_project/data_sources").unwrap();
@@ -158,9 +294,9 @@ fn database_metadata_contents_correct(#[case] option: &str) -> () {
 // Extract //
 // +++++++ //
 #[rstest]
-#[case("--extract")]
-#[case("-e")]
-fn extract_metadata_contents_correct(#[case] option: &str) -> () {
+#[case("--kind=extract")]
+#[case("-kextract")]
+fn extract_metadata_contents_correct(#[case] option: &str) {
     test_in_tmp_dir(
         || {
             fs::create_dir_all("./test_project/data_sources").unwrap();
@@ -189,9 +325,9 @@ fn extract_metadata_contents_correct(#[case] option: &str) -> () {
 // Web //
 // +++ //
 #[rstest]
-#[case("--web")]
-#[case("-w")]
-fn web_metadata_contents_correct(#[case] option: &str) -> () {
+#[case("--kind=web")]
+#[case("-kweb")]
+fn web_metadata_contents_correct(#[case] option: &str) {
     test_in_tmp_dir(
         || {
             fs::create_dir_all("./test_project/data_sources").unwrap();
@@ -216,15 +352,48 @@ fn web_metadata_contents_correct(#[case] option: &str) -> () {
     )
 }
 
-// ++++++++++++++++ //
-// Multiple options //
-// ++++++++++++++++ //
+// +++++++++++++++++++++++ //
+// API, survey & synthetic //
+// +++++++++++++++++++++++ //
 #[rstest]
-#[case("--web --database")]
-#[case("--web --extract")]
-#[case("--extract --database")]
-#[case("--web --extract --database")]
-fn multiple_options_should_error(#[case] option: &str) -> () {
+#[case("api", "## API details")]
+#[case("survey", "## Survey details")]
+#[case("synthetic", "## Synthetic data details")]
+fn new_kind_metadata_contents_correct(#[case] kind: &str, #[case] details: &str) {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+
+            cmd.arg("add")
+                .arg("data-source")
+                .arg("--kind")
+                .arg(kind)
+                .arg("test_data_source");
+            cmd.assert().success();
+
+            let contents =
+                fs::read_to_string("./data_sources/test_data_source/metadata.md").unwrap();
+            assert!(predicates::str::contains("# test_data_source").eval(&contents));
+            assert!(predicates::str::contains(details).eval(&contents));
+            assert!(predicates::str::contains("## Data dictionary").eval(&contents));
+            assert!(predicates::str::contains("## Key people").eval(&contents))
+        },
+        false,
+    )
+}
+
+// +++++++++++++ //
+// Invalid kinds //
+// +++++++++++++ //
+#[rstest]
+#[case("--kind=web --kind=database")]
+#[case("--kind=spreadsheet")]
+#[case("--kind")]
+#[case("--web")]
+fn invalid_kind_should_error(#[case] option: &str) {
     test_in_tmp_dir(
         || {
             fs::create_dir_all("./test_project/data_sources").unwrap();
@@ -244,3 +413,195 @@ fn multiple_options_should_error(#[case] option: &str) -> () {
         false,
     )
 }
+
+// +++++++++++ //
+// Interactive //
+// +++++++++++ //
+#[test]
+fn interactive_answers_rendered_into_metadata() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            let mut cmd = assert_cmd::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["add", "data-source", "--kind=web", "--interactive", "iris"])
+                .write_stdin("Ronald Fisher\n\nhttps://example.com/iris.data\n\n");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("What is the download URL?"));
+
+            let contents = fs::read_to_string("./data_sources/iris/metadata.md").unwrap();
+            assert!(contents.contains("owner: Ronald Fisher\n"));
+            assert!(!contents.contains("description:"));
+            assert!(contents.contains("| *e.g iris.data* | https://example.com/iris.data |"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn answers_file_used_without_prompting() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+            fs::write(
+                "answers.yaml",
+                "tables: flowers\ndescription: Fisher's iris data\n",
+            )
+            .unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args([
+                "add",
+                "data-source",
+                "--kind=database",
+                "--answers",
+                "answers.yaml",
+                "iris",
+            ]);
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("?").not());
+
+            let contents = fs::read_to_string("./data_sources/iris/metadata.md").unwrap();
+            assert!(contents.contains("description: Fisher's iris data\n"));
+            assert!(contents.contains("| *e.g datasets* | flowers | *e.g iris_transformed* |"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn creates_data_sources_from_csv() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources/sales").unwrap();
+            env::set_current_dir("test_project").unwrap();
+            fs::write(
+                "inventory.csv",
+                "Name,Kind,Owner,Description,URL\n\
+                iris,,Ronald Fisher,Fisher's iris data,https://example.com/iris.data\n\
+                sales,database,Finance,,\n\
+                crm,extract,,Customer records,\n",
+            )
+            .unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args([
+                "add",
+                "data-source",
+                "--from-csv",
+                "inventory.csv",
+                "--sensitivity",
+                "internal",
+            ]);
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains(
+                    "2 data sources created from inventory.csv!",
+                ))
+                .stdout(predicate::str::contains(
+                    "line 3 data_sources/sales already exists, skipped",
+                ));
+
+            let iris = fs::read_to_string("./data_sources/iris/metadata.md").unwrap();
+            assert!(iris.contains("kind: web\n"));
+            assert!(iris.contains("owner: Ronald Fisher\n"));
+            assert!(iris.contains("sensitivity: internal\n"));
+            assert!(iris.contains("| *e.g iris.data* | https://example.com/iris.data |"));
+
+            let crm = fs::read_to_string("./data_sources/crm/metadata.md").unwrap();
+            assert!(crm.contains("description: Customer records\n"));
+
+            assert!(!path::Path::new("./data_sources/sales/metadata.md").exists());
+        },
+        false,
+    )
+}
+
+#[test]
+fn reports_invalid_csv_rows() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+            fs::write("inventory.csv", "name,kind\niris,web\nsales,spreadsheet\n").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["add", "data-source", "--from-csv", "inventory.csv"]);
+
+            cmd.assert()
+                .failure()
+                .code(1)
+                .stdout(predicate::str::contains(
+                    "1 data sources created from inventory.csv!",
+                ))
+                .stdout(predicate::str::contains("line 3 has the kind spreadsheet"));
+
+            assert!(path::Path::new("./data_sources/iris/metadata.md").exists());
+            assert!(!path::Path::new("./data_sources/sales").exists());
+        },
+        false,
+    )
+}
+
+#[test]
+fn name_or_csv_required() {
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+    cmd.args(["add", "data-source"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("<NAME>"));
+}
+
+#[test]
+fn slugifies_name_keeping_title() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["add", "data-source", "Sales Data 2022", "--slugify"]);
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("sales_data_2022 created!"));
+
+            let metadata =
+                fs::read_to_string("./data_sources/sales_data_2022/metadata.md").unwrap();
+            assert!(metadata.contains("name: sales_data_2022\n"));
+            assert!(metadata.contains("title: Sales Data 2022\n"));
+            assert!(metadata.contains("# Sales Data 2022\n"));
+        },
+        false,
+    )
+}
+
+#[rstest]
+#[case("../oops", "contains a path separator")]
+#[case("Sales Data", "contains a space, try sales_data")]
+#[case("con", "is reserved")]
+fn invalid_name_errors_before_creating(#[case] name: &str, #[case] reason: &str) {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["add", "data-source", name]);
+
+            cmd.assert()
+                .failure()
+                .stderr(predicate::str::contains(reason));
+            assert_eq!(fs::read_dir("./data_sources").unwrap().count(), 0);
+        },
+        false,
+    )
+}
@@ -0,0 +1,80 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{env, fs, path, process};
+use test_fixtures::test_in_tmp_dir;
+
+fn setup_folder() {
+    fs::create_dir_all("old_project/raw/iris").unwrap();
+    fs::write("old_project/raw/iris/iris.csv", "id\n").unwrap();
+    fs::create_dir_all("old_project/scripts").unwrap();
+    fs::write("old_project/scripts/train.py", "print('hi')").unwrap();
+}
+
+#[test]
+fn adopt_maps_folders_and_registers_components() {
+    test_in_tmp_dir(
+        || {
+            setup_folder();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["adopt", "old_project", "--map", "scripts=model", "--yes"]);
+
+            cmd.assert().success().stdout(predicate::str::contains(
+                "old_project adopted with 2 components!",
+            ));
+
+            env::set_current_dir("old_project").unwrap();
+
+            assert!(path::Path::new(".geoff").exists());
+            assert!(path::Path::new("products").is_dir());
+            assert!(path::Path::new("data_sources/iris/metadata.md").exists());
+            assert!(path::Path::new("models/train/train.py").exists());
+
+            let readme = fs::read_to_string("README.md").unwrap();
+            assert!(readme.contains("# old_project\n"));
+            assert!(readme.contains("* [iris](data_sources/iris/metadata.md)\n"));
+            assert!(readme.contains("* [train](models/train/README.md)\n"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn adopt_asks_for_confirmation() {
+    test_in_tmp_dir(
+        || {
+            setup_folder();
+
+            let mut cmd = assert_cmd::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["adopt", "old_project"]).write_stdin("n\n");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("\u{2190} raw/iris"))
+                .stdout(predicate::str::contains("Nothing was adopted"));
+
+            assert!(!path::Path::new("old_project/.geoff").exists());
+            assert!(path::Path::new("old_project/raw/iris/iris.csv").exists());
+        },
+        false,
+    )
+}
+
+#[test]
+fn adopt_errors_for_geoff_project() {
+    test_in_tmp_dir(
+        || {
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("create").arg("test_project");
+            cmd.assert().success();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["adopt", "test_project", "--yes"]);
+
+            cmd.assert()
+                .failure()
+                .stderr(predicate::str::contains("is already managed by geoff"));
+        },
+        false,
+    )
+}
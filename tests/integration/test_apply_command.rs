@@ -0,0 +1,184 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{fs, path, process};
+use test_fixtures::test_in_tmp_dir;
+
+const SPEC: &str = "name: iris_classifier
+details:
+  problem: Botanists can't tell iris species apart
+layout:
+  - notebooks
+data_sources:
+  - name: iris
+    kind: web
+    owner: Ronald Fisher
+    metadata:
+      description: Measurements of 150 iris flowers
+      download_url: https://example.com/iris.data
+explorations:
+  - name: eda
+    uses: [iris]
+models:
+  - name: classifier
+    owner: Edgar Anderson
+    uses: [eda]
+";
+
+fn apply() -> process::Command {
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+    cmd.args(["apply", "project.yaml"]);
+    cmd
+}
+
+#[test]
+fn apply_creates_project() {
+    test_in_tmp_dir(
+        || {
+            fs::write("project.yaml", SPEC).unwrap();
+
+            apply()
+                .assert()
+                .success()
+                .stdout(predicate::str::contains("iris_classifier applied!"))
+                .stdout(predicate::str::contains(
+                    "models/classifier uses explorations/eda",
+                ));
+
+            assert!(path::Path::new("iris_classifier/.geoff").exists());
+            assert!(path::Path::new("iris_classifier/notebooks").is_dir());
+
+            let readme = fs::read_to_string("iris_classifier/README.md").unwrap();
+            assert!(readme.contains("Botanists can't tell iris species apart\n"));
+            assert!(readme.contains(
+                "* [iris](data_sources/iris/metadata.md) - Measurements of 150 iris flowers\n"
+            ));
+
+            let metadata =
+                fs::read_to_string("iris_classifier/data_sources/iris/metadata.md").unwrap();
+            assert!(metadata.contains("owner: Ronald Fisher\n"));
+            assert!(metadata.contains("https://example.com/iris.data"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn apply_is_idempotent() {
+    test_in_tmp_dir(
+        || {
+            fs::write("project.yaml", SPEC).unwrap();
+            apply().assert().success();
+            let manifest = fs::read_to_string("iris_classifier/geoff.toml").unwrap();
+
+            apply().assert().success().stdout(predicate::str::contains(
+                "Nothing to create, iris_classifier already has everything in the spec",
+            ));
+
+            assert_eq!(
+                fs::read_to_string("iris_classifier/geoff.toml").unwrap(),
+                manifest
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn apply_adds_whats_missing() {
+    test_in_tmp_dir(
+        || {
+            fs::write("project.yaml", SPEC).unwrap();
+            apply().assert().success();
+
+            fs::write(
+                "project.yaml",
+                format!(
+                    "{}products:\n  - name: dashboard\n    uses: [classifier]\n",
+                    SPEC
+                ),
+            )
+            .unwrap();
+
+            apply()
+                .assert()
+                .success()
+                .stdout(predicate::str::contains(
+                    "products/dashboard uses models/classifier",
+                ))
+                .stdout(predicate::str::contains("explorations/eda uses").not());
+
+            assert!(path::Path::new("iris_classifier/products/dashboard/README.md").exists());
+        },
+        false,
+    )
+}
+
+#[test]
+fn apply_reports_drift() {
+    test_in_tmp_dir(
+        || {
+            fs::write("project.yaml", SPEC).unwrap();
+            apply().assert().success();
+
+            fs::create_dir("iris_classifier/products/dashboard").unwrap();
+            let readme_path = "iris_classifier/models/classifier/README.md";
+            let readme = fs::read_to_string(readme_path).unwrap();
+            fs::write(
+                readme_path,
+                readme.replace("owner: Edgar Anderson", "owner: Ronald Fisher"),
+            )
+            .unwrap();
+
+            apply()
+                .assert()
+                .failure()
+                .stdout(predicate::str::contains(
+                    "These differ from the spec and were left alone",
+                ))
+                .stdout(predicate::str::contains(
+                    "models/classifier owner is Edgar Anderson in the spec but Ronald Fisher in the project",
+                ))
+                .stdout(predicate::str::contains("products/dashboard isn't in the spec"));
+
+            assert!(fs::read_to_string(readme_path)
+                .unwrap()
+                .contains("owner: Ronald Fisher"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn apply_dry_run_creates_nothing() {
+    test_in_tmp_dir(
+        || {
+            fs::write("project.yaml", SPEC).unwrap();
+
+            apply()
+                .arg("--dry-run")
+                .assert()
+                .success()
+                .stdout(predicate::str::contains(
+                    "Dry run, iris_classifier would be changed",
+                ));
+
+            assert!(!path::Path::new("iris_classifier").exists());
+        },
+        false,
+    )
+}
+
+#[test]
+fn apply_refuses_unmanaged_folder() {
+    test_in_tmp_dir(
+        || {
+            fs::write("project.yaml", SPEC).unwrap();
+            fs::create_dir("iris_classifier").unwrap();
+
+            apply().assert().failure().stderr(predicate::str::contains(
+                "exists but isn't managed by geoff, see geoff adopt",
+            ));
+        },
+        false,
+    )
+}
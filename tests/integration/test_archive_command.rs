@@ -0,0 +1,145 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{env, fs, path, process};
+use test_fixtures::test_in_tmp_dir;
+
+fn setup_project() {
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+    cmd.arg("create").arg("test_project");
+    cmd.assert().success();
+
+    env::set_current_dir("test_project").unwrap();
+
+    let commands: [&[&str]; 2] = [
+        &["add", "data-source", "iris"],
+        &["add", "exploration", "eda", "--uses", "iris"],
+    ];
+    for args in commands.iter() {
+        let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+        cmd.args(args.iter());
+        cmd.assert().success();
+    }
+}
+
+#[test]
+fn archive_moves_component_and_warns_about_dependents() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("archive")
+                .arg("data-source")
+                .arg("iris")
+                .arg("--reason")
+                .arg("Agreement ended");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("data_sources/iris archived!"))
+                .stderr(predicate::str::contains(
+                    "data_sources/iris is still used by explorations/eda",
+                ));
+
+            assert!(path::Path::new("data_sources/_archive/iris/metadata.md").exists());
+
+            let manifest = fs::read_to_string("geoff.toml").unwrap();
+            assert!(manifest.contains("[archived.\"data_sources/iris\"]"));
+            assert!(manifest.contains("reason = 'Agreement ended'"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn archived_components_hidden_from_list() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("archive").arg("exploration").arg("eda");
+            cmd.assert().success();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("list").arg("exploration");
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("eda").not());
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("list").arg("--archived");
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("_archive"))
+                .stdout(predicate::str::contains("eda ("));
+        },
+        false,
+    )
+}
+
+#[test]
+fn restore_brings_component_back() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("archive").arg("exploration").arg("eda");
+            cmd.assert().success();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("restore").arg("exploration").arg("eda");
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("explorations/eda restored!"));
+
+            assert!(path::Path::new("explorations/eda/README.md").exists());
+
+            let manifest = fs::read_to_string("geoff.toml").unwrap();
+            assert!(!manifest.contains("[archived"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn restore_errors_when_not_archived() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("restore").arg("exploration").arg("eda");
+
+            cmd.assert().failure().stderr(predicate::str::contains(
+                "isn't an archived component in this project",
+            ));
+        },
+        false,
+    )
+}
+
+#[test]
+fn archive_and_restore_by_start_of_name() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("archive").arg("exploration").arg("ed");
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("explorations/eda archived!"));
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("restore").arg("exploration").arg("ed");
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("explorations/eda restored!"));
+
+            assert!(path::Path::new("explorations/eda/README.md").exists());
+        },
+        false,
+    )
+}
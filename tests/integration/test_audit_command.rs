@@ -0,0 +1,92 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{env, fs, process};
+use test_fixtures::test_in_tmp_dir;
+
+fn add_patients_data_source() {
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+    cmd.arg("add")
+        .arg("data-source")
+        .arg("--kind=extract")
+        .arg("--owner")
+        .arg("Florence Nightingale")
+        .arg("--sensitivity=restricted")
+        .arg("patients");
+    cmd.assert().success();
+
+    let metadata = fs::read_to_string("data_sources/patients/metadata.md").unwrap();
+    fs::write(
+        "data_sources/patients/metadata.md",
+        metadata.replace(
+            "| *e.g ID* | *e.g INT* | *e.g A unique identifier for each record* | *e.g no* |",
+            "| id | INT | A unique identifier for each record | no |\n\
+            | full_name | VARCHAR | The patient's name | yes |",
+        ),
+    )
+    .unwrap();
+}
+
+#[test]
+fn lists_pii_fields_with_data_source_and_owner() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            add_patients_data_source();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("audit").arg("pii");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("patients"))
+                .stdout(predicate::str::contains("full_name"))
+                .stdout(predicate::str::contains("Florence Nightingale"))
+                .stdout(predicate::str::contains("restricted"))
+                .stdout(predicate::str::contains("sepal_width").not());
+        },
+        false,
+    )
+}
+
+#[test]
+fn lists_pii_fields_as_json() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            add_patients_data_source();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("audit").arg("pii").arg("--format=json");
+
+            let output = cmd.assert().success().get_output().stdout.clone();
+            let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+            assert_eq!(json.as_array().unwrap().len(), 1);
+            assert_eq!(json[0]["field"], "full_name");
+            assert_eq!(json[0]["owner"], "Florence Nightingale");
+        },
+        false,
+    )
+}
+
+#[test]
+fn reports_when_no_pii() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("audit").arg("pii");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("No fields are flagged as PII"));
+        },
+        false,
+    )
+}
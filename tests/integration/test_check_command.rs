@@ -0,0 +1,86 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{env, fs, process};
+use test_fixtures::test_in_tmp_dir;
+
+#[test]
+fn reports_unfilled_template_text_in_new_project() {
+    test_in_tmp_dir(
+        || {
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("create").arg("test_project");
+            cmd.assert().success();
+
+            env::set_current_dir("test_project").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("add")
+                .arg("data-source")
+                .arg("--kind=database")
+                .arg("iris");
+            cmd.assert().success();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("check");
+
+            cmd.assert()
+                .failure()
+                .stdout(predicate::str::contains(
+                    "[Introduction] template text *brief intro to project*",
+                ))
+                .stdout(predicate::str::contains("project_scoping.md:"))
+                .stdout(predicate::str::contains("data_sources/iris/metadata.md:"))
+                .stdout(predicate::str::contains("[Key people]"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn reports_unreplaced_tags() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources/iris").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            fs::write(
+                "data_sources/iris/metadata.md",
+                "# <<<data_source_name>>>\n",
+            )
+            .unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("check");
+
+            cmd.assert().failure().stdout(predicate::str::contains(
+                "data_sources/iris/metadata.md:1 [<<<data_source_name>>>] unreplaced tag <<<data_source_name>>>",
+            ));
+        },
+        false,
+    )
+}
+
+#[test]
+fn passes_when_documents_filled_in() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources/iris").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            fs::write("README.md", "# test_project\n## Introduction\nIris\n").unwrap();
+            fs::write(
+                "data_sources/iris/metadata.md",
+                "---\nname: iris\ncreated: 2022-04-01\n---\n\n# iris\n",
+            )
+            .unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("check");
+
+            cmd.assert().success().stdout(predicate::str::contains(
+                "Every document has been filled in",
+            ));
+        },
+        false,
+    )
+}
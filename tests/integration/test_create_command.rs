@@ -4,7 +4,7 @@ use std::{env, fs, path, process};
 use test_fixtures::test_in_tmp_dir;
 
 #[test]
-fn creates_new_project() -> () {
+fn creates_new_project() {
     test_in_tmp_dir(
         || {
             let mut cmd: process::Command = process::Command::cargo_bin("geoff").unwrap();
@@ -19,7 +19,7 @@ fn creates_new_project() -> () {
 }
 
 #[test]
-fn create_prints_created_message() -> () {
+fn create_prints_created_message() {
     test_in_tmp_dir(
         || {
             let mut cmd: process::Command = process::Command::cargo_bin("geoff").unwrap();
@@ -28,15 +28,13 @@ fn create_prints_created_message() -> () {
             cmd.assert()
                 .success()
                 .stdout(predicate::str::contains("\u{1F680} test_project created!"));
-
-            ()
         },
         false,
     )
 }
 
 #[test]
-fn creates_new_project_path_passed() -> () {
+fn creates_new_project_path_passed() {
     test_in_tmp_dir(
         || {
             let mut cmd: process::Command = process::Command::cargo_bin("geoff").unwrap();
@@ -53,7 +51,7 @@ fn creates_new_project_path_passed() -> () {
 }
 
 #[test]
-fn errors_on_dir_already_exists() -> () {
+fn errors_on_dir_already_exists() {
     test_in_tmp_dir(
         || {
             fs::create_dir("./test_project/").unwrap();
@@ -70,7 +68,7 @@ fn errors_on_dir_already_exists() -> () {
 }
 
 #[test]
-fn errors_on_parents_dont_exist() -> () {
+fn errors_on_parents_dont_exist() {
     test_in_tmp_dir(
         || {
             let mut cmd: process::Command = process::Command::cargo_bin("geoff").unwrap();
@@ -85,13 +83,12 @@ fn errors_on_parents_dont_exist() -> () {
 }
 
 #[test]
-fn errors_on_permission_denied() -> () {
+fn errors_on_permission_denied() {
     test_in_tmp_dir(
         || {
             if env::consts::OS == "windows" {
                 // FIXME: Unable to create a directory in windows that causes a
                 //        permissions error
-                assert!(true)
             } else {
                 // FIXME: Shouldn't be reliant on a system created folder
                 env::set_current_dir("/etc/").expect("Can't change to read only dir");
@@ -109,7 +106,7 @@ fn errors_on_permission_denied() -> () {
 }
 
 #[test]
-fn subdirectories_created() -> () {
+fn subdirectories_created() {
     test_in_tmp_dir(
         || {
             let mut cmd: process::Command = process::Command::cargo_bin("geoff").unwrap();
@@ -117,28 +114,32 @@ fn subdirectories_created() -> () {
             cmd.arg("create").arg("test_project");
             cmd.assert().success();
 
-            let mut expected_dirs = vec![
+            let mut expected_dirs = [
                 path::PathBuf::from("test_project/data_sources"),
                 path::PathBuf::from("test_project/explorations"),
                 path::PathBuf::from("test_project/models"),
                 path::PathBuf::from("test_project/products"),
+                path::PathBuf::from("test_project/.geoff_templates"),
             ];
 
-            let mut actual_dirs = fs::read_dir("./test_project/")
+            let mut actual_dirs = fs::read_dir("test_project/")
                 .unwrap()
                 .filter_map(|e| e.ok())
                 .filter(|e| e.file_type().unwrap().is_dir())
                 .map(|e| e.path())
                 .collect::<Vec<_>>();
 
-            assert_eq!(expected_dirs.sort(), actual_dirs.sort())
+            expected_dirs.sort();
+            actual_dirs.sort();
+
+            assert_eq!(expected_dirs.to_vec(), actual_dirs)
         },
         false,
     )
 }
 
 #[test]
-fn files_created() -> () {
+fn files_created() {
     test_in_tmp_dir(
         || {
             let mut cmd: process::Command = process::Command::cargo_bin("geoff").unwrap();
@@ -146,27 +147,31 @@ fn files_created() -> () {
             cmd.arg("create").arg("test_project");
             cmd.assert().success();
 
-            let mut expected_files = vec![
+            let mut expected_files = [
                 path::PathBuf::from("test_project/README.md"),
                 path::PathBuf::from("test_project/project_scoping.md"),
                 path::PathBuf::from("test_project/.geoff"),
+                path::PathBuf::from("test_project/geoff.toml"),
             ];
 
-            let mut actual_files = fs::read_dir("./test_project/")
+            let mut actual_files = fs::read_dir("test_project/")
                 .unwrap()
                 .filter_map(|e| e.ok())
                 .filter(|e| e.file_type().unwrap().is_file())
                 .map(|e| e.path())
                 .collect::<Vec<_>>();
 
-            assert_eq!(expected_files.sort(), actual_files.sort())
+            expected_files.sort();
+            actual_files.sort();
+
+            assert_eq!(expected_files.to_vec(), actual_files)
         },
         false,
     )
 }
 
 #[test]
-fn prints_tree() -> () {
+fn prints_tree() {
     test_in_tmp_dir(
         || {
             let mut cmd: process::Command = process::Command::cargo_bin("geoff").unwrap();
@@ -185,3 +190,66 @@ fn prints_tree() -> () {
         false,
     )
 }
+
+#[test]
+fn answers_rendered_into_root_documents() {
+    test_in_tmp_dir(
+        || {
+            fs::write(
+                "answers.yaml",
+                "problem: Botanists can't tell iris species apart\nstakeholders: Ronald Fisher\n",
+            )
+            .unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["create", "test_project", "--answers", "answers.yaml"]);
+            cmd.assert().success();
+
+            let readme = fs::read_to_string("test_project/README.md").unwrap();
+            assert!(readme.contains("## Description\nBotanists can't tell iris species apart\n"));
+            assert!(readme.contains("*brief intro to project*"));
+
+            let scoping = fs::read_to_string("test_project/project_scoping.md").unwrap();
+            assert!(
+                scoping.contains("## Business problem\nBotanists can't tell iris species apart\n")
+            );
+            assert!(scoping.contains("## Key stakeholders\nRonald Fisher\n"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn slugifies_project_name() {
+    test_in_tmp_dir(
+        || {
+            let mut cmd: process::Command = process::Command::cargo_bin("geoff").unwrap();
+
+            cmd.args(["create", "Iris Classifier", "--slugify"]);
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("iris_classifier created!"));
+
+            let readme = fs::read_to_string("iris_classifier/README.md").unwrap();
+            assert!(readme.contains("project: iris_classifier\n"));
+            assert!(readme.contains("title: Iris Classifier\n"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn errors_on_invalid_name() {
+    test_in_tmp_dir(
+        || {
+            let mut cmd: process::Command = process::Command::cargo_bin("geoff").unwrap();
+
+            cmd.arg("create").arg("Iris Classifier");
+            cmd.assert().failure().stderr(predicate::str::contains(
+                "contains a space, try iris_classifier",
+            ));
+            assert!(!path::Path::new("Iris Classifier").exists());
+        },
+        false,
+    )
+}
@@ -0,0 +1,141 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::io::{BufRead, BufReader, Write};
+use std::{env, fs, net, process, thread};
+use test_fixtures::test_in_tmp_dir;
+
+/// Serves iris.csv with an ETag, answering conditional requests with a 304
+fn serve_iris(requests: usize) -> String {
+    let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    thread::spawn(move || {
+        for stream in listener.incoming().take(requests) {
+            let mut stream = stream.unwrap();
+            let mut conditional = false;
+
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+
+                if line.to_lowercase().starts_with("if-none-match: \"v1\"") {
+                    conditional = true;
+                }
+                if line.trim().is_empty() {
+                    break;
+                }
+            }
+
+            let response = if conditional {
+                String::from("HTTP/1.1 304 Not Modified\r\nContent-Length: 0\r\n\r\n")
+            } else {
+                String::from(
+                    "HTTP/1.1 200 OK\r\n\
+                    ETag: \"v1\"\r\n\
+                    Last-Modified: Fri, 01 Apr 2022 00:00:00 GMT\r\n\
+                    Content-Length: 9\r\n\
+                    \r\n\
+                    id\n1\n2\n3\n",
+                )
+            };
+
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+
+    format!("http://{}/iris.csv", address)
+}
+
+fn setup_web_data_source() {
+    fs::create_dir_all("./test_project/data_sources").unwrap();
+    env::set_current_dir("test_project").unwrap();
+
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+    cmd.arg("add")
+        .arg("data-source")
+        .arg("--kind=web")
+        .arg("iris");
+    cmd.assert().success();
+}
+
+#[test]
+fn fetches_file_over_http() {
+    test_in_tmp_dir(
+        || {
+            setup_web_data_source();
+            let url = serve_iris(1);
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("data-source").arg("fetch").arg("iris").arg(&url);
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("iris.csv downloaded!"));
+
+            assert_eq!(
+                fs::read_to_string("data_sources/iris/iris.csv").unwrap(),
+                "id\n1\n2\n3\n"
+            );
+
+            let metadata = fs::read_to_string("data_sources/iris/metadata.md").unwrap();
+            assert!(metadata.contains(&format!("| iris.csv | {} |", url)));
+            assert!(metadata.contains(
+                "ETag: \"v1\", Last-Modified: Fri, 01 Apr 2022 00:00:00 GMT, size: 9 bytes"
+            ));
+
+            let manifest = fs::read_to_string("geoff.toml").unwrap();
+            assert!(manifest.contains("[[data_sources.iris.downloads]]"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn refresh_skips_unchanged_file() {
+    test_in_tmp_dir(
+        || {
+            setup_web_data_source();
+            let url = serve_iris(2);
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("data-source").arg("fetch").arg("iris").arg(&url);
+            cmd.assert().success();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("data-source")
+                .arg("fetch")
+                .arg("--refresh")
+                .arg("iris")
+                .arg(&url);
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("iris.csv is up to date"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn fetches_file_url() {
+    test_in_tmp_dir(
+        || {
+            setup_web_data_source();
+            fs::write("../iris.data", "5.1,3.5\n").unwrap();
+            let url = format!(
+                "file://{}",
+                env::current_dir().unwrap().join("../iris.data").display()
+            );
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("data-source").arg("fetch").arg("iris").arg(&url);
+            cmd.assert().success();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("data-source").arg("fetch").arg("iris").arg(&url);
+            cmd.assert()
+                .failure()
+                .stderr(predicate::str::contains("pass `--refresh`"));
+        },
+        false,
+    )
+}
@@ -0,0 +1,128 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{env, fs, path, process};
+use test_fixtures::test_in_tmp_dir;
+
+fn setup_extract_data_source() {
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+    cmd.arg("create").arg("test_project");
+    cmd.assert().success();
+
+    env::set_current_dir("test_project").unwrap();
+
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+    cmd.arg("add")
+        .arg("data-source")
+        .arg("--kind=extract")
+        .arg("iris");
+    cmd.assert().success();
+}
+
+fn receive(file: &str, date: &str) -> assert_cmd::assert::Assert {
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+
+    cmd.arg("data-source")
+        .arg("receive")
+        .arg("iris")
+        .arg(file)
+        .arg("--date")
+        .arg(date)
+        .arg("--diff");
+
+    cmd.assert()
+}
+
+#[test]
+fn receives_new_version() {
+    test_in_tmp_dir(
+        || {
+            setup_extract_data_source();
+            fs::write("../iris.csv", "id,sepal_width\n1,3.5\n").unwrap();
+
+            receive("../iris.csv", "2022-04-01")
+                .success()
+                .stdout(predicate::str::contains(
+                    "versions/2022-04-01/iris.csv received!",
+                ))
+                .stdout(predicate::str::contains("No previous version"));
+
+            assert!(path::Path::new("data_sources/iris/versions/2022-04-01/iris.csv").exists());
+
+            let manifest = fs::read_to_string("geoff.toml").unwrap();
+            assert!(manifest.contains("[[data_sources.iris.versions]]"));
+            assert!(manifest.contains("rows = 1"));
+
+            let metadata = fs::read_to_string("data_sources/iris/metadata.md").unwrap();
+            assert!(metadata.contains("| versions/2022-04-01/iris.csv |  |  | 2022-04-01 |"));
+            assert!(metadata.contains("refreshed: 2022-04-01\n---\n"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn shows_schema_diff_with_previous_version() {
+    test_in_tmp_dir(
+        || {
+            setup_extract_data_source();
+            fs::write("../iris.csv", "id,sepal_width\n1,3.5\n").unwrap();
+            receive("../iris.csv", "2022-04-01").success();
+
+            fs::write("../iris.csv", "id,petal_width\n1,0.2\n").unwrap();
+            receive("../iris.csv", "2022-05-01")
+                .success()
+                .stdout(predicate::str::contains("+ petal_width"))
+                .stdout(predicate::str::contains("- sepal_width"));
+
+            let metadata = fs::read_to_string("data_sources/iris/metadata.md").unwrap();
+            assert!(metadata.contains(
+                "| versions/2022-04-01/iris.csv |  |  | 2022-04-01 |\n\
+                | versions/2022-05-01/iris.csv |  |  | 2022-05-01 |"
+            ));
+        },
+        false,
+    )
+}
+
+#[test]
+fn errors_on_unknown_data_source() {
+    test_in_tmp_dir(
+        || {
+            setup_extract_data_source();
+            fs::write("../iris.csv", "id\n1\n").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("data-source")
+                .arg("receive")
+                .arg("penguins")
+                .arg("../iris.csv");
+
+            cmd.assert().failure().stderr(predicate::str::contains(
+                "isn't a component in this project",
+            ));
+        },
+        false,
+    )
+}
+
+#[test]
+fn errors_on_data_source_outside_project() {
+    test_in_tmp_dir(
+        || {
+            setup_extract_data_source();
+            fs::write("../iris.csv", "id\n1\n").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("data-source")
+                .arg("receive")
+                .arg("..")
+                .arg("../iris.csv");
+
+            cmd.assert().failure().stderr(predicate::str::contains(
+                "isn't a component in this project",
+            ));
+            assert!(!path::Path::new("versions").exists());
+        },
+        false,
+    )
+}
@@ -0,0 +1,207 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{env, fs, process};
+use test_fixtures::test_in_tmp_dir;
+
+fn setup_project() {
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+    cmd.arg("create").arg("test_project");
+    cmd.assert().success();
+
+    env::set_current_dir("test_project").unwrap();
+
+    let commands: [&[&str]; 3] = [
+        &["add", "data-source", "iris"],
+        &["add", "exploration", "eda", "--uses", "data_sources/iris"],
+        &["add", "model", "classifier"],
+    ];
+    for args in commands.iter() {
+        let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+        cmd.args(args.iter());
+        cmd.assert().success();
+    }
+}
+
+#[test]
+fn link_records_dependencies() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("link").arg("classifier").arg("--uses").arg("eda");
+
+            cmd.assert().success().stdout(predicate::str::contains(
+                "models/classifier uses explorations/eda",
+            ));
+
+            let manifest = fs::read_to_string("geoff.toml").unwrap();
+            assert!(manifest.contains("\"models/classifier\" = ['explorations/eda']"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn link_rejects_cycles() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("link").arg("iris").arg("--uses").arg("eda");
+
+            cmd.assert().failure().stderr(predicate::str::contains(
+                "can't use a component that already uses it",
+            ));
+        },
+        false,
+    )
+}
+
+#[test]
+fn link_resolves_start_of_name() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("link")
+                .arg("class")
+                .arg("--uses")
+                .arg("explorations/ed");
+
+            cmd.assert().success().stdout(predicate::str::contains(
+                "models/classifier uses explorations/eda",
+            ));
+        },
+        false,
+    )
+}
+
+#[test]
+fn link_suggests_similar_names() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("link").arg("clasifier").arg("--uses").arg("eda");
+
+            cmd.assert().failure().stderr(predicate::str::contains(
+                "clasifier isn't a component in this project, did you mean models/classifier?",
+            ));
+            assert!(!fs::read_to_string("geoff.toml")
+                .unwrap()
+                .contains("\"models/classifier\" ="));
+        },
+        false,
+    )
+}
+
+#[test]
+fn link_requires_uses() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("link").arg("classifier");
+
+            cmd.assert().failure();
+        },
+        false,
+    )
+}
+
+#[test]
+fn graph_outputs_dot() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("graph");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::starts_with("digraph lineage {"))
+                .stdout(predicate::str::contains(
+                    "\"models/classifier\" [label=\"classifier\"];",
+                ))
+                .stdout(predicate::str::contains(
+                    "\"data_sources/iris\" -> \"explorations/eda\";",
+                ));
+        },
+        false,
+    )
+}
+
+#[test]
+fn graph_outputs_mermaid() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("graph").arg("--format=mermaid");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::starts_with("flowchart LR"))
+                .stdout(predicate::str::contains("n0[\"iris\"]"))
+                .stdout(predicate::str::contains("n0 --> n1"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn impact_lists_downstream_components() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("link").arg("classifier").arg("--uses").arg("eda");
+            cmd.assert().success();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("impact").arg("iris");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains(
+                    "2 components depend on data_sources/iris",
+                ))
+                .stdout(predicate::str::contains("explorations/eda"))
+                .stdout(predicate::str::contains("models/classifier"));
+
+            let readme = fs::read_to_string("models/classifier/README.md").unwrap();
+            assert!(!readme.contains("needs_review"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn impact_marks_components_for_review() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("impact")
+                .arg("data_sources/iris")
+                .arg("--mark-review");
+
+            cmd.assert().success().stdout(predicate::str::contains(
+                "1 components marked as needing review",
+            ));
+
+            let readme = fs::read_to_string("explorations/eda/README.md").unwrap();
+            assert!(readme.contains("needs_review: data_sources/iris changed on "));
+        },
+        false,
+    )
+}
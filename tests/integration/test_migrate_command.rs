@@ -0,0 +1,84 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{fs, path, process};
+use test_fixtures::test_in_tmp_dir;
+
+fn setup_old_project() {
+    fs::write(".geoff", "").unwrap();
+    fs::write(
+        "README.md",
+        "# iris\n\nData sources\n* data source 1\n* data source 2\n",
+    )
+    .unwrap();
+    fs::create_dir_all("data_sources/iris").unwrap();
+    fs::write("data_sources/iris/metadata.md", "# iris\n").unwrap();
+}
+
+#[test]
+fn migrate_upgrades_old_project() {
+    test_in_tmp_dir(
+        || {
+            setup_old_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("migrate");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("Add front matter"))
+                .stdout(predicate::str::contains("Migrated from format 0 to 2"));
+
+            assert_eq!(fs::read_to_string(".geoff").unwrap(), "format = 2\n");
+            assert!(fs::read_to_string("README.md")
+                .unwrap()
+                .contains("* [iris](data_sources/iris/metadata.md)\n"));
+            assert!(path::Path::new(".geoff_backup").is_dir());
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("migrate");
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("already at format 2"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn migrate_dry_run_changes_nothing() {
+    test_in_tmp_dir(
+        || {
+            setup_old_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("migrate").arg("--dry-run");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("data_sources/iris/metadata.md"))
+                .stdout(predicate::str::contains("Dry run, nothing was changed"));
+
+            assert_eq!(fs::read_to_string(".geoff").unwrap(), "");
+            assert!(!path::Path::new(".geoff_backup").exists());
+        },
+        false,
+    )
+}
+
+#[test]
+fn new_projects_dont_need_migrating() {
+    test_in_tmp_dir(
+        || {
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("create").arg("test_project");
+            cmd.assert().success();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("migrate").current_dir("test_project");
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("already at format 2"));
+        },
+        false,
+    )
+}
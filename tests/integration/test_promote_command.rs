@@ -0,0 +1,87 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{env, fs, path, process};
+use test_fixtures::test_in_tmp_dir;
+
+fn setup_project() {
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+    cmd.arg("create").arg("test_project");
+    cmd.assert().success();
+
+    env::set_current_dir("test_project").unwrap();
+
+    let commands: [&[&str]; 2] = [
+        &["add", "data-source", "iris"],
+        &["add", "exploration", "eda", "--uses", "iris"],
+    ];
+    for args in commands.iter() {
+        let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+        cmd.args(args.iter());
+        cmd.assert().success();
+    }
+
+    fs::write("explorations/eda/eda.ipynb", "{}").unwrap();
+}
+
+#[test]
+fn promote_creates_model_from_exploration() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args([
+                "promote",
+                "exploration",
+                "eda",
+                "--to",
+                "model",
+                "classifier",
+                "--copy",
+                "eda.ipynb",
+            ]);
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains(
+                    "explorations/eda promoted to models/classifier!",
+                ))
+                .stdout(predicate::str::contains("eda.ipynb"));
+
+            assert!(path::Path::new("models/classifier/eda.ipynb").exists());
+            assert!(fs::read_to_string("models/classifier/README.md")
+                .unwrap()
+                .contains("## Provenance"));
+
+            let manifest = fs::read_to_string("geoff.toml").unwrap();
+            assert!(manifest.contains("\"models/classifier\" = ['data_sources/iris']"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn promote_errors_for_missing_exploration() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args([
+                "promote",
+                "exploration",
+                "clustering",
+                "--to",
+                "model",
+                "classifier",
+            ]);
+
+            cmd.assert().failure().stderr(predicate::str::contains(
+                "isn't a component in this project",
+            ));
+
+            assert!(!path::Path::new("models/classifier").exists());
+        },
+        false,
+    )
+}
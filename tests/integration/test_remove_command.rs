@@ -0,0 +1,218 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{env, fs, path, process};
+use test_fixtures::test_in_tmp_dir;
+
+fn setup_project() {
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+    cmd.arg("create").arg("test_project");
+    cmd.assert().success();
+
+    env::set_current_dir("test_project").unwrap();
+
+    let commands: [&[&str]; 2] = [
+        &["add", "data-source", "iris"],
+        &["add", "exploration", "eda", "--uses", "iris"],
+    ];
+    for args in commands.iter() {
+        let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+        cmd.args(args.iter());
+        cmd.assert().success();
+    }
+}
+
+#[test]
+fn remove_shows_files_and_moves_to_trash() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = assert_cmd::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("remove")
+                .arg("data-source")
+                .arg("iris")
+                .write_stdin("y\n");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("metadata.md"))
+                .stdout(predicate::str::contains(
+                    "It is still used by explorations/eda",
+                ))
+                .stdout(predicate::str::contains(
+                    "data_sources/iris moved to .trash/data_sources/iris",
+                ));
+
+            assert!(!path::Path::new("data_sources/iris").exists());
+            assert!(path::Path::new(".trash/data_sources/iris/metadata.md").exists());
+
+            let manifest = fs::read_to_string("geoff.toml").unwrap();
+            assert!(!manifest.contains("[dependencies]"));
+            assert!(manifest.contains("[[trash]]"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn remove_does_nothing_without_confirmation() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = assert_cmd::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("remove")
+                .arg("exploration")
+                .arg("eda")
+                .write_stdin("n\n");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("Nothing was removed"));
+
+            assert!(path::Path::new("explorations/eda").exists());
+        },
+        false,
+    )
+}
+
+#[test]
+fn remove_asks_before_removing_prefix_match() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = assert_cmd::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("remove")
+                .arg("--yes")
+                .arg("exploration")
+                .arg("ed")
+                .write_stdin("n\n");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("ed matches explorations/eda"))
+                .stdout(predicate::str::contains("Nothing was removed"));
+
+            assert!(path::Path::new("explorations/eda").exists());
+        },
+        false,
+    )
+}
+
+#[test]
+fn trash_restore_puts_component_back() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("remove")
+                .arg("--yes")
+                .arg("data-source")
+                .arg("iris");
+            cmd.assert().success();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("trash").arg("list");
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("data_sources/iris removed on"));
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("trash")
+                .arg("restore")
+                .arg("data-source")
+                .arg("iris");
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("data_sources/iris restored!"));
+
+            assert!(path::Path::new("data_sources/iris/metadata.md").exists());
+
+            let manifest = fs::read_to_string("geoff.toml").unwrap();
+            assert!(manifest.contains("\"explorations/eda\" = ['data_sources/iris']"));
+            assert!(!manifest.contains("[[trash]]"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn trash_empty_deletes_permanently() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("remove").arg("--yes").arg("exploration").arg("eda");
+            cmd.assert().success();
+
+            let mut cmd = assert_cmd::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("trash").arg("empty").write_stdin("y\n");
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("1 components permanently deleted"));
+
+            assert!(!path::Path::new(".trash").exists());
+        },
+        false,
+    )
+}
+
+#[test]
+fn remove_suggests_similar_names() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = assert_cmd::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["remove", "data-source", "irsi", "--yes"]);
+
+            cmd.assert().failure().stderr(predicate::str::contains(
+                "data_sources/irsi isn't a component in this project, did you mean data_sources/iris?",
+            ));
+            assert!(path::Path::new("data_sources/iris").is_dir());
+        },
+        false,
+    )
+}
+
+#[test]
+fn removes_and_restores_archived_component() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["archive", "exploration", "eda"]);
+            cmd.assert().success();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["remove", "--yes", "exploration", "eda"]);
+            cmd.assert()
+                .failure()
+                .stderr(predicate::str::contains("pass --archived to remove it"));
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["remove", "--yes", "--archived", "exploration", "eda"]);
+            cmd.assert().success().stdout(predicate::str::contains(
+                "explorations/eda moved to .trash/explorations/eda",
+            ));
+
+            assert!(!path::Path::new("explorations/_archive/eda").exists());
+            let manifest = fs::read_to_string("geoff.toml").unwrap();
+            assert!(!manifest.contains("[archived"));
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["trash", "restore", "exploration", "eda"]);
+            cmd.assert().success();
+
+            assert!(path::Path::new("explorations/_archive/eda/README.md").exists());
+            assert!(!path::Path::new("explorations/eda").exists());
+            let manifest = fs::read_to_string("geoff.toml").unwrap();
+            assert!(manifest.contains("[archived"));
+        },
+        false,
+    )
+}
@@ -0,0 +1,171 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{env, fs, path, process};
+use test_fixtures::test_in_tmp_dir;
+
+fn setup_project() {
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+    cmd.arg("create").arg("test_project");
+    cmd.assert().success();
+
+    env::set_current_dir("test_project").unwrap();
+
+    let commands: [&[&str]; 2] = [
+        &["add", "data-source", "iris"],
+        &["add", "exploration", "eda", "--uses", "iris"],
+    ];
+    for args in commands.iter() {
+        let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+        cmd.args(args.iter());
+        cmd.assert().success();
+    }
+}
+
+#[test]
+fn rename_moves_component_and_updates_manifest() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("rename")
+                .arg("data-source")
+                .arg("iris")
+                .arg("flowers");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains(
+                    "data_sources/iris renamed to data_sources/flowers!",
+                ))
+                .stdout(predicate::str::contains("data_sources/flowers/metadata.md"));
+
+            assert!(!path::Path::new("data_sources/iris").exists());
+
+            let metadata = fs::read_to_string("data_sources/flowers/metadata.md").unwrap();
+            assert!(metadata.contains("name: flowers\n"));
+            assert!(metadata.contains("# flowers\n"));
+
+            let manifest = fs::read_to_string("geoff.toml").unwrap();
+            assert!(manifest.contains("\"explorations/eda\" = ['data_sources/flowers']"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn rename_errors_when_name_taken() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["add", "exploration", "clustering"]);
+            cmd.assert().success();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("rename")
+                .arg("exploration")
+                .arg("eda")
+                .arg("clustering");
+
+            cmd.assert()
+                .failure()
+                .stderr(predicate::str::contains("already exists"));
+
+            assert!(path::Path::new("explorations/eda/README.md").exists());
+        },
+        false,
+    )
+}
+
+#[test]
+fn rename_project_moves_folder_and_rewrites_headings() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("rename-project").arg("flowers");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("test_project renamed to flowers!"))
+                .stdout(predicate::str::contains("please change to it"));
+
+            env::set_current_dir("../flowers").unwrap();
+
+            let readme = fs::read_to_string("README.md").unwrap();
+            assert!(readme.contains("project: flowers\n"));
+            assert!(readme.contains("# flowers\n"));
+            assert!(fs::read_to_string("project_scoping.md")
+                .unwrap()
+                .starts_with("---\nproject: flowers\n"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn renumber_closes_gaps_and_updates_references() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+            fs::write(
+                "geoff.toml",
+                format!(
+                    "{}\n[naming.prefixes]\nexplorations = \"number\"\n",
+                    fs::read_to_string("geoff.toml").unwrap()
+                ),
+            )
+            .unwrap();
+
+            for name in ["churn", "age"] {
+                let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+                cmd.args(["add", "exploration", name, "--uses", "iris"]);
+                cmd.assert().success();
+            }
+            assert!(path::Path::new("explorations/002_age").is_dir());
+
+            fs::remove_dir_all("explorations/001_churn").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("renumber").arg("exploration");
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("1 explorations renumbered!"))
+                .stdout(predicate::str::contains("001_age (was 002_age)"));
+
+            assert!(path::Path::new("explorations/001_age/README.md").exists());
+            assert!(fs::read_to_string("README.md")
+                .unwrap()
+                .contains("explorations/001_age/README.md"));
+            assert!(fs::read_to_string("geoff.toml")
+                .unwrap()
+                .contains("\"explorations/001_age\" = ['data_sources/iris']"));
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("renumber").arg("exploration");
+            cmd.assert().success().stdout(predicate::str::contains(
+                "Nothing to renumber, the explorations are already in order",
+            ));
+        },
+        false,
+    )
+}
+
+#[test]
+fn renumber_errors_when_stage_isnt_numbered() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("renumber").arg("model");
+            cmd.assert()
+                .failure()
+                .stderr(predicate::str::contains("models aren't numbered"));
+        },
+        false,
+    )
+}
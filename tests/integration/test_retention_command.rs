@@ -0,0 +1,187 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{env, fs, path, process};
+use test_fixtures::test_in_tmp_dir;
+
+fn add_data_source(name: &str, expires: &str) {
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+    cmd.arg("add")
+        .arg("data-source")
+        .arg("--kind=extract")
+        .arg("--expires")
+        .arg(expires)
+        .arg(name);
+    cmd.assert().success();
+
+    fs::write(format!("data_sources/{}/{}.csv", name, name), "id\n1\n").unwrap();
+}
+
+#[test]
+fn check_lists_expired_data_sources() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            add_data_source("iris", "2000-01-01");
+            add_data_source("penguins", "2999-01-01");
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("retention").arg("check");
+
+            cmd.assert()
+                .failure()
+                .stdout(predicate::str::contains("expired:"))
+                .stdout(predicate::str::contains("iris on 2000-01-01"))
+                .stdout(predicate::str::contains("penguins").not());
+        },
+        false,
+    )
+}
+
+#[test]
+fn check_passes_when_nothing_expires() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            add_data_source("penguins", "2999-01-01");
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("retention").arg("check").arg("--within=7");
+
+            cmd.assert().success().stdout(predicate::str::contains(
+                "No data sources expire within 7 days",
+            ));
+        },
+        false,
+    )
+}
+
+#[test]
+fn purge_deletes_data_files_after_confirmation() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            add_data_source("iris", "2000-01-01");
+
+            let mut cmd = assert_cmd::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("retention")
+                .arg("purge")
+                .arg("iris")
+                .write_stdin("y\n");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("1 data files purged from iris!"));
+
+            assert!(path::Path::new("data_sources/iris/metadata.md").exists());
+            assert!(!path::Path::new("data_sources/iris/iris.csv").exists());
+
+            let manifest = fs::read_to_string("geoff.toml").unwrap();
+            assert!(manifest.contains("[[data_sources.iris.purges]]"));
+            assert!(manifest.contains("expired = '2000-01-01'"));
+            assert!(manifest.contains("files = ['iris.csv']"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn purge_keeps_files_when_not_confirmed() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            add_data_source("iris", "2000-01-01");
+
+            let mut cmd = assert_cmd::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("retention")
+                .arg("purge")
+                .arg("iris")
+                .write_stdin("n\n");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("Nothing was deleted"));
+
+            assert!(path::Path::new("data_sources/iris/iris.csv").exists());
+            assert!(!fs::read_to_string("geoff.toml").unwrap().contains("purges"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn purge_without_prompt() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            add_data_source("iris", "2000-01-01");
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("retention").arg("purge").arg("--yes").arg("iris");
+
+            cmd.assert().success();
+
+            assert!(!path::Path::new("data_sources/iris/iris.csv").exists());
+        },
+        false,
+    )
+}
+
+#[test]
+fn purge_without_data_files_logs_nothing() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            add_data_source("iris", "2000-01-01");
+            fs::remove_file("data_sources/iris/iris.csv").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("retention").arg("purge").arg("--yes").arg("iris");
+
+            cmd.assert().success().stdout(predicate::str::contains(
+                "Nothing to purge, iris has no data files",
+            ));
+
+            assert!(!fs::read_to_string("geoff.toml").unwrap().contains("purges"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn purge_asks_before_purging_prefix_match() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            add_data_source("iris", "2000-01-01");
+
+            let mut cmd = assert_cmd::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("retention")
+                .arg("purge")
+                .arg("--yes")
+                .arg("ir")
+                .write_stdin("n\n");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("ir matches data_sources/iris"))
+                .stdout(predicate::str::contains("Nothing was deleted"));
+
+            assert!(path::Path::new("data_sources/iris/iris.csv").exists());
+        },
+        false,
+    )
+}
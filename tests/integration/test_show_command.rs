@@ -0,0 +1,88 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{env, fs, process};
+use test_fixtures::test_in_tmp_dir;
+
+#[test]
+fn shows_data_source_as_json() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("add")
+                .arg("data-source")
+                .arg("--kind=database")
+                .arg("iris");
+            cmd.assert().success();
+
+            let metadata = fs::read_to_string("data_sources/iris/metadata.md").unwrap();
+            fs::write(
+                "data_sources/iris/metadata.md",
+                metadata.replace(
+                    "| *e.g ID* | *e.g INT* | *e.g A unique identifier for each record* | *e.g no* |",
+                    "| id | INT | A unique identifier for each record | no |",
+                ),
+            )
+            .unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("show")
+                .arg("data-source")
+                .arg("iris")
+                .arg("--format")
+                .arg("json");
+
+            let output = cmd.assert().success().get_output().stdout.clone();
+            let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+            assert_eq!(json["title"], "iris");
+            assert_eq!(json["front_matter"]["kind"], "database");
+            assert_eq!(json["data_dictionary"][0]["field"], "id");
+            assert_eq!(json["data_dictionary"][0]["data_type"], "INT");
+        },
+        false,
+    )
+}
+
+#[test]
+fn errors_on_unknown_data_source() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources/iris").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("show").arg("data-source").arg("irsi");
+
+            cmd.assert().failure().stderr(predicate::str::contains(
+                "isn't a component in this project, did you mean data_sources/iris?",
+            ));
+        },
+        false,
+    )
+}
+
+#[test]
+fn shows_data_source_from_start_of_name() {
+    test_in_tmp_dir(
+        || {
+            fs::create_dir_all("./test_project/data_sources").unwrap();
+            env::set_current_dir("test_project").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["add", "data-source", "iris"]);
+            cmd.assert().success();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["show", "data-source", "ir", "--format", "json"]);
+
+            let output = cmd.assert().success().get_output().stdout.clone();
+            let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+            assert_eq!(json["title"], "iris");
+        },
+        false,
+    )
+}
@@ -0,0 +1,93 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{env, fs, process};
+use test_fixtures::test_in_tmp_dir;
+
+fn setup_project() {
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+    cmd.arg("create").arg("test_project");
+    cmd.assert().success();
+
+    env::set_current_dir("test_project").unwrap();
+}
+
+fn geoff(args: &[&str]) {
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+    cmd.args(args.iter());
+    cmd.assert().success();
+}
+
+#[test]
+fn readme_lists_follow_add_rename_and_remove() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            geoff(&["add", "data-source", "iris"]);
+            geoff(&["add", "model", "classifier"]);
+
+            let readme = fs::read_to_string("README.md").unwrap();
+            assert!(readme.contains("* [iris](data_sources/iris/metadata.md)\n"));
+            assert!(readme.contains("* [classifier](models/classifier/README.md)\n"));
+
+            geoff(&["rename", "model", "classifier", "predictor"]);
+            geoff(&["remove", "--yes", "data-source", "iris"]);
+
+            let readme = fs::read_to_string("README.md").unwrap();
+            assert!(readme.contains("* [predictor](models/predictor/README.md)\n"));
+            assert!(!readme.contains("classifier"));
+            assert!(!readme.contains("iris"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn sync_readme_keeps_text_outside_markers() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            geoff(&["add", "exploration", "eda"]);
+
+            let readme = fs::read_to_string("README.md")
+                .unwrap()
+                .replace(
+                    "<!-- geoff:begin explorations -->\n",
+                    "<!-- geoff:begin explorations -->\n* stale\n",
+                )
+                .replace("## Contributors", "## Contributors\nRonald Fisher");
+            fs::write("README.md", readme).unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("sync").arg("readme");
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("4 sections in README.md synced"));
+
+            let readme = fs::read_to_string("README.md").unwrap();
+            assert!(!readme.contains("* stale"));
+            assert!(readme.contains("* [eda](explorations/eda/README.md)\n"));
+            assert!(readme.contains("## Contributors\nRonald Fisher"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn sync_readme_errors_without_markers() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            fs::write("README.md", "# test_project\n").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("sync").arg("readme");
+            cmd.assert().failure().stderr(predicate::str::contains(
+                "doesn't have any sections managed by geoff",
+            ));
+        },
+        false,
+    )
+}
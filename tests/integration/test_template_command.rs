@@ -0,0 +1,129 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{env, fs, process};
+use test_fixtures::test_in_tmp_dir;
+
+/// Makes the exploration look like it was generated from an older template
+/// without the Approach section
+fn age_exploration() {
+    let readme = fs::read_to_string("explorations/eda/README.md").unwrap();
+    let approach = readme.find("## Approach").unwrap();
+    let findings = readme.find("## Findings").unwrap();
+    let older = format!("{}{}", &readme[..approach], &readme[findings..])
+        .replace("*What was learnt and what should happen next*", "It works");
+    fs::write("explorations/eda/README.md", older).unwrap();
+
+    let template = include_str!("../../src/templates/explorations/README.md");
+    let approach = template.find("## Approach").unwrap();
+    let findings = template.find("## Findings").unwrap();
+    fs::write(
+        ".geoff_templates/old.md",
+        format!("{}{}", &template[..approach], &template[findings..]),
+    )
+    .unwrap();
+
+    let manifest = fs::read_to_string("geoff.toml").unwrap();
+    let start = manifest
+        .find("[templates.\"explorations/eda/README.md\"]")
+        .unwrap();
+    let version = start + manifest[start..].find("version = '").unwrap() + 11;
+    let end = version + manifest[version..].find('\'').unwrap();
+    fs::write(
+        "geoff.toml",
+        format!("{}old{}", &manifest[..version], &manifest[end..]),
+    )
+    .unwrap();
+}
+
+fn setup_project() {
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+    cmd.arg("create").arg("test_project");
+    cmd.assert().success();
+
+    env::set_current_dir("test_project").unwrap();
+
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+    cmd.args(["add", "exploration", "eda"]);
+    cmd.assert().success();
+
+    age_exploration();
+}
+
+#[test]
+fn template_update_merges_new_sections() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("template").arg("update");
+
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains(
+                    "explorations/eda/README.md merged",
+                ))
+                .stdout(predicate::str::contains("README.md up to date"));
+
+            let readme = fs::read_to_string("explorations/eda/README.md").unwrap();
+            assert!(readme.contains("## Approach"));
+            assert!(readme.contains("## Findings\nIt works"));
+            assert!(!fs::read_to_string("geoff.toml")
+                .unwrap()
+                .contains("version = 'old'"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn template_update_dry_run_changes_nothing() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+            let readme = fs::read_to_string("explorations/eda/README.md").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("template").arg("update").arg("--dry-run");
+
+            cmd.assert().success().stdout(predicate::str::contains(
+                "explorations/eda/README.md merged",
+            ));
+
+            assert_eq!(
+                fs::read_to_string("explorations/eda/README.md").unwrap(),
+                readme
+            );
+        },
+        false,
+    )
+}
+
+#[test]
+fn template_update_after_rename_keeps_new_name() {
+    test_in_tmp_dir(
+        || {
+            setup_project();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.args(["rename", "exploration", "eda", "churn"]);
+            cmd.assert().success();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("template").arg("update");
+
+            cmd.assert().success().stdout(predicate::str::contains(
+                "explorations/churn/README.md merged",
+            ));
+
+            let readme = fs::read_to_string("explorations/churn/README.md").unwrap();
+            assert!(readme.contains("# churn\n"));
+            assert!(readme.contains("## Approach"));
+            assert!(!readme.contains("eda"));
+            assert!(fs::read_to_string("geoff.toml")
+                .unwrap()
+                .contains("name = 'churn'"));
+        },
+        false,
+    )
+}
@@ -0,0 +1,71 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{env, fs, process};
+use test_fixtures::test_in_tmp_dir;
+
+fn setup_locked_data_source() {
+    fs::create_dir_all("./test_project/data_sources/iris").unwrap();
+    env::set_current_dir("test_project").unwrap();
+    fs::write(".geoff", "").unwrap();
+    fs::write("data_sources/iris/metadata.md", "# iris\n").unwrap();
+    fs::write("data_sources/iris/iris.csv", "id\n1\n").unwrap();
+
+    let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+    cmd.arg("data-source").arg("lock").arg("iris");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("1 data files locked in iris!"));
+}
+
+#[test]
+fn lock_records_checksums_in_manifest() {
+    test_in_tmp_dir(
+        || {
+            setup_locked_data_source();
+
+            let manifest = fs::read_to_string("geoff.toml").unwrap();
+            assert!(manifest.contains("[data_sources.iris.checksums]"));
+            assert!(manifest.contains("\"iris.csv\" = "));
+        },
+        false,
+    )
+}
+
+#[test]
+fn verify_passes_for_unchanged_files() {
+    test_in_tmp_dir(
+        || {
+            setup_locked_data_source();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("verify");
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::contains("All data files match"));
+        },
+        false,
+    )
+}
+
+#[test]
+fn verify_fails_for_changed_files() {
+    test_in_tmp_dir(
+        || {
+            setup_locked_data_source();
+            fs::write("data_sources/iris/iris.csv", "id\n2\n").unwrap();
+            fs::write("data_sources/iris/notes.txt", "extra").unwrap();
+
+            let mut cmd = process::Command::cargo_bin("geoff").unwrap();
+            cmd.arg("verify");
+            cmd.assert()
+                .failure()
+                .stdout(predicate::str::contains(
+                    "modified: data_sources/iris/iris.csv",
+                ))
+                .stdout(predicate::str::contains(
+                    "untracked: data_sources/iris/notes.txt",
+                ));
+        },
+        false,
+    )
+}